@@ -0,0 +1,44 @@
+#!/usr/bin/env python3
+
+# compile-dictionary.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+# Turns a JSON dictionary into the indexed file the app loads at runtime.
+# Usage: compile-dictionary.py INPUT.json OUTPUT.db [METADATA.json]
+#
+# METADATA.json provides source and license details for dictionaries
+# that don't carry their own.
+
+import json
+import os
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(__file__), '..', 'src'))
+
+from core import Dictionary, compile_dictionary
+
+if __name__ == '__main__':
+    input_path, output_path = sys.argv[1:3]
+    lang = os.path.splitext(os.path.basename(input_path))[0]
+    with open(input_path, 'rb') as f:
+        dictionary = Dictionary.parse(lang, f.read())
+    if len(sys.argv) > 3:
+        with open(sys.argv[3], encoding='utf-8') as f:
+            dictionary.metadata = {**json.load(f), **dictionary.metadata}
+    compile_dictionary(dictionary, output_path)
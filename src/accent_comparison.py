@@ -0,0 +1,86 @@
+# accent_comparison.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gtk
+from . import languages
+from .core import phonemes
+
+# names linguists give the accents of the bundled dictionaries
+ACCENT_LABELS = {
+    "en_US": "GenAm",
+    "en_UK": "RP",
+}
+
+
+def accent_languages():
+    """Codes of the English dictionaries, bundled or downloaded."""
+    return [code for code, _name in languages.available() if code.split("_")[0] == "en"]
+
+
+def accent_label(code):
+    return ACCENT_LABELS.get(code, languages.display_name(code))
+
+
+def compared_accents(settings, code):
+    """The accents the user wants code compared with, or [] if code
+    isn't one of them.
+    """
+    chosen = [lang for lang in settings.get_strv('accents') if lang in accent_languages()]
+    if code not in chosen:
+        return []
+    return [lang for lang in chosen if lang != code]
+
+
+class Word2ipaAccentComparison(Gtk.Grid):
+    """Transcriptions of a word in several accents, aligned sound by
+    sound with the first one.
+    """
+    __gtype_name__ = 'Word2ipaAccentComparison'
+
+    def __init__(self, **kwargs):
+        super().__init__(column_spacing=8, row_spacing=4,
+                         halign=Gtk.Align.CENTER, **kwargs)
+
+    def clear(self):
+        while child := self.get_first_child():
+            self.remove(child)
+
+    def compare(self, accents):
+        """Show a row for each (lang, ipa); sounds differing from the
+        first row are highlighted.
+        """
+        self.clear()
+        # compare the main variant of each
+        first = phonemes.variants(accents[0][1])[0]
+        for column, sound in enumerate(phonemes.split(first), 1):
+            self.attach(self.sound_label(sound, False), column, 0, 1, 1)
+
+        for row, (lang, ipa) in enumerate(accents):
+            self.attach(Gtk.Label(label=accent_label(lang), xalign=0,
+                                  css_classes=["heading"]), 0, row, 1, 1)
+            if row == 0:
+                continue
+            # one cell per sound of the first accent
+            cells = phonemes.align_cells(first, phonemes.variants(ipa)[0])
+            for column, (a, b) in enumerate(zip(phonemes.split(first), cells), 1):
+                self.attach(self.sound_label(b, a != b), column, row, 1, 1)
+
+    def sound_label(self, sound, differs):
+        css_classes = ["title-3", "accent"] if differs else ["title-3"]
+        return Gtk.Label(label=sound or "–", css_classes=css_classes)
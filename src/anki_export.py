@@ -0,0 +1,162 @@
+# anki_export.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Adw, GLib, Gtk
+import hashlib
+import os
+import shutil
+import threading
+from . import export, forvo, languages, speech
+from .favorites import Favorites
+from .history import History
+
+
+def media_name(word, lang, extension):
+    """A file name for the sound of word that won't clash in Anki's media folder."""
+    digest = hashlib.sha1(f"{lang}:{word}".encode("utf-8")).hexdigest()[:12]
+    return f"word2ipa-{digest}.{extension}"
+
+
+def generate_audio(items, media_dir, progress_cb, done_cb, cancelled):
+    """Write a sound file for each item into media_dir on a worker thread,
+    a Forvo recording if the user set that up and synthesized speech
+    otherwise.
+
+    progress_cb(fraction) and done_cb(audio, error) run on the main loop;
+    audio maps (word, lang) to file names. Once the threading.Event
+    cancelled is set the job stops and done_cb isn't called.
+    """
+    use_forvo = forvo.is_enabled()
+
+    def fetch_recording(word, lang):
+        try:
+            return forvo.fetch(word, lang)
+        except (OSError, ValueError, KeyError) as e:
+            print(f"could not fetch the Forvo recording of {word}: {e}")
+            return None
+
+    def worker():
+        audio = {}
+        try:
+            os.makedirs(media_dir, exist_ok=True)
+            for i, item in enumerate(items):
+                if cancelled.is_set():
+                    return
+                word, lang = item["word"], item["lang"]
+                if use_forvo and (recording := fetch_recording(word, lang)):
+                    name = media_name(word, lang, "mp3")
+                    shutil.copyfile(recording, os.path.join(media_dir, name))
+                    audio[(word, lang)] = name
+                elif speech.can_record():
+                    name = media_name(word, lang, "wav")
+                    speech.record(word, lang, os.path.join(media_dir, name))
+                    audio[(word, lang)] = name
+                GLib.idle_add(progress_cb, (i + 1) / len(items))
+        except OSError as e:
+            GLib.idle_add(done_cb, None, str(e))
+            return
+        except GLib.Error as e:
+            GLib.idle_add(done_cb, None, e.message)
+            return
+        GLib.idle_add(done_cb, audio, None)
+
+    threading.Thread(target=worker, daemon=True).start()
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/anki_export.ui')
+class Word2ipaAnkiExportDialog(Adw.Dialog):
+    """Lets the user pick history and favorite entries to turn into cards."""
+    __gtype_name__ = 'Word2ipaAnkiExportDialog'
+    export_button = Gtk.Template.Child()
+    stack = Gtk.Template.Child()
+    audio_row = Gtk.Template.Child()
+    progress_bar = Gtk.Template.Child()
+    toast_overlay = Gtk.Template.Child()
+    favorites_group = Gtk.Template.Child()
+    favorites_list = Gtk.Template.Child()
+    history_group = Gtk.Template.Child()
+    history_list = Gtk.Template.Child()
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        # (word, lang) -> (item, check button); favorites are picked by default
+        self.choices = {}
+        self.add_rows(self.favorites_list, Favorites.get_default(), True)
+        self.add_rows(self.history_list, History.get_default(), False)
+        self.favorites_group.set_visible(len(Favorites.get_default()) > 0)
+        self.history_group.set_visible(len(History.get_default()) > 0)
+        self.update_export_button()
+
+        self.audio_row.set_sensitive(speech.can_record() or forvo.is_enabled())
+        # set to stop generating audio when the dialog goes away
+        self.cancelled = threading.Event()
+        self.connect("closed", lambda *_args: self.cancelled.set())
+
+    def add_rows(self, list_box, items, active):
+        for item in items:
+            key = (item.word, item.lang)
+            if key in self.choices:
+                continue
+            check = Gtk.CheckButton(active=active, valign=Gtk.Align.CENTER)
+            check.connect("toggled", lambda _c: self.update_export_button())
+            row = Adw.ActionRow(title=item.word, subtitle=f"{item.ipa} · {languages.display_name(item.lang)}",
+                                activatable_widget=check)
+            row.add_prefix(check)
+            list_box.append(row)
+            self.choices[key] = (item, check)
+
+    def selected(self):
+        return [{"word": item.word, "ipa": item.ipa, "lang": item.lang}
+                for item, check in self.choices.values() if check.get_active()]
+
+    def update_export_button(self):
+        self.export_button.set_sensitive(len(self.selected()) > 0)
+
+    @Gtk.Template.Callback()
+    def on_cancel_clicked(self, button):
+        self.close()
+
+    @Gtk.Template.Callback()
+    def on_export_clicked(self, button):
+        items = self.selected()
+
+        def on_path(path):
+            if not self.audio_row.get_active():
+                export.write_anki_tsv(path, items)
+                self.close()
+                return
+            self.stack.set_visible_child_name("progress")
+            self.export_button.set_sensitive(False)
+            media_dir = f"{os.path.splitext(path)[0]}.media"
+            generate_audio(items, media_dir, self.progress_bar.set_fraction,
+                           lambda audio, error: self.on_audio_generated(path, items, audio, error),
+                           self.cancelled)
+
+        export.choose_save_path(self, "word2ipa-cards.txt", on_path,
+                                filters=[export.file_filter(_("Anki Text File"), "*.txt", "*.tsv")])
+
+    def on_audio_generated(self, path, items, audio, error):
+        if error is not None:
+            print(f"could not generate audio: {error}")
+            self.toast_overlay.add_toast(Adw.Toast(title=_("Could not generate the audio")))
+            self.stack.set_visible_child_name("choose")
+            self.update_export_button()
+            return
+        export.write_anki_tsv(path, items, audio)
+        self.close()
@@ -0,0 +1,107 @@
+# backends.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""The transcription backends lookups go through, in the priority
+order of the "transcription-backends" setting.
+"""
+
+from gi.repository import Gio, GObject
+from .core import BackendChain, Dictionary, DictionaryBackend, RulesBackend
+from .langpacks import LanguagePacks
+from .online import OnlineResults
+from .userdict import UserDictionary
+
+USER = "user"
+DICTIONARY = "dictionary"
+ONLINE = "online"
+SPELLING_RULES = "spelling-rules"
+BACKENDS = [USER, DICTIONARY, ONLINE, SPELLING_RULES]
+
+
+def build(lang, dictionary, names, user_entries, online_entries, rules):
+    """A BackendChain for lang of the backends names, in that order, made
+    from plain data so it can be built on any thread.
+    """
+    backends = {
+        USER: lambda: DictionaryBackend(Dictionary(lang, user_entries), USER),
+        DICTIONARY: lambda: DictionaryBackend(dictionary, DICTIONARY),
+        ONLINE: lambda: DictionaryBackend(Dictionary(lang, online_entries), ONLINE),
+        SPELLING_RULES: lambda: RulesBackend(lang, rules, SPELLING_RULES),
+    }
+    return BackendChain(backends[name]() for name in names)
+
+
+class Backends(GObject.Object):
+    """The backend chain of each language, kept until the setting or
+    the data of a backend changes.
+    """
+    __gtype_name__ = 'Word2ipaBackends'
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        self.user_dict = UserDictionary.get_default()
+        self.online_results = OnlineResults.get_default()
+        # (lang, online) -> (dictionary, chain)
+        self.chains = {}
+        self.settings.connect('changed::transcription-backends', self.clear)
+        self.user_dict.connect('changed', self.clear)
+        self.online_results.connect('changed', self.clear)
+        LanguagePacks.get_default().connect('changed', self.clear)
+
+    def clear(self, *_args):
+        self.chains.clear()
+
+    def order(self):
+        """The names of the backends to use, highest priority first. Ones
+        left out of the setting aren't used.
+        """
+        return [name for name in self.settings.get_strv('transcription-backends') if name in BACKENDS]
+
+    def prepare(self, lang, online=True):
+        """Return make(dictionary) building the chain of lang from a copy
+        of the data as it is now, to be called on a worker thread.
+        """
+        names = [name for name in self.order() if online or name != ONLINE]
+        user_entries = dict(self.user_dict.languages.get(lang, {}))
+        online_entries = dict(self.online_results.languages.get(lang, {}))
+        rules = self.user_dict.rules(lang)
+        return lambda dictionary: build(lang, dictionary, names, user_entries, online_entries, rules)
+
+    def chain(self, lang, dictionary, online=True):
+        """The BackendChain of lang, dictionary being its loaded one.
+        Unless online is True the words found on Wiktionary are left out,
+        for lookups that only read what is installed.
+        """
+        cached = self.chains.get((lang, online))
+        if cached is None or cached[0] is not dictionary:
+            cached = self.chains[(lang, online)] = (dictionary, self.prepare(lang, online)(dictionary))
+        return cached[1]
+
+
+def chain(lang, dictionary, online=True):
+    """Backends.chain() of the default Backends."""
+    return Backends.get_default().chain(lang, dictionary, online)
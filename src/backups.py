@@ -0,0 +1,155 @@
+# backups.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio
+import datetime
+import json
+import os
+import shutil
+from . import storage
+from .core import orthography
+from .favorites import FAVORITES_FILE, Favorites
+from .userdict import ENTRY_INFO_FILE, SPELLING_RULES_FILE, USER_DICT_FILE, UserDictionary
+
+BACKUPS_DIR = 'backups'
+# the data files the user would most miss, favorites, their own entries and spelling rules
+BACKED_UP = [USER_DICT_FILE, FAVORITES_FILE, ENTRY_INFO_FILE, SPELLING_RULES_FILE]
+NAME_FORMAT = '%Y%m%d-%H%M%S'
+
+
+def backups_dir():
+    path = storage.data_path(BACKUPS_DIR)
+    os.makedirs(path, exist_ok=True)
+    return path
+
+
+def list_backups():
+    """Return (name, datetime) of the backups, newest first."""
+    found = []
+    for name in os.listdir(backups_dir()):
+        try:
+            found.append((name, datetime.datetime.strptime(name, NAME_FORMAT)))
+        except ValueError:
+            continue
+    return sorted(found, key=lambda backup: backup[1], reverse=True)
+
+
+def _read(path):
+    try:
+        with open(path, 'rb') as f:
+            return f.read()
+    except FileNotFoundError:
+        return None
+
+
+def make_backup(keep_backup=None):
+    """Copy the data files into a new timestamped backup, unless they
+    haven't changed since the last one, and drop the oldest backups
+    beyond the "backup-count" setting, except keep_backup.
+    """
+    backups = list_backups()
+    if backups:
+        latest = os.path.join(backups_dir(), backups[0][0])
+        if all(_read(storage.data_path(name)) == _read(os.path.join(latest, name))
+               for name in BACKED_UP):
+            return
+    if not any(os.path.exists(storage.data_path(name)) for name in BACKED_UP):
+        return
+
+    path = os.path.join(backups_dir(), datetime.datetime.now().strftime(NAME_FORMAT))
+    os.makedirs(path, exist_ok=True)
+    for name in BACKED_UP:
+        if os.path.exists(storage.data_path(name)):
+            shutil.copy2(storage.data_path(name), os.path.join(path, name))
+
+    keep = Gio.Settings.new('io.github.mohfy.word2ipa').get_int('backup-count')
+    for name, _date in list_backups()[keep:]:
+        if name == keep_backup:
+            continue
+        shutil.rmtree(os.path.join(backups_dir(), name), ignore_errors=True)
+
+
+def _entries(user_dict, favorites, spelling_rules):
+    """{(kind, lang, word): ipa} of the user entries, favorites and
+    spelling rules, the letters of a rule standing for its word.
+    """
+    entries = {}
+    for lang, words in user_dict.items():
+        for word, ipa in words.items():
+            entries[("entry", lang, word)] = ipa
+    for item in favorites:
+        entries[("favorite", item["lang"], item["word"])] = item["ipa"]
+    for lang, text in spelling_rules.items():
+        try:
+            rules = orthography.parse_rules(text)
+        except orthography.RuleError:
+            # compared as a whole
+            rules = {"": text}
+        for letters, sounds in rules.items():
+            entries[("rule", lang, letters)] = sounds
+    return entries
+
+
+def _load(path, name, default):
+    data = _read(os.path.join(path, name))
+    if data is None:
+        return default
+    try:
+        return json.loads(data)
+    except ValueError:
+        return default
+
+
+def differences(backup):
+    """What restoring backup would change, as lists of (kind, lang, word,
+    ipa) under "added" and "removed" and of (kind, lang, word, old, new)
+    under "changed"; kind is "entry", "favorite" or "rule".
+    """
+    path = os.path.join(backups_dir(), backup)
+    restored = _entries(_load(path, USER_DICT_FILE, {}), _load(path, FAVORITES_FILE, []),
+                        _load(path, SPELLING_RULES_FILE, {}))
+    current = _entries(storage.load_json(USER_DICT_FILE, {}), storage.load_json(FAVORITES_FILE, []),
+                       storage.load_json(SPELLING_RULES_FILE, {}))
+    return {
+        "added": [(*key, ipa) for key, ipa in restored.items() if key not in current],
+        "removed": [(*key, ipa) for key, ipa in current.items() if key not in restored],
+        "changed": [(*key, current[key], ipa) for key, ipa in restored.items()
+                    if key in current and current[key] != ipa],
+    }
+
+
+def restore(backup):
+    """Put the files of backup back in place. The current ones are backed
+    up first, so a restore can be undone by restoring that backup.
+    Raises FileNotFoundError if backup doesn't exist.
+    """
+    path = os.path.join(backups_dir(), backup)
+    # files missing from the backup are removed, so a missing backup would empty everything
+    if not os.path.isdir(path):
+        raise FileNotFoundError(f"no backup {backup}")
+    # the backup being restored may be the oldest one
+    make_backup(keep_backup=backup)
+    for name in BACKED_UP:
+        source = os.path.join(path, name)
+        with storage.lock(name):
+            if os.path.exists(source):
+                shutil.copy2(source, storage.data_path(name))
+            elif os.path.exists(storage.data_path(name)):
+                os.remove(storage.data_path(name))
+    UserDictionary.get_default().reload()
+    Favorites.get_default().reload()
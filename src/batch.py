@@ -0,0 +1,232 @@
+# batch.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, Gtk
+import re
+from . import backends, dictionaries, export, languages, printing
+from .core import clean_input, encoding, lookup_word
+from .word_result import Word2ipaWordResult
+
+
+def read_word_list(path):
+    """Return (the usable lines of a plain text file, one word each,
+    count of characters that couldn't be read) in whichever encoding the
+    file looks to be in.
+    """
+    with open(path, 'rb') as f:
+        text, replaced = encoding.decode(f.read())
+    words = []
+    for line in text.splitlines():
+        word, problem = clean_input(line)
+        if problem is None:
+            words.append(word)
+    return words, replaced
+
+
+# a word of running text, keeping apostrophes and hyphens inside it
+WORD = re.compile(r"\w[\w'’-]*")
+# words transcribed at a time; the first batch fills the visible rows
+CHUNK_SIZE = 100
+
+
+def words_of_text(text):
+    """The words of running text, like a pasted paragraph."""
+    return WORD.findall(text)
+
+
+def highlight(text, query):
+    """Markup of text with every case-insensitive match of query highlighted."""
+    folded = text.casefold()
+    # casefold can change the length of some letters, those aren't highlighted
+    if not query or len(folded) != len(text):
+        return GLib.markup_escape_text(text)
+    markup = []
+    start = 0
+    while (found := folded.find(query, start)) != -1:
+        markup.append(GLib.markup_escape_text(text[start:found]))
+        match = GLib.markup_escape_text(text[found:found + len(query)])
+        markup.append(f'<span bgcolor="#f6d32d" bgalpha="60%">{match}</span>')
+        start = found + len(query)
+    markup.append(GLib.markup_escape_text(text[start:]))
+    return "".join(markup)
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/batch.ui')
+class Word2ipaBatchDialog(Adw.Dialog):
+    """Transcribes every word of an imported list at once."""
+    __gtype_name__ = 'Word2ipaBatchDialog'
+    results_group = Gtk.Template.Child()
+    results_list = Gtk.Template.Child()
+    save_button = Gtk.Template.Child()
+    print_button = Gtk.Template.Child()
+    spinner = Gtk.Template.Child()
+    search_bar = Gtk.Template.Child()
+    search_entry = Gtk.Template.Child()
+    progress_bar = Gtk.Template.Child()
+
+    def __init__(self, words, lang, unreadable=0, **kwargs):
+        super().__init__(**kwargs)
+        self.words = words
+        self.lang = lang
+        # characters of the file that couldn't be read
+        self.unreadable = unreadable
+        # (word, ipa or None) in file order
+        self.results = []
+        self.rows = []
+        # rows matching the search, and which of them was scrolled to
+        self.matches = []
+        self.match_index = 0
+        self.results_group.set_title(languages.display_name(lang))
+
+        self.search_bar.set_key_capture_widget(self)
+        # the dialog's Ctrl+F comes before the window's symbol search
+        find = Gtk.ShortcutController(scope=Gtk.ShortcutScope.LOCAL)
+        find.add_shortcut(Gtk.Shortcut(trigger=Gtk.ShortcutTrigger.parse_string("<primary>f"),
+                                       action=Gtk.CallbackAction.new(self.on_find_shortcut)))
+        self.add_controller(find)
+        self.chunk_source = None
+        self.connect("closed", self.on_closed)
+        dictionaries.load_async(lang, self.on_dictionary_loaded)
+
+    def on_dictionary_loaded(self, dictionary, error):
+        self.spinner.set_visible(False)
+        if error:
+            self.results_group.set_description(_("Could not load the dictionary"))
+            print(f"could not load {self.lang} dictionary: {error}")
+            return
+
+        self.sources = [backends.chain(self.lang, dictionary, online=False).for_language(self.lang)]
+        # long lists are transcribed a chunk at a time so the dialog stays responsive
+        self.transcribe_chunk()
+        if len(self.results) < len(self.words):
+            self.progress_bar.set_visible(True)
+            self.chunk_source = GLib.idle_add(self.transcribe_chunk)
+
+    def transcribe_chunk(self):
+        query = self.search_entry.get_text().strip().casefold()
+        for word in self.words[len(self.results):len(self.results) + CHUNK_SIZE]:
+            ipa, _form, _match = lookup_word(word, self.sources)
+            self.results.append((word, ipa))
+            row = Adw.ActionRow(subtitle_selectable=True)
+            self.set_row_text(row, word, ipa, query)
+            if ipa is None:
+                row.add_css_class("dim-label")
+            else:
+                row.set_activatable(True)
+                row.connect("activated", self.on_row_activated, word, ipa)
+            self.results_list.append(row)
+            self.rows.append(row)
+            if query and self.matches_query(word, ipa, query):
+                self.matches.append(row)
+
+        self.progress_bar.set_fraction(len(self.results) / max(len(self.words), 1))
+        if len(self.results) < len(self.words):
+            return GLib.SOURCE_CONTINUE
+        self.chunk_source = None
+        self.progress_bar.set_visible(False)
+        self.show_summary()
+        return GLib.SOURCE_REMOVE
+
+    def on_closed(self, dialog):
+        if self.chunk_source is not None:
+            GLib.source_remove(self.chunk_source)
+            self.chunk_source = None
+
+    def show_summary(self):
+        found = sum(1 for _word, ipa in self.results if ipa is not None)
+        description = ngettext("{found} of {total} word found", "{found} of {total} words found",
+                               len(self.results)).format(found=found, total=len(self.results))
+        if self.unreadable:
+            description += "\n" + ngettext("{} character of the file could not be read",
+                                            "{} characters of the file could not be read",
+                                            self.unreadable).format(self.unreadable)
+        self.results_group.set_description(description)
+        self.save_button.set_sensitive(found > 0)
+        self.print_button.set_sensitive(found > 0)
+
+    def on_row_activated(self, row, word, ipa):
+        popover = Gtk.Popover(child=Word2ipaWordResult(word, ipa, self.lang))
+        popover.set_parent(row)
+        popover.connect("closed", lambda *_args: GLib.idle_add(popover.unparent))
+        popover.popup()
+
+    def on_find_shortcut(self, widget, args):
+        self.search_bar.set_search_mode(True)
+        self.search_entry.grab_focus()
+        return True
+
+    def set_row_text(self, row, word, ipa, query):
+        row.set_title(highlight(word, query))
+        row.set_subtitle(highlight(ipa, query) if ipa else GLib.markup_escape_text(_("Not found")))
+
+    def matches_query(self, word, ipa, query):
+        return query in word.casefold() or (ipa is not None and query in ipa.casefold())
+
+    @Gtk.Template.Callback()
+    def on_search_changed(self, entry):
+        query = entry.get_text().strip().casefold()
+        self.matches = []
+        for row, (word, ipa) in zip(self.rows, self.results):
+            self.set_row_text(row, word, ipa, query)
+            if query and self.matches_query(word, ipa, query):
+                self.matches.append(row)
+        self.match_index = 0
+        self.show_match()
+
+    @Gtk.Template.Callback()
+    def on_next_match(self, entry):
+        self.move_match(1)
+
+    @Gtk.Template.Callback()
+    def on_previous_match(self, entry):
+        self.move_match(-1)
+
+    def move_match(self, step):
+        if self.matches:
+            self.match_index = (self.match_index + step) % len(self.matches)
+            self.show_match()
+
+    def show_match(self):
+        if not self.matches:
+            return
+        # focusing the row scrolls it into view, then typing goes on in the entry
+        self.matches[self.match_index].grab_focus()
+        self.search_entry.get_delegate().grab_focus_without_selecting()
+
+    @Gtk.Template.Callback()
+    def on_stop_search(self, entry):
+        entry.set_text("")
+        self.search_bar.set_search_mode(False)
+
+    @Gtk.Template.Callback()
+    def on_cancel_clicked(self, button):
+        self.close()
+
+    @Gtk.Template.Callback()
+    def on_print_clicked(self, button):
+        printing.print_transcriptions(self.get_root(), languages.display_name(self.lang), self.results,
+                                      [self.lang])
+
+    @Gtk.Template.Callback()
+    def on_save_clicked(self, button):
+        def on_path(path):
+            export.write_transcriptions(path, self.results, self.lang)
+            self.close()
+
+        export.choose_save_path(self, "transcriptions.txt", on_path,
+                                filters=[export.file_filter(_("Text File"), "*.txt", "*.tsv")])
@@ -0,0 +1,191 @@
+# chart_render.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Draws the IPA charts into printable files without any widgets."""
+
+import gi
+gi.require_foreign('cairo')
+
+from gi.repository import Pango, PangoCairo
+import cairo
+from .consonant_table import MANNERS, PLACES
+from .core import phonemes
+from .vowel_chart import HEIGHT as CHART_HEIGHT, PAIR_OFFSET, VOWELS, WIDTH as CHART_WIDTH, position
+
+# A4 in points
+PAGE_WIDTH = 595
+PAGE_HEIGHT = 842
+MARGIN = 48
+PNG_SCALE = 2
+LABEL_WIDTH = 110
+ROW_HEIGHT = 28
+
+
+def shown(symbol, symbols):
+    return symbols is None or symbol in symbols
+
+
+def text(cr, string, x, y, size=12, bold=False, center=True):
+    layout = PangoCairo.create_layout(cr)
+    font = Pango.FontDescription.from_string(f"Sans {'Bold ' if bold else ''}{size}")
+    layout.set_font_description(font)
+    layout.set_text(string, -1)
+    width, height = layout.get_pixel_size()
+    cr.move_to(x - width / 2 if center else x, y - height / 2)
+    PangoCairo.show_layout(cr, layout)
+
+
+def draw_vowels(cr, symbols):
+    # the chart's own layout, scaled down to the page width
+    scale = (PAGE_WIDTH - 2 * MARGIN) / CHART_WIDTH
+    cr.save()
+    cr.translate(MARGIN, 90)
+    cr.scale(scale, scale)
+    cr.set_source_rgb(0.6, 0.6, 0.6)
+    cr.set_line_width(1)
+    corners = [position(0, 0), position(0, 2), position(6, 2), position(6, 0)]
+    cr.move_to(*corners[-1])
+    for corner in corners:
+        cr.line_to(*corner)
+    for row in (2, 4):
+        cr.move_to(*position(row, 0))
+        cr.line_to(*position(row, 2))
+    cr.move_to(*position(0, 1))
+    cr.line_to(*position(6, 1))
+    cr.stroke()
+
+    cr.set_source_rgb(0, 0, 0)
+    for symbol, height, backness, rounded in VOWELS:
+        if not shown(symbol, symbols):
+            continue
+        x, y = position(height, backness)
+        if rounded is not None:
+            x += PAIR_OFFSET if rounded else -PAIR_OFFSET
+        text(cr, symbol, x, y, size=14)
+    cr.restore()
+    return 90 + CHART_HEIGHT * scale
+
+
+def draw_consonants(cr, entries, symbols, top):
+    placed = [entry for entry in entries
+              if entry.get("place") in dict(PLACES) and entry.get("manner") in dict(MANNERS)]
+    places = [place for place in PLACES if any(entry["place"] == place[0] for entry in placed)]
+    manners = [manner for manner in MANNERS if any(entry["manner"] == manner[0] for entry in placed)]
+    cell = (PAGE_WIDTH - 2 * MARGIN - LABEL_WIDTH) / len(places)
+    left = MARGIN + LABEL_WIDTH
+
+    cr.set_source_rgb(0, 0, 0)
+    for column, (_place, label) in enumerate(places):
+        cr.save()
+        # place names don't fit side by side, so they are written upwards
+        cr.translate(left + (column + 0.5) * cell, top + 40)
+        cr.rotate(-1.2)
+        text(cr, label, 0, 0, size=8, bold=True, center=False)
+        cr.restore()
+    top += 60
+    for row, (_manner, label) in enumerate(manners):
+        text(cr, label, MARGIN, top + (row + 0.5) * ROW_HEIGHT, size=8, bold=True, center=False)
+
+    cr.set_source_rgb(0.6, 0.6, 0.6)
+    cr.set_line_width(0.5)
+    cr.rectangle(left, top, cell * len(places), ROW_HEIGHT * len(manners))
+    for column in range(1, len(places)):
+        cr.move_to(left + column * cell, top)
+        cr.line_to(left + column * cell, top + ROW_HEIGHT * len(manners))
+    for row in range(1, len(manners)):
+        cr.move_to(left, top + row * ROW_HEIGHT)
+        cr.line_to(left + cell * len(places), top + row * ROW_HEIGHT)
+    cr.stroke()
+
+    cr.set_source_rgb(0, 0, 0)
+    columns = {place: column for column, (place, _label) in enumerate(places)}
+    rows = {manner: row for row, (manner, _label) in enumerate(manners)}
+    for entry in placed:
+        if not shown(entry["symbol"], symbols):
+            continue
+        # voiceless on the left half of the cell, voiced on the right
+        x = left + columns[entry["place"]] * cell + cell * (0.75 if entry.get("voiced") else 0.25)
+        y = top + (rows[entry["manner"]] + 0.5) * ROW_HEIGHT
+        text(cr, entry["symbol"], x, y, size=12)
+
+
+def render_comparison(path, rows, shared):
+    """Write a table of compared transcriptions to path, a PNG if it
+    ends in .png and a PDF otherwise.
+
+    rows are (word, cells) with one sound per cell, aligned column by
+    column; cells with one of the shared sounds are drawn in bold.
+    """
+    columns = max(len(cells) for _word, cells in rows)
+    cell = min(40, (PAGE_WIDTH - 2 * MARGIN - LABEL_WIDTH) / max(columns, 1))
+    width = PAGE_WIDTH
+    height = int(2 * MARGIN + ROW_HEIGHT * len(rows))
+    png = path.lower().endswith('.png')
+    if png:
+        surface = cairo.ImageSurface(cairo.FORMAT_ARGB32, width * PNG_SCALE, height * PNG_SCALE)
+        cr = cairo.Context(surface)
+        cr.scale(PNG_SCALE, PNG_SCALE)
+        cr.set_source_rgb(1, 1, 1)
+        cr.paint()
+    else:
+        surface = cairo.PDFSurface(path, width, height)
+        cr = cairo.Context(surface)
+
+    cr.set_source_rgb(0, 0, 0)
+    for row, (word, cells) in enumerate(rows):
+        y = MARGIN + (row + 0.5) * ROW_HEIGHT
+        text(cr, word, MARGIN, y, bold=True, center=False)
+        for column, sound in enumerate(cells):
+            x = MARGIN + LABEL_WIDTH + (column + 0.5) * cell
+            is_shared = sound is not None and not shared.isdisjoint(phonemes.split(sound))
+            text(cr, sound or "–", x, y, size=14, bold=is_shared)
+
+    if png:
+        surface.write_to_png(path)
+    surface.finish()
+
+
+def render_chart(path, entries, title, sounds=None):
+    """Write the vowel chart and consonant table to path.
+
+    entries are the lookup table entries; path gets a PNG if it ends
+    in .png and a PDF otherwise. If sounds is given, only the symbols
+    of those sounds are drawn, e.g. the inventory of a language.
+    """
+    symbols = None
+    if sounds is not None:
+        symbols = set(sounds) | {phonemes.base(sound) for sound in sounds}
+    png = path.lower().endswith('.png')
+    if png:
+        surface = cairo.ImageSurface(cairo.FORMAT_ARGB32, PAGE_WIDTH * PNG_SCALE, PAGE_HEIGHT * PNG_SCALE)
+        cr = cairo.Context(surface)
+        cr.scale(PNG_SCALE, PNG_SCALE)
+        cr.set_source_rgb(1, 1, 1)
+        cr.paint()
+    else:
+        surface = cairo.PDFSurface(path, PAGE_WIDTH, PAGE_HEIGHT)
+        cr = cairo.Context(surface)
+
+    cr.set_source_rgb(0, 0, 0)
+    text(cr, title, PAGE_WIDTH / 2, MARGIN, size=18, bold=True)
+    bottom = draw_vowels(cr, symbols)
+    draw_consonants(cr, entries, symbols, bottom + 30)
+
+    if png:
+        surface.write_to_png(path)
+    surface.finish()
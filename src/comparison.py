@@ -0,0 +1,143 @@
+# comparison.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, GObject, Gtk
+from . import chart_render, export, languages
+from .core import phonemes
+
+
+class ComparisonBasket(GObject.Object):
+    """Results put aside to be compared side by side, for this session."""
+    __gtype_name__ = 'Word2ipaComparisonBasket'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        # {"word", "ipa", "lang"} in the order they were added
+        self.items = []
+
+    def __len__(self):
+        return len(self.items)
+
+    def contains(self, word, lang):
+        return any((item["word"], item["lang"]) == (word, lang) for item in self.items)
+
+    def add(self, word, ipa, lang):
+        if not self.contains(word, lang):
+            self.items.append({"word": word, "ipa": ipa, "lang": lang})
+            self.emit('changed')
+
+    def remove(self, word, lang):
+        self.items = [item for item in self.items if (item["word"], item["lang"]) != (word, lang)]
+        self.emit('changed')
+
+    def clear(self):
+        self.items = []
+        self.emit('changed')
+
+
+def align_items(items):
+    """The sounds of the first item's transcription and, for each item,
+    its sounds in one cell per sound of the first.
+    """
+    first = phonemes.variants(items[0]["ipa"])[0]
+    return phonemes.split(first), [phonemes.align_cells(first, phonemes.variants(item["ipa"])[0])
+                                   for item in items]
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/comparison.ui')
+class Word2ipaComparisonDialog(Adw.Dialog):
+    """The results of the basket in a table, aligned sound by sound with
+    the first one; the sounds all of them have are highlighted.
+    """
+    __gtype_name__ = 'Word2ipaComparisonDialog'
+    stack = Gtk.Template.Child()
+    grid = Gtk.Template.Child()
+    export_button = Gtk.Template.Child()
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self.basket = ComparisonBasket.get_default()
+        self._changed_id = self.basket.connect("changed", lambda *_: self.fill())
+        self.fill()
+
+    def fill(self):
+        while child := self.grid.get_first_child():
+            self.grid.remove(child)
+        items = self.basket.items
+        self.export_button.set_sensitive(bool(items))
+        self.stack.set_visible_child_name("table" if items else "empty")
+        if not items:
+            return
+
+        _sounds, rows = align_items(items)
+        shared = phonemes.shared_sounds(phonemes.variants(item["ipa"])[0] for item in items)
+        for row, (item, cells) in enumerate(zip(items, rows)):
+            label = Gtk.Box(orientation=Gtk.Orientation.VERTICAL)
+            label.append(Gtk.Label(label=item["word"], xalign=0, css_classes=["heading"]))
+            label.append(Gtk.Label(label=languages.display_name(item["lang"]), xalign=0,
+                                   css_classes=["dim-label", "caption"]))
+            self.grid.attach(label, 0, row, 1, 1)
+            for column, cell in enumerate(cells, 1):
+                # a joined cell like "ts" counts as shared if one of its sounds is
+                is_shared = cell is not None and not shared.isdisjoint(phonemes.split(cell))
+                self.grid.attach(Gtk.Label(label=cell or "–",
+                                           css_classes=["title-3", "accent"] if is_shared else ["title-3"]),
+                                 column, row, 1, 1)
+            remove_button = Gtk.Button(icon_name="list-remove-symbolic",
+                                       tooltip_text=_("Remove from Comparison"),
+                                       valign=Gtk.Align.CENTER,
+                                       css_classes=["flat", "circular"])
+            remove_button.connect("clicked", lambda _b, i=item: self.basket.remove(i["word"], i["lang"]))
+            self.grid.attach(remove_button, len(cells) + 1, row, 1, 1)
+
+    @Gtk.Template.Callback()
+    def on_clear_clicked(self, button):
+        self.basket.clear()
+
+    @Gtk.Template.Callback()
+    def on_export_clicked(self, button):
+        items = list(self.basket.items)
+        _sounds, rows = align_items(items)
+
+        def on_path(path):
+            if path.lower().endswith(('.png', '.pdf')):
+                shared = phonemes.shared_sounds(phonemes.variants(item["ipa"])[0] for item in items)
+                chart_render.render_comparison(path, [(item["word"], cells) for item, cells in zip(items, rows)],
+                                               shared)
+            else:
+                export.write_comparison(path, items, rows)
+
+        export.choose_save_path(self, "comparison.csv", on_path,
+                                filters=[export.file_filter(_("CSV"), "*.csv"),
+                                         export.file_filter(_("PNG Image"), "*.png"),
+                                         export.file_filter(_("PDF"), "*.pdf")])
+
+    @Gtk.Template.Callback()
+    def on_closed(self, dialog):
+        self.basket.disconnect(self._changed_id)
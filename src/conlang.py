@@ -0,0 +1,72 @@
+# conlang.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, Gtk
+import sqlite3
+from . import symbols
+from .core import phonemes
+from .langpacks import LanguagePacks
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/conlang.ui')
+class Word2ipaConlangDialog(Adw.Dialog):
+    """Creates a constructed language from a name and the sounds of the
+    IPA lookup table it uses. on_created(code) is called once it exists.
+    """
+    __gtype_name__ = 'Word2ipaConlangDialog'
+    create_button = Gtk.Template.Child()
+    name_row = Gtk.Template.Child()
+    vowels_box = Gtk.Template.Child()
+    consonants_box = Gtk.Template.Child()
+    toast_overlay = Gtk.Template.Child()
+
+    def __init__(self, on_created, **kwargs):
+        super().__init__(**kwargs)
+        self.on_created = on_created
+        self.sound_buttons = {}
+        for entry in symbols.load_table():
+            # diphthongs are made of the vowels
+            if entry.get("diphthong"):
+                continue
+            button = Gtk.ToggleButton(label=entry["symbol"], tooltip_text=entry["sound"])
+            button.connect("toggled", self.on_changed)
+            box = self.vowels_box if phonemes.is_vowel(entry["symbol"]) else self.consonants_box
+            box.append(button)
+            self.sound_buttons[entry["symbol"]] = button
+
+    def inventory(self):
+        return [symbol for symbol, button in self.sound_buttons.items() if button.get_active()]
+
+    @Gtk.Template.Callback()
+    def on_changed(self, *args):
+        self.create_button.set_sensitive(bool(self.name_row.get_text().strip()) and bool(self.inventory()))
+
+    @Gtk.Template.Callback()
+    def on_cancel_clicked(self, button):
+        self.close()
+
+    @Gtk.Template.Callback()
+    def on_create_clicked(self, button):
+        try:
+            code = LanguagePacks.get_default().create_conlang(self.name_row.get_text().strip(), self.inventory())
+        except (OSError, sqlite3.Error) as e:
+            print(f"could not create the language: {e}")
+            self.toast_overlay.add_toast(Adw.Toast(title=_("Could not create the language")))
+            return
+        self.close()
+        self.on_created(code)
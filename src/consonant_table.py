@@ -0,0 +1,88 @@
+# consonant_table.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import GObject, Gtk
+
+# columns and rows of the pulmonic consonant chart, in chart order
+PLACES = [
+    ("bilabial", _("Bilabial")),
+    ("labiodental", _("Labiodental")),
+    ("dental", _("Dental")),
+    ("alveolar", _("Alveolar")),
+    ("postalveolar", _("Postalveolar")),
+    ("retroflex", _("Retroflex")),
+    ("palatal", _("Palatal")),
+    ("velar", _("Velar")),
+    ("uvular", _("Uvular")),
+    ("pharyngeal", _("Pharyngeal")),
+    ("glottal", _("Glottal")),
+]
+MANNERS = [
+    ("plosive", _("Plosive")),
+    ("nasal", _("Nasal")),
+    ("trill", _("Trill")),
+    ("tap", _("Tap or Flap")),
+    ("fricative", _("Fricative")),
+    ("lateral fricative", _("Lateral Fricative")),
+    ("approximant", _("Approximant")),
+    ("lateral approximant", _("Lateral Approximant")),
+]
+
+
+class Word2ipaConsonantTable(Gtk.Grid):
+    """Consonants by manner (rows) and place (columns) of articulation.
+
+    Voiceless consonants are on the left of each cell and voiced ones
+    on the right, like on the IPA chart. Only the places and manners
+    the given entries use are shown. Sounds that are among the other
+    symbols of the IPA chart, like the labial-velar ⟨w⟩ and the
+    affricates, are left out and only listed in the lookup table.
+    """
+    __gtype_name__ = 'Word2ipaConsonantTable'
+    __gsignals__ = {
+        'symbol-activated': (GObject.SignalFlags.RUN_FIRST, None, (str,)),
+    }
+
+    def __init__(self, **kwargs):
+        super().__init__(column_spacing=2, row_spacing=4,
+                         halign=Gtk.Align.CENTER, **kwargs)
+
+    def set_entries(self, entries):
+        """Fill the table from lookup table entries with place and manner."""
+        while child := self.get_first_child():
+            self.remove(child)
+        placed = [entry for entry in entries
+                  if entry.get("place") in dict(PLACES) and entry.get("manner") in dict(MANNERS)]
+        places = [place for place in PLACES if any(entry["place"] == place[0] for entry in placed)]
+        manners = [manner for manner in MANNERS if any(entry["manner"] == manner[0] for entry in placed)]
+
+        for column, (_place, label) in enumerate(places):
+            self.attach(Gtk.Label(label=label, css_classes=["caption-heading"], margin_start=6,
+                                  margin_end=6), 1 + 2 * column, 0, 2, 1)
+        for row, (_manner, label) in enumerate(manners, 1):
+            self.attach(Gtk.Label(label=label, xalign=0, css_classes=["caption-heading"],
+                                  margin_end=6), 0, row, 1, 1)
+
+        place_columns = {place: column for column, (place, _label) in enumerate(places)}
+        manner_rows = {manner: row for row, (manner, _label) in enumerate(manners, 1)}
+        for entry in placed:
+            button = Gtk.Button(label=entry["symbol"], tooltip_text=entry["sound"],
+                                css_classes=["flat"])
+            button.connect("clicked", lambda _b, symbol=entry["symbol"]: self.emit('symbol-activated', symbol))
+            column = 1 + 2 * place_columns[entry["place"]] + int(entry.get("voiced", False))
+            self.attach(button, column, manner_rows[entry["manner"]], 1, 1)
@@ -0,0 +1,52 @@
+# content_filter.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio, GLib
+
+FILTER_PATH = "/io/github/mohfy/word2ipa/dicts/content_filter/{}.txt"
+
+
+def bundled_words(lang):
+    """The offensive words shipped for lang's language, if any."""
+    try:
+        data = Gio.resources_lookup_data(FILTER_PATH.format(lang.split("_")[0]),
+                                         Gio.ResourceLookupFlags.NONE)
+    except GLib.Error:
+        return set()
+    lines = data.get_data().decode("utf-8").splitlines()
+    return {line.strip().lower() for line in lines if line.strip() and not line.startswith("#")}
+
+
+def user_words(settings, lang):
+    return settings.get_value('filtered-words').unpack().get(lang, [])
+
+
+def set_user_words(settings, lang, words):
+    filtered = settings.get_value('filtered-words').unpack()
+    filtered[lang] = sorted(set(words))
+    settings.set_value('filtered-words', GLib.Variant('a{sas}', filtered))
+
+
+def blocked_words(lang):
+    """Words of lang that practice rounds must not pick, empty if the
+    user turned the filter off.
+    """
+    settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+    if not settings.get_boolean('content-filter'):
+        return set()
+    return bundled_words(lang) | {word.lower() for word in user_words(settings, lang)}
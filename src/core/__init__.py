@@ -0,0 +1,66 @@
+# __init__.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+"""Lookup logic of word2ipa without any GTK dependency.
+
+Everything in this package only needs the standard library so it can
+be reused by the app, a command line tool or third party code.
+"""
+
+from .dictionary import (Dictionary, DictionaryError, case_forms, clean_input, compose, find_duplicates,
+                         find_replace, lookup_word, mark_compound, sort_levels, word_of_the_day, word_to_ipa)
+from .compiled import CompiledDictionary, compile_dictionary, load_dictionary
+from .backends import (BackendChain, BackendError, DictionaryBackend, RulesBackend,
+                       TranscriptionBackend)
+from . import (collation, csv_import, encoding, normalize, notation, orthography, phonemes, respelling, similarity, sound_change,
+               wikitext)
+
+__all__ = [
+    'BackendChain',
+    'BackendError',
+    'CompiledDictionary',
+    'Dictionary',
+    'DictionaryBackend',
+    'DictionaryError',
+    'RulesBackend',
+    'TranscriptionBackend',
+    'case_forms',
+    'clean_input',
+    'collation',
+    'compile_dictionary',
+    'compose',
+    'csv_import',
+    'encoding',
+    'find_duplicates',
+    'find_replace',
+    'load_dictionary',
+    'lookup_word',
+    'mark_compound',
+    'normalize',
+    'notation',
+    'orthography',
+    'phonemes',
+    'respelling',
+    'similarity',
+    'sort_levels',
+    'sound_change',
+    'wikitext',
+    'word_of_the_day',
+    'word_to_ipa',
+]
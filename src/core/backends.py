@@ -0,0 +1,132 @@
+# backends.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Where transcriptions come from, behind one interface.
+
+A TranscriptionBackend turns a word of a language into its
+transcriptions. Dictionaries, spelling rules and anything else that
+transcribes words, like a G2P engine or an online service, can be put
+in a BackendChain to be asked in order of priority:
+
+    >>> from word2ipa.core import Dictionary
+    >>> chain = BackendChain([DictionaryBackend(Dictionary("en_US", {"cat": "/kæt/"})),
+    ...                       RulesBackend("en_US", {"c": "k", "a": "æ", "t": "t", "s": "s"})])
+    >>> chain.transcribe("cat", "en_US"), chain.transcribe("cats", "en_US")
+    (['/kæt/'], ['/kæts/'])
+"""
+
+from . import orthography, phonemes
+
+
+class BackendError(Exception):
+    """Raised by a backend that couldn't transcribe a word, like an
+    online service that can't be reached; a chain goes on to the next.
+    """
+
+
+class TranscriptionBackend:
+    """Transcribes words; subclasses implement transcribe(). name tells
+    backends apart, as in a priority order the user chose.
+
+    for_language() lets a backend be passed where dictionaries are, as to
+    lookup_word(), with its language set.
+    """
+    name = None
+
+    def transcribe(self, word, lang):
+        """Return the transcriptions of word in lang, most usual first,
+        or [] if it has none. Raises BackendError if it can't tell.
+        """
+        raise NotImplementedError
+
+    def for_language(self, lang):
+        """A dictionary-like view of the backend for lang."""
+        return _LanguageView(self, lang)
+
+
+class _LanguageView:
+
+    def __init__(self, backend, lang):
+        self.backend = backend
+        self.lang = lang
+
+    def lookup(self, word):
+        """Return the transcriptions of word as one dictionary value, or
+        None, like Dictionary.lookup().
+        """
+        try:
+            transcriptions = self.backend.transcribe(word, self.lang)
+        except BackendError:
+            return None
+        return ", ".join(transcriptions) or None
+
+
+class DictionaryBackend(TranscriptionBackend):
+    """Transcribes the words of a Dictionary or CompiledDictionary, for
+    its language only.
+    """
+
+    def __init__(self, dictionary, name=None):
+        self.dictionary = dictionary
+        self.name = name
+
+    def transcribe(self, word, lang):
+        if lang != self.dictionary.lang or (ipa := self.dictionary.lookup(word)) is None:
+            return []
+        return phonemes.variants(ipa)
+
+
+class RulesBackend(TranscriptionBackend):
+    """Spells words out by letters -> sounds rules of lang, as parsed by
+    orthography.parse_rules(). Words with letters the rules don't cover
+    aren't transcribed.
+    """
+
+    def __init__(self, lang, rules, name=None):
+        self.lang = lang
+        self.rules = rules
+        self.name = name
+
+    def transcribe(self, word, lang):
+        if lang != self.lang or not self.rules:
+            return []
+        ipa, unknown = orthography.transcribe(word, self.rules)
+        return [] if unknown else [ipa]
+
+
+class BackendChain(TranscriptionBackend):
+    """Asks backends in order and returns the transcriptions of the first
+    one that has the word. A backend raising BackendError is skipped.
+    """
+
+    def __init__(self, backends):
+        self.backends = list(backends)
+
+    def transcribe(self, word, lang):
+        return self.transcribe_from(word, lang)[0]
+
+    def transcribe_from(self, word, lang):
+        """Return (transcriptions, backend they came from), or ([], None)."""
+        for backend in self.backends:
+            try:
+                transcriptions = backend.transcribe(word, lang)
+            except BackendError:
+                continue
+            if transcriptions:
+                return transcriptions, backend
+        return [], None
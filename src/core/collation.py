@@ -0,0 +1,71 @@
+# collation.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Sorting words the way speakers of their language expect.
+
+PyICU is used when it is installed. Without it words are compared
+letter by letter with accents only breaking ties, so é sorts with e,
+and the letters some alphabets put at the end keep their place.
+"""
+
+import unicodedata
+
+try:
+    import icu
+except ImportError:
+    icu = None
+
+# dictionary codes that aren't ICU locales
+ICU_LOCALES = {
+    "fr_QC": "fr_CA",
+    "jam": "en_JM",
+    "ma": "mr",
+    "zh_hans": "zh_Hans",
+    "zh_hant": "zh_Hant",
+}
+
+# letters sorted as letters of their own, by language; \uffff puts them
+# after every other letter following the same one
+TAILORINGS = {
+    "es": {"ñ": "n\uffff"},
+    "fi": {"å": "z\uffff", "ä": "z\uffff\uffff", "ö": "z\uffff\uffff\uffff"},
+    "sv": {"å": "z\uffff", "ä": "z\uffff\uffff", "ö": "z\uffff\uffff\uffff"},
+    "nb": {"æ": "z\uffff", "ø": "z\uffff\uffff", "å": "z\uffff\uffff\uffff"},
+}
+
+
+def sort_key(lang):
+    """Return a function turning a word of lang into its sort key."""
+    if icu is not None:
+        collator = icu.Collator.createInstance(icu.Locale(ICU_LOCALES.get(lang, lang)))
+        return collator.getSortKey
+    tailoring = TAILORINGS.get(lang.split("_")[0], {})
+
+    def key(word):
+        folded = "".join(tailoring.get(char, char) for char in unicodedata.normalize("NFC", word.casefold()))
+        letters = "".join(char for char in unicodedata.normalize("NFD", folded)
+                          if not unicodedata.combining(char))
+        return letters, folded, word
+
+    return key
+
+
+def sort_words(items, lang, word=lambda item: item):
+    """Return items sorted by word(item) in the order of lang."""
+    key = sort_key(lang)
+    return sorted(items, key=lambda item: key(word(item)))
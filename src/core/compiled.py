@@ -0,0 +1,220 @@
+# compiled.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+import os
+import random
+import sqlite3
+from . import phonemes
+from .dictionary import (Dictionary, allowed, attribution, find_rhymes, find_sound_alikes, find_sound_pairs,
+                         find_word_pairs, has_any_sound, pick_examples, sort_levels)
+
+
+def load_dictionary(lang, path):
+    """Open the dictionary of lang at path: a compiled one if it ends in
+    .db, else the JSON of the bundled files.
+    """
+    if path.endswith('.db'):
+        return CompiledDictionary(lang, path)
+    return Dictionary.load(lang, path)
+
+
+def compile_dictionary(dictionary, path):
+    """Write dictionary to an indexed SQLite file at path."""
+    tmp_path = f'{path}.tmp'
+    if os.path.exists(tmp_path):
+        os.remove(tmp_path)
+    db = sqlite3.connect(tmp_path)
+    with db:
+        db.execute('CREATE TABLE entries (word TEXT PRIMARY KEY, ipa TEXT NOT NULL, plain INTEGER NOT NULL, rhyme TEXT) WITHOUT ROWID')
+        db.executemany('INSERT INTO entries VALUES (?, ?, ?, ?)',
+                       ((word, ipa, word.isalpha(), phonemes.rhyme_key(ipa))
+                        for word, ipa in dictionary.items()))
+        # words sharing an ending are found through this index
+        db.execute('CREATE INDEX entries_rhyme ON entries (rhyme)')
+        db.execute('CREATE TABLE metadata (key TEXT PRIMARY KEY, value TEXT NOT NULL)')
+        db.executemany('INSERT INTO metadata VALUES (?, ?)', dictionary.metadata.items())
+        db.execute('CREATE TABLE levels (word TEXT PRIMARY KEY, level TEXT NOT NULL) WITHOUT ROWID')
+        db.executemany('INSERT INTO levels VALUES (?, ?)', dictionary.word_levels.items())
+    db.close()
+    os.replace(tmp_path, path)
+
+
+class CompiledDictionary:
+    """A dictionary read on demand from a file made by compile_dictionary.
+
+    Nothing is loaded until the first lookup, and only the rows that
+    are asked for are read afterwards.
+    """
+
+    def __init__(self, lang, path):
+        self.lang = lang
+        self.path = path
+        self._db = None
+        self._metadata = None
+
+    @property
+    def db(self):
+        if self._db is None:
+            self._db = sqlite3.connect(f'file:{self.path}?mode=ro', uri=True,
+                                       check_same_thread=False)
+        return self._db
+
+    def close(self):
+        """Close the file; it is opened again if the dictionary is used."""
+        if self._db is not None:
+            self._db.close()
+            self._db = None
+
+    def __len__(self):
+        return self.db.execute('SELECT COUNT(*) FROM entries').fetchone()[0]
+
+    @property
+    def metadata(self):
+        if self._metadata is None:
+            try:
+                self._metadata = dict(self.db.execute('SELECT key, value FROM metadata'))
+            except sqlite3.OperationalError:
+                # compiled before metadata was kept
+                self._metadata = {}
+        return self._metadata
+
+    def attribution(self):
+        return attribution(self.lang, self.metadata)
+
+    def __contains__(self, word):
+        return self.lookup(word) is not None
+
+    def __iter__(self):
+        """The words of the dictionary, in code point order."""
+        return (word for word, in self.db.execute('SELECT word FROM entries ORDER BY word'))
+
+    def lookup(self, word):
+        """Return the IPA of word, or None if it isn't in the dictionary."""
+        row = self.db.execute('SELECT ipa FROM entries WHERE word = ?', (word,)).fetchone()
+        return row[0] if row else None
+
+    def items(self):
+        return self.db.execute('SELECT word, ipa FROM entries')
+
+    def prefix(self, text, limit=None):
+        """Return up to limit (word, ipa) of the words starting with
+        text, in code point order.
+        """
+        # a range of the primary key, the highest code point closing it
+        rows = self.db.execute('SELECT word, ipa FROM entries WHERE word >= ? AND word < ? ORDER BY word LIMIT ?',
+                               (text, text + '\U0010ffff', -1 if limit is None else limit))
+        return rows.fetchall()
+
+    def level(self, word):
+        """Return the difficulty tag of word, or None if it has none."""
+        try:
+            row = self.db.execute('SELECT level FROM levels WHERE word = ?', (word,)).fetchone()
+        except sqlite3.OperationalError:
+            # compiled before levels were kept
+            return None
+        return row[0] if row else None
+
+    def levels(self):
+        """The difficulty tags used in the dictionary, easiest first."""
+        try:
+            return sort_levels(level for level, in self.db.execute('SELECT DISTINCT level FROM levels'))
+        except sqlite3.OperationalError:
+            return []
+
+    def words_at_level(self, level):
+        try:
+            return {word for word, in self.db.execute('SELECT word FROM levels WHERE level = ?', (level,))}
+        except sqlite3.OperationalError:
+            return set()
+
+    def random_words(self, count, rng=random, containing=None, exclude=(), within=None, level=None):
+        """Pick up to count distinct plain words (letters only) at random.
+
+        If containing is a collection of sounds, only words whose
+        transcription has at least one of them are picked. Words whose
+        lowercase form is in exclude are never picked, and if within is
+        a set of lowercase words only those are. If level is given only
+        words tagged with it are picked.
+        """
+        if containing is None:
+            words = [word for word, in self.db.execute('SELECT word FROM entries WHERE plain')]
+        else:
+            # narrow down with LIKE, then check whole sounds so ɪ doesn't match aɪ
+            sounds = list(containing)
+            condition = ' OR '.join('ipa LIKE ?' for _sound in sounds)
+            rows = self.db.execute(f'SELECT word, ipa FROM entries WHERE plain AND ({condition})',
+                                   [f'%{sound}%' for sound in sounds])
+            words = [word for word, ipa in rows if has_any_sound(ipa, sounds)]
+        words = [word for word in words if allowed(word, exclude, within)]
+        if level is not None:
+            at_level = self.words_at_level(level)
+            words = [word for word in words if word in at_level]
+        return rng.sample(words, min(count, len(words)))
+
+    def rhymes(self, word, limit=50):
+        """Return up to limit (word, ipa) pairs that rhyme with word."""
+        ipa = self.lookup(word)
+        if ipa is None:
+            return []
+        key = phonemes.rhyme_key(ipa)
+        if key is None:
+            return []
+        try:
+            return self.db.execute('SELECT word, ipa FROM entries WHERE rhyme = ? AND plain AND word != ? LIMIT ?',
+                                   (key, word, limit)).fetchall()
+        except sqlite3.OperationalError:
+            # compiled before rhymes were indexed
+            return find_rhymes(word, ipa, self.items(), limit)
+
+    def minimal_pairs(self, word, limit=50):
+        """Return up to limit (other, other_ipa, (sound, other_sound))
+        for the words that differ from word in one sound.
+        """
+        ipa = self.lookup(word)
+        if ipa is None:
+            return []
+        # one sound more or less only changes the length a little
+        rows = self.db.execute('SELECT word, ipa FROM entries WHERE plain AND length(ipa) BETWEEN ? AND ?',
+                               (len(ipa) - 3, len(ipa) + 3))
+        return find_word_pairs(word, ipa, rows, limit)
+
+    def sound_pairs(self, first, second, limit=50):
+        """Return up to limit word pairs that contrast sounds first and second."""
+        rows = self.db.execute('SELECT word, ipa FROM entries WHERE plain AND (ipa LIKE ? OR ipa LIKE ?)',
+                               (f'%{first}%', f'%{second}%'))
+        return find_sound_pairs(first, second, rows, limit)
+
+    def sound_alikes(self, word, limit=20, threshold=0.75, weights=None):
+        """Return up to limit (other, other_ipa, score) for the words that
+        sound most like word, see find_sound_alikes().
+        """
+        ipa = self.lookup(word)
+        if ipa is None:
+            return []
+        rows = self.db.execute('SELECT word, ipa FROM entries WHERE plain AND length(ipa) BETWEEN ? AND ?',
+                               (len(ipa) - 4, len(ipa) + 4))
+        return find_sound_alikes(word, ipa, rows, limit, threshold, weights)
+
+    def examples(self, sound, count=3):
+        """Return count (word, ipa) with sound, shortest words first as
+        they tend to be the common ones.
+        """
+        rows = self.db.execute('SELECT word, ipa FROM entries WHERE plain AND ipa LIKE ? ORDER BY length(word), word',
+                               (f'%{sound}%',))
+        return pick_examples(rows, sound, count)
@@ -0,0 +1,109 @@
+# csv_import.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Reading word/IPA pairs from spreadsheets saved as CSV, whatever
+their columns, delimiter and encoding:
+
+    >>> rows = read_rows("Word;IPA\\ncat;/kæt/\\n", ";")
+    >>> map_rows(rows, 0, 1, header=True)
+    ([('cat', '/kæt/')], 0)
+"""
+
+import csv
+import io
+from . import notation, phonemes
+
+DELIMITERS = (",", ";", "\t", "|")
+# common names of the columns, to recognize a header row
+WORD_HEADERS = {"word", "words", "term", "spelling", "orthography", "lemma"}
+IPA_HEADERS = {"ipa", "pronunciation", "transcription", "phonetic", "phonemic"}
+
+
+def sniff_delimiter(text):
+    """The delimiter of DELIMITERS text most likely uses, "," if unsure."""
+    try:
+        return csv.Sniffer().sniff(text[:4096], delimiters="".join(DELIMITERS)).delimiter
+    except csv.Error:
+        return ","
+
+
+def read_rows(text, delimiter):
+    """The rows of text as lists of cells, leaving out empty ones."""
+    return [row for row in csv.reader(io.StringIO(text), delimiter=delimiter) if any(cell.strip() for cell in row)]
+
+
+def is_ipa_like(cell):
+    cell = cell.strip()
+    return cell.startswith(("/", "[")) or any(not char.isascii() and char.isalpha() for char in cell)
+
+
+def guess_columns(rows):
+    """(word column, IPA column, whether the first row is a header) for
+    rows, from the names of a header row or else from which column looks
+    most like IPA.
+    """
+    if not rows:
+        return 0, 1, False
+    names = [cell.strip().casefold() for cell in rows[0]]
+    if any(name in IPA_HEADERS for name in names):
+        ipa = next(i for i, name in enumerate(names) if name in IPA_HEADERS)
+        word = next((i for i, name in enumerate(names) if name in WORD_HEADERS), 0 if ipa else 1)
+        return word, ipa, True
+    columns = max(len(row) for row in rows)
+    if columns < 2:
+        return 0, 1, False
+    sample = rows[:50]
+    scores = [sum(1 for row in sample if i < len(row) and is_ipa_like(row[i])) for i in range(columns)]
+    ipa = max(range(columns), key=lambda i: (scores[i], i == 1))
+    return (0 if ipa else 1), ipa, False
+
+
+def map_rows(rows, word_column, ipa_column, header=False):
+    """Return ((word, ipa) of rows, count of rows skipped), taking each
+    from its column. Rows missing either are skipped.
+    """
+    entries = []
+    skipped = 0
+    for row in rows[1:] if header else rows:
+        word = row[word_column].strip() if word_column < len(row) else ""
+        ipa = row[ipa_column].strip() if ipa_column < len(row) else ""
+        if word and ipa:
+            entries.append((word, ipa))
+        else:
+            skipped += 1
+    return entries, skipped
+
+
+def check_symbols(entries, strict):
+    """Return (entries kept, {word: its symbols that aren't IPA} for
+    the kept ones having any, count of entries rejected). In strict mode
+    entries with such symbols are rejected, otherwise they are kept to be
+    reviewed later. Withdrawn symbols like ʤ count as IPA.
+    """
+    kept = []
+    unknown = {}
+    rejected = 0
+    for word, ipa in entries:
+        symbols = phonemes.unknown_symbols(notation.canonicalize(ipa))
+        if symbols and strict:
+            rejected += 1
+            continue
+        kept.append((word, ipa))
+        if symbols:
+            unknown[word] = "".join(symbols)
+    return kept, unknown, rejected
@@ -0,0 +1,443 @@
+# dictionary.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+import json
+import random
+import re
+import unicodedata
+from . import phonemes, similarity
+
+
+# shorter words are mostly letter names and abbreviations
+MIN_EXAMPLE_LENGTH = 3
+# and are too easily found inside longer words to mark compounds
+MIN_MORPHEME_LENGTH = 3
+# hyphen-minus, hyphen and non-breaking hyphen
+HYPHENS = re.compile("[-\u2010\u2011]")
+
+# difficulty tags are free text, these are listed first and in this order
+CEFR_LEVELS = ("A1", "A2", "B1", "B2", "C1", "C2")
+
+
+class DictionaryError(Exception):
+    """Raised when dictionary data can't be parsed."""
+
+
+def clean_input(text):
+    """Tidy up typed or imported text before it is looked up.
+
+    Returns (word, problem): word has its whitespace trimmed and
+    collapsed, problem is None, "empty", "control" if it has control
+    characters or "no-letters" if it is only emoji, digits or symbols.
+    """
+    word = " ".join(text.split())
+    if not word:
+        return word, "empty"
+    if any(unicodedata.category(char) == "Cc" for char in word):
+        return word, "control"
+    if not any(unicodedata.category(char)[0] in "LM" for char in word):
+        return word, "no-letters"
+    return word, None
+
+
+def attribution(lang, metadata):
+    """A one line citation like "en_US: ipa-dict (MIT) <url>"."""
+    if not metadata.get("source"):
+        return None
+    text = f'{lang}: {metadata["source"]}'
+    if license := metadata.get("license"):
+        text += f' ({license})'
+    if url := metadata.get("url"):
+        text += f' <{url}>'
+    return text
+
+
+def sort_levels(levels):
+    """Order difficulty tags from easiest: CEFR levels first, then the
+    others like "Grade 2" by name.
+    """
+    def key(level):
+        if level in CEFR_LEVELS:
+            return (0, CEFR_LEVELS.index(level), "")
+        return (1, 0, level)
+    return sorted(levels, key=key)
+
+
+class Dictionary:
+    """Word to IPA entries of one language.
+
+    metadata describes where the entries come from, with optional
+    "source", "license" and "url" keys. levels optionally tags words
+    with a difficulty, like "A1" or "Grade 3".
+
+    CompiledDictionary offers the same methods, so code looking words up
+    works with either; load_dictionary() opens both kinds of file.
+    """
+
+    def __init__(self, lang, entries, metadata=None, levels=None):
+        self.lang = lang
+        self.entries = entries
+        self.metadata = metadata or {}
+        self.word_levels = levels or {}
+
+    @classmethod
+    def parse(cls, lang, data):
+        """Build a dictionary from the JSON used by the bundled files.
+
+        data is the file content as bytes or str: an object with an
+        "entries" list of word -> IPA maps and an optional "levels" map
+        of word -> difficulty tag.
+        """
+        if isinstance(data, bytes):
+            data = data.decode('utf-8')
+        try:
+            parsed = json.loads(data)
+            entries = {}
+            for entry_map in parsed["entries"]:
+                entries.update(entry_map)
+            metadata = dict(parsed.get("metadata", {}))
+            levels = {word: str(level) for word, level in parsed.get("levels", {}).items()}
+        except (ValueError, KeyError, TypeError, AttributeError) as e:
+            raise DictionaryError(f"invalid dictionary for {lang}: {e}") from e
+        return cls(lang, entries, metadata, levels)
+
+    @classmethod
+    def load(cls, lang, path):
+        """Read a dictionary from a JSON file, see parse()."""
+        with open(path, 'rb') as f:
+            return cls.parse(lang, f.read())
+
+    def __len__(self):
+        return len(self.entries)
+
+    def __iter__(self):
+        """The words of the dictionary, in no particular order."""
+        return iter(self.entries)
+
+    def attribution(self):
+        return attribution(self.lang, self.metadata)
+
+    def __contains__(self, word):
+        return word in self.entries
+
+    def close(self):
+        """Nothing to close, like CompiledDictionary.close()."""
+
+    def lookup(self, word):
+        """Return the IPA of word, or None if it isn't in the dictionary."""
+        return self.entries.get(word)
+
+    def items(self):
+        return self.entries.items()
+
+    def prefix(self, text, limit=None):
+        """Return up to limit (word, ipa) of the words starting with
+        text, in code point order.
+        """
+        return sorted((word, ipa) for word, ipa in self.entries.items() if word.startswith(text))[:limit]
+
+    def level(self, word):
+        """Return the difficulty tag of word, or None if it has none."""
+        return self.word_levels.get(word)
+
+    def levels(self):
+        """The difficulty tags used in the dictionary, easiest first."""
+        return sort_levels(set(self.word_levels.values()))
+
+    def random_words(self, count, rng=random, containing=None, exclude=(), within=None, level=None):
+        """Pick up to count distinct plain words (letters only) at random.
+
+        If containing is a collection of sounds, only words whose
+        transcription has at least one of them are picked. Words whose
+        lowercase form is in exclude are never picked, and if within is
+        a set of lowercase words only those are. If level is given only
+        words tagged with it are picked.
+        """
+        words = [word for word, ipa in self.entries.items()
+                 if word.isalpha() and has_any_sound(ipa, containing)
+                 and allowed(word, exclude, within)
+                 and (level is None or self.word_levels.get(word) == level)]
+        return rng.sample(words, min(count, len(words)))
+
+    def rhymes(self, word, limit=50):
+        """Return up to limit (word, ipa) pairs that rhyme with word."""
+        ipa = self.lookup(word)
+        if ipa is None:
+            return []
+        return find_rhymes(word, ipa, self.entries.items(), limit)
+
+    def minimal_pairs(self, word, limit=50):
+        """Return up to limit (other, other_ipa, (sound, other_sound))
+        for the words that differ from word in one sound.
+        """
+        ipa = self.lookup(word)
+        if ipa is None:
+            return []
+        return find_word_pairs(word, ipa, self.entries.items(), limit)
+
+    def sound_pairs(self, first, second, limit=50):
+        """Return up to limit word pairs that contrast sounds first and second."""
+        return find_sound_pairs(first, second, self.entries.items(), limit)
+
+    def sound_alikes(self, word, limit=20, threshold=0.75, weights=None):
+        """Return up to limit (other, other_ipa, score) for the words that
+        sound most like word, see find_sound_alikes().
+        """
+        ipa = self.lookup(word)
+        if ipa is None:
+            return []
+        return find_sound_alikes(word, ipa, self.entries.items(), limit, threshold, weights)
+
+    def examples(self, sound, count=3):
+        """Return count (word, ipa) with sound, shortest words first as
+        they tend to be the common ones.
+        """
+        items = sorted(self.entries.items(), key=lambda item: (len(item[0]), item[0]))
+        return pick_examples(items, sound, count)
+
+
+def find_rhymes(word, ipa, items, limit):
+    """Plain words of items whose rhyme key matches ipa's."""
+    key = phonemes.rhyme_key(ipa)
+    if key is None:
+        return []
+    rhymes = []
+    for other, other_ipa in items:
+        if other != word and other.isalpha() and phonemes.rhyme_key(other_ipa) == key:
+            rhymes.append((other, other_ipa))
+            if len(rhymes) == limit:
+                break
+    return rhymes
+
+
+def find_word_pairs(word, ipa, items, limit):
+    """(other, other_ipa, (sound, other_sound)) for the plain words of
+    items that differ from ipa in a single sound, the ones whose sounds
+    are closest, and so hardest to tell apart, first.
+    """
+    pairs = []
+    for other, other_ipa in items:
+        if other == word or not other.isalpha():
+            continue
+        if difference := phonemes.minimal_pair(ipa, other_ipa):
+            pairs.append((other, other_ipa, difference))
+    pairs.sort(key=lambda pair: similarity.sound_distance(*pair[2]))
+    return pairs[:limit]
+
+
+def find_sound_alikes(word, ipa, items, limit, threshold=0.75, weights=None):
+    """(other, other_ipa, score) for up to limit plain words of items that
+    sound at least threshold alike to ipa, most alike first. See
+    similarity.similarity() for the score and weights.
+    """
+    length = len(phonemes.sounds(ipa))
+    found = []
+    for other, other_ipa in items:
+        # words with many more or fewer sounds can't score high
+        if other == word or not other.isalpha() or abs(len(phonemes.sounds(other_ipa)) - length) > 1:
+            continue
+        score = similarity.similarity(ipa, other_ipa, weights)
+        if score >= threshold:
+            found.append((other, other_ipa, score))
+    found.sort(key=lambda match: -match[2])
+    return found[:limit]
+
+
+def find_sound_pairs(first, second, items, limit):
+    """((word, ipa), (other, other_ipa)) pairs of plain words that only
+    differ in having first where the other has second, like ship/sheep.
+    """
+    # the sounds of each word containing first, with that sound blanked out
+    blanked = {}
+    candidates = []
+    for word, ipa in items:
+        if not word.isalpha():
+            continue
+        sounds = phonemes.sounds(ipa)
+        for i, sound in enumerate(sounds):
+            if sound == first:
+                blanked.setdefault(tuple(sounds[:i] + [None] + sounds[i+1:]), (word, ipa))
+            elif sound == second:
+                candidates.append((tuple(sounds[:i] + [None] + sounds[i+1:]), (word, ipa)))
+    pairs = []
+    for key, entry in candidates:
+        if key in blanked:
+            pairs.append((blanked[key], entry))
+            if len(pairs) == limit:
+                break
+    return pairs
+
+
+def pick_examples(items, sound, count):
+    """The first count (word, ipa) of items with sound, skipping words
+    too short to make good examples.
+    """
+    examples = []
+    for word, ipa in items:
+        if len(word) >= MIN_EXAMPLE_LENGTH and word.isalpha() and has_any_sound(ipa, [sound]):
+            examples.append((word, ipa))
+            if len(examples) == count:
+                break
+    return examples
+
+
+def word_of_the_day(dictionary, day, exclude=()):
+    """The featured word of day, a datetime.date; the same one all day
+    for a dictionary and None if it has no plain words.
+    """
+    words = dictionary.random_words(1, rng=random.Random(day.toordinal()), exclude=exclude)
+    return words[0] if words else None
+
+
+def allowed(word, exclude, within):
+    word = word.lower()
+    return word not in exclude and (within is None or word in within)
+
+
+def has_any_sound(ipa, sounds):
+    """Whether ipa contains one of sounds; always true if sounds is None."""
+    if sounds is None:
+        return True
+    return not set(sounds).isdisjoint(phonemes.split(ipa))
+
+
+def find_replace(entries, find, replace, regex=False):
+    """(word, ipa, replaced ipa) of the (word, ipa) entries that find
+    matches, with its matches replaced by replace. With regex, find is a
+    regular expression and replace may refer to its groups, like \\1;
+    re.error is raised for invalid ones.
+    """
+    if not find:
+        return []
+    pattern = re.compile(find if regex else re.escape(find))
+    if not regex:
+        # backslashes are meant literally too
+        replace = replace.replace("\\", "\\\\")
+    changes = []
+    for word, ipa in entries:
+        replaced = pattern.sub(replace, ipa)
+        if replaced != ipa:
+            changes.append((word, ipa, replaced))
+    return changes
+
+
+def find_duplicates(word, ipa, entries, editing=None):
+    """(word, ipa, kind) of the (word, ipa) entries a new entry would
+    duplicate, kind being "word" for the same word, "case" for the same
+    word in other letter case and "ipa" for another word sharing a
+    pronunciation with it, in that order. The entry being edited, whose
+    word is editing, isn't a duplicate of itself.
+    """
+    word = word.strip()
+    pronunciations = {variant.strip("/[]") for variant in phonemes.variants(ipa)}
+    kinds = ("word", "case", "ipa")
+    duplicates = []
+    for other, other_ipa in entries:
+        if other == editing or not word:
+            continue
+        if other == word:
+            kind = "word"
+        elif other.casefold() == word.casefold():
+            kind = "case"
+        elif pronunciations & {variant.strip("/[]") for variant in phonemes.variants(other_ipa)}:
+            kind = "ipa"
+        else:
+            continue
+        duplicates.append((other, other_ipa, kind))
+    return sorted(duplicates, key=lambda duplicate: kinds.index(duplicate[2]))
+
+
+def compose(word, lookup):
+    """Transcribe a word the dictionaries don't have from its parts: the
+    ones between hyphens, as in "well-known", or else the two words it
+    is made of, as in "bookshop". lookup(part) returns the IPA of part
+    or None.
+
+    Returns (ipa, parts) with a morpheme boundary between the parts, or
+    (None, None) if some part isn't found.
+    """
+    if HYPHENS.search(word):
+        parts = [part for part in HYPHENS.split(word) if part]
+        candidates = [parts] if len(parts) > 1 else []
+    else:
+        # the longest first part wins, like in mark_compound()
+        candidates = [[word[:i], word[i:]]
+                      for i in range(len(word) - MIN_MORPHEME_LENGTH, MIN_MORPHEME_LENGTH - 1, -1)]
+    for parts in candidates:
+        transcriptions = [lookup(part) for part in parts]
+        if None not in transcriptions:
+            joined = phonemes.MORPHEME_BOUNDARY.join(
+                phonemes.variants(ipa)[0].strip("/[]") for ipa in transcriptions)
+            return f"/{joined}/", parts
+    return None, None
+
+
+def mark_compound(word, ipa, lookup):
+    """Return ipa with a morpheme boundary between the two parts of
+    word if both are words of their own that together sound like word,
+    as in /ˈbʊk+ʃɒp/. lookup(part) returns the IPA of part or None.
+
+    Boundaries already in ipa are kept as they are.
+    """
+    if phonemes.MORPHEME_BOUNDARY in ipa:
+        return ipa
+    sounds = phonemes.sounds(ipa)
+    # the longest first part wins, so "doorknob" is door+knob rather than do+orknob
+    for i in range(len(word) - MIN_MORPHEME_LENGTH, MIN_MORPHEME_LENGTH - 1, -1):
+        first, second = lookup(word[:i]), lookup(word[i:])
+        if first is None or second is None:
+            continue
+        first_sounds = phonemes.sounds(first)
+        if first_sounds + phonemes.sounds(second) == sounds:
+            return phonemes.mark_boundary(ipa, len(first_sounds))
+    return ipa
+
+
+def word_to_ipa(word, dictionaries):
+    """Look word up in each dictionary in order and return the first hit."""
+    for dictionary in dictionaries:
+        if (ipa := dictionary.lookup(word)) is not None:
+            return ipa
+    return None
+
+
+def case_forms(word):
+    """The forms of word lookup_word() tries, with how each one matches:
+    as typed ("exact"), lowercased ("lowercase") and as a proper noun,
+    like "Paris" ("proper-noun").
+    """
+    forms = {}
+    for form, match in ((word, "exact"), (word.lower(), "lowercase"), (word.title(), "proper-noun")):
+        forms.setdefault(form, match)
+    return list(forms.items())
+
+
+def lookup_word(word, dictionaries):
+    """Like word_to_ipa(), but words typed in another case than the
+    dictionary's are found too, so "IPA", "iPhone", "Cat" and "paris"
+    all work. Returns (ipa, form found, match) with match as in
+    case_forms(), or (None, None, None).
+
+    Every dictionary is searched for a form before the next is tried,
+    so a word as typed wins over another one differing only in case.
+    """
+    for form, match in case_forms(word):
+        if (ipa := word_to_ipa(form, dictionaries)) is not None:
+            return ipa, form, match
+    return None, None, None
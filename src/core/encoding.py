@@ -0,0 +1,72 @@
+# encoding.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Reading text files of unknown encoding, as word lists and
+dictionaries often come from older tools and Windows:
+
+    >>> decode("café".encode("latin-1"))
+    ('café', 0)
+"""
+
+# Python codec names; utf-8-sig also reads UTF-8 with a byte order mark
+ENCODINGS = ("utf-8-sig", "utf-16", "latin-1", "cp1252")
+BOMS = [
+    (b"\xef\xbb\xbf", "utf-8-sig"),
+    (b"\xff\xfe\x00\x00", "utf-32"),
+    (b"\x00\x00\xfe\xff", "utf-32"),
+    (b"\xff\xfe", "utf-16"),
+    (b"\xfe\xff", "utf-16"),
+]
+# bytes of the start looked at to tell UTF-16 without a byte order mark
+SAMPLE_SIZE = 4096
+REPLACEMENT = "\ufffd"
+
+
+def detect(data):
+    """The codec data most likely is in: as its byte order mark says,
+    UTF-16 if every other byte is zero as in mostly Latin text, UTF-8 if
+    it reads as that and else Windows-1252, or Latin-1 for the bytes
+    Windows-1252 leaves out.
+    """
+    for bom, encoding in BOMS:
+        if data.startswith(bom):
+            return encoding
+    sample = data[:SAMPLE_SIZE - SAMPLE_SIZE % 2]
+    if sample and sample[1::2].count(0) > len(sample) // 4:
+        return "utf-16-le"
+    if sample and sample[0::2].count(0) > len(sample) // 4:
+        return "utf-16-be"
+    for encoding in ("utf-8", "cp1252"):
+        try:
+            data.decode(encoding)
+        except UnicodeDecodeError:
+            continue
+        return encoding
+    return "latin-1"
+
+
+def decode(data, encoding=None):
+    """Return (text of data, count of characters that couldn't be read
+    and were replaced by U+FFFD) in encoding, detected if None.
+    """
+    encoding = encoding or detect(data)
+    try:
+        return data.decode(encoding), 0
+    except UnicodeDecodeError:
+        text = data.decode(encoding, errors="replace")
+        return text, text.count(REPLACEMENT) - data.decode(encoding, errors="ignore").count(REPLACEMENT)
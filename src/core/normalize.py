@@ -0,0 +1,225 @@
+# normalize.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Spelling out numbers and abbreviations, which dictionaries don't
+have, as the words they are read as:
+
+    >>> expand("Dr. Who at 42", "en_US")
+    'doctor Who at forty two'
+
+Compound number words are split into the parts dictionaries have, so
+42 is "forty two" and not "forty-two". Languages without rules here
+are left as they are.
+"""
+
+import re
+from . import phonemes
+from .dictionary import lookup_word
+
+ONES = {
+    "en": ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+           "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+           "nineteen"],
+    "de": ["null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn",
+           "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn", "achtzehn",
+           "neunzehn"],
+    "es": ["cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez",
+           "once", "doce", "trece", "catorce", "quince", "dieciséis", "diecisiete", "dieciocho",
+           "diecinueve", "veinte", "veintiuno", "veintidós", "veintitrés", "veinticuatro",
+           "veinticinco", "veintiséis", "veintisiete", "veintiocho", "veintinueve"],
+    "fr": ["zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix",
+           "onze", "douze", "treize", "quatorze", "quinze", "seize"],
+}
+# tens from twenty, or from thirty for Spanish
+TENS = {
+    "en": ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"],
+    "de": ["zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig"],
+    "es": ["treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa"],
+    "fr": ["vingt", "trente", "quarante", "cinquante", "soixante"],
+}
+SPANISH_HUNDREDS = ["ciento", "doscientos", "trescientos", "cuatrocientos", "quinientos", "seiscientos",
+                    "setecientos", "ochocientos", "novecientos"]
+ENGLISH_ORDINALS = {"one": "first", "two": "second", "three": "third", "five": "fifth", "eight": "eighth",
+                    "nine": "ninth", "twelve": "twelfth"}
+# numbers from a billion on are left as digits
+LIMIT = 10 ** 9
+
+# what common abbreviations are read as, by language without region
+ABBREVIATIONS = {
+    "en": {
+        "dr.": "doctor", "mr.": "mister", "mrs.": "missus", "prof.": "professor", "jr.": "junior",
+        "sr.": "senior", "etc.": "et cetera", "e.g.": "for example", "i.e.": "that is",
+        "vs.": "versus", "no.": "number", "approx.": "approximately", "dept.": "department",
+        "mt.": "mount",
+    },
+    "de": {
+        "dr.": "Doktor", "prof.": "Professor", "hr.": "Herr", "fr.": "Frau", "nr.": "Nummer",
+        "z.b.": "zum Beispiel", "usw.": "und so weiter", "bzw.": "beziehungsweise", "ca.": "circa",
+        "d.h.": "das heißt",
+    },
+    "es": {
+        "dr.": "doctor", "dra.": "doctora", "sr.": "señor", "sra.": "señora", "srta.": "señorita",
+        "ud.": "usted", "uds.": "ustedes", "etc.": "etcétera", "p.ej.": "por ejemplo", "núm.": "número",
+    },
+    "fr": {
+        "dr": "docteur", "dr.": "docteur", "m.": "monsieur", "mme": "madame", "mlle": "mademoiselle",
+        "etc.": "et cetera", "p.ex.": "par exemple",
+    },
+}
+
+ORDINAL = re.compile(r"(\d+)(st|nd|rd|th)")
+# 1,000 in English, 1.000 in the others
+GROUPED = {"en": re.compile(r"\d{1,3}(,\d{3})+"), "de": re.compile(r"\d{1,3}(\.\d{3})+"),
+           "es": re.compile(r"\d{1,3}(\.\d{3})+"), "fr": re.compile(r"\d{1,3}(\.\d{3})+")}
+
+
+def _english(n):
+    if n < 20:
+        return [ONES["en"][n]]
+    if n < 100:
+        return [TENS["en"][n // 10 - 2]] + ([ONES["en"][n % 10]] if n % 10 else [])
+    if n < 1000:
+        return [ONES["en"][n // 100], "hundred"] + (_english(n % 100) if n % 100 else [])
+    for value, name in ((10 ** 6, "million"), (1000, "thousand")):
+        if n >= value:
+            return _english(n // value) + [name] + (_english(n % value) if n % value else [])
+
+
+def _german(n):
+    if n < 20:
+        return [ONES["de"][n]]
+    if n < 100:
+        # 42 is said "two and forty"
+        units = ["ein" if n % 10 == 1 else ONES["de"][n % 10], "und"] if n % 10 else []
+        return units + [TENS["de"][n // 10 - 2]]
+    if n < 1000:
+        count = [] if n // 100 == 1 else _german(n // 100)
+        return count + ["hundert"] + (_german(n % 100) if n % 100 else [])
+    if n < 10 ** 6:
+        count = [] if n // 1000 == 1 else _german(n // 1000)
+        return [word if word != "eins" else "ein" for word in count] + ["tausend"] + (
+            _german(n % 1000) if n % 1000 else [])
+    millions = ["eine", "Million"] if n // 10 ** 6 == 1 else _german(n // 10 ** 6) + ["Millionen"]
+    return millions + (_german(n % 10 ** 6) if n % 10 ** 6 else [])
+
+
+def _spanish(n):
+    if n < 30:
+        return [ONES["es"][n]]
+    if n < 100:
+        return [TENS["es"][n // 10 - 3]] + (["y", ONES["es"][n % 10]] if n % 10 else [])
+    if n == 100:
+        return ["cien"]
+    if n < 1000:
+        return [SPANISH_HUNDREDS[n // 100 - 1]] + (_spanish(n % 100) if n % 100 else [])
+    if n < 10 ** 6:
+        # "uno" becomes "un" before a noun like mil
+        count = [] if n // 1000 == 1 else [{"uno": "un", "veintiuno": "veintiún"}.get(word, word)
+                                            for word in _spanish(n // 1000)]
+        return count + ["mil"] + (_spanish(n % 1000) if n % 1000 else [])
+    millions = ["un", "millón"] if n // 10 ** 6 == 1 else [
+        {"uno": "un", "veintiuno": "veintiún"}.get(word, word) for word in _spanish(n // 10 ** 6)] + ["millones"]
+    return millions + (_spanish(n % 10 ** 6) if n % 10 ** 6 else [])
+
+
+def _french(n):
+    if n < 17:
+        return [ONES["fr"][n]]
+    if n < 20:
+        return ["dix", ONES["fr"][n - 10]]
+    if n < 70:
+        tens, units = TENS["fr"][n // 10 - 2], n % 10
+        if units == 1:
+            return [tens, "et", "un"]
+        return [tens] + ([ONES["fr"][units]] if units else [])
+    if n < 80:
+        # 71 is "sixty and eleven", 72 "sixty twelve"
+        return ["soixante"] + (["et"] if n == 71 else []) + _french(n - 60)
+    if n < 100:
+        return ["quatre", "vingts"] if n == 80 else ["quatre", "vingt"] + _french(n - 80)
+    if n < 1000:
+        hundreds, rest = n // 100, n % 100
+        count = [] if hundreds == 1 else [ONES["fr"][hundreds]]
+        return count + ["cents" if hundreds > 1 and not rest else "cent"] + (_french(rest) if rest else [])
+    if n < 10 ** 6:
+        count = [] if n // 1000 == 1 else _french(n // 1000)
+        return count + ["mille"] + (_french(n % 1000) if n % 1000 else [])
+    millions = ["un", "million"] if n // 10 ** 6 == 1 else _french(n // 10 ** 6) + ["millions"]
+    return millions + (_french(n % 10 ** 6) if n % 10 ** 6 else [])
+
+
+SPELLERS = {"en": _english, "de": _german, "es": _spanish, "fr": _french}
+
+
+def number_words(n, lang):
+    """The words n is read as in lang, or None if there are no rules
+    for lang or n is too large.
+    """
+    speller = SPELLERS.get(lang.split("_")[0])
+    if speller is None or not 0 <= n < LIMIT:
+        return None
+    return speller(n)
+
+
+def _expand_token(token, lang):
+    base = lang.split("_")[0]
+    if (expansion := ABBREVIATIONS.get(base, {}).get(token.casefold())) is not None:
+        return expansion
+    digits = token
+    if base in GROUPED and GROUPED[base].fullmatch(token):
+        digits = re.sub(r"\D", "", token)
+    if digits.isascii() and digits.isdigit():
+        words = number_words(int(digits), lang)
+        return " ".join(words) if words else token
+    if base == "en" and (match := ORDINAL.fullmatch(token)):
+        words = number_words(int(match[1]), lang)
+        if words is None:
+            return token
+        last = words[-1]
+        if last in ENGLISH_ORDINALS:
+            last = ENGLISH_ORDINALS[last]
+        elif last.endswith("y"):
+            last = last[:-1] + "ieth"
+        else:
+            last += "th"
+        return " ".join(words[:-1] + [last])
+    return token
+
+
+def expand(text, lang):
+    """Return text with its numbers and the abbreviations of lang
+    spelled out, or text itself if it has none.
+    """
+    return " ".join(_expand_token(token, lang) for token in text.split())
+
+
+def lookup_expanded(text, lang, dictionaries):
+    """Look up text spelled out with expand(), as a whole or word by
+    word. Returns (ipa, spelled out text), or (None, None) if there was
+    nothing to spell out or a word isn't in dictionaries.
+    """
+    spelled = expand(text, lang)
+    if spelled == text:
+        return None, None
+    if (ipa := lookup_word(spelled, dictionaries)[0]) is not None:
+        return ipa, spelled
+    transcriptions = [lookup_word(word, dictionaries)[0] for word in spelled.split()]
+    if None in transcriptions:
+        return None, None
+    # the first variant of each word, read as one phrase
+    return "/" + " ".join(phonemes.variants(ipa)[0].strip("/[]") for ipa in transcriptions) + "/", spelled
@@ -0,0 +1,155 @@
+# notation.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Transcription conventions applied when a result is shown.
+
+Dictionaries differ in how they write the same sound, e.g. /r/ or /ɹ/
+and /e/ or /ɛ/. A profile rewrites a transcription from the source's
+convention into the one the user is used to.
+"""
+
+import re
+import unicodedata
+from .phonemes import MULTI_CHAR, TIE_BARS, variants
+
+# id -> {"name", "languages", "rules"}; rules map a sound to its spelling
+PROFILES = {
+    "wells": {
+        "name": "Wells",
+        "languages": {"en_UK", "en_US"},
+        "rules": {"ɹ": "r", "ɛ": "e", "ɛə": "eə", "ɚ": "ər"},
+    },
+    "merriam-webster": {
+        "name": "Merriam-Webster",
+        "languages": {"en_UK", "en_US"},
+        "rules": {"ɹ": "r", "e": "ɛ", "eə": "ɛə"},
+    },
+}
+
+
+def profiles_for(lang):
+    """Return the ids of the profiles that apply to lang."""
+    return [profile for profile, info in PROFILES.items() if lang in info["languages"]]
+
+
+def convert(ipa, profile):
+    """Rewrite ipa with the rules of profile; unknown profiles keep it as is."""
+    if profile not in PROFILES:
+        return ipa
+    rules = PROFILES[profile]["rules"]
+    # multi character sounds are matched first so the "e" of "eɪ" stays
+    sequences = sorted(set(rules) | set(MULTI_CHAR), key=len, reverse=True)
+    converted = []
+    i = 0
+    while i < len(ipa):
+        for seq in sequences:
+            if ipa.startswith(seq, i):
+                converted.append(rules.get(seq, seq))
+                i += len(seq)
+                break
+        else:
+            converted.append(ipa[i])
+            i += 1
+    return "".join(converted)
+
+
+# symbols the IPA has withdrawn, mostly in 1989, and how the sounds are
+# written now; older dictionaries and fonts still use them
+ALIASES = {
+    "ʤ": "d͡ʒ",
+    "ʧ": "t͡ʃ",
+    "ʦ": "t͡s",
+    "ʣ": "d͡z",
+    "ʨ": "t͡ɕ",
+    "ʥ": "d͡ʑ",
+    "ɷ": "ʊ",
+    "ɩ": "ɪ",
+    "ʚ": "ɞ",
+    "ɼ": "r̝",
+    "ʇ": "ǀ",
+    "ʖ": "ǁ",
+    "ʗ": "ǃ",
+}
+
+
+def canonicalize(ipa):
+    """Replace the withdrawn symbols of ipa by the ones used now, e.g.
+    /ʤæm/ by /d͡ʒæm/.
+    """
+    return "".join(ALIASES.get(char, char) for char in ipa)
+
+
+def former_symbols(symbol):
+    """The withdrawn symbols that were written for symbol, with or
+    without its tie bar, e.g. ["ʤ"] for "dʒ".
+    """
+    def untied(text):
+        return "".join(char for char in text if char not in TIE_BARS)
+    return [old for old, new in ALIASES.items() if untied(new) == untied(symbol)]
+
+
+# how optional sounds like the "ə" of /ˈɒf(ə)n/ are shown
+OPTIONAL_MODES = ("keep", "both", "with", "without")
+# how diacritics are encoded: as in the dictionary, precomposed where
+# possible (NFC) or always as combining characters (NFD)
+NORMALIZATION_FORMS = ("keep", "NFC", "NFD")
+
+
+def normalize(ipa, form):
+    """Encode the diacritics of ipa in one way, so copied transcriptions
+    compare and search alike in other tools. form is one of
+    NORMALIZATION_FORMS.
+    """
+    if form == "keep":
+        return ipa
+    return unicodedata.normalize(form, ipa)
+
+
+def optional_segments(ipa):
+    """Split ipa into (text, optional) parts, e.g. /ˈɒf(ə)n/ into
+    ("/ˈɒf", False), ("ə", True), ("n/", False).
+    """
+    segments = []
+    for part in re.split(r'(\([^()]*\))', ipa):
+        if part.startswith("(") and part.endswith(")"):
+            segments.append((part[1:-1], True))
+        elif part:
+            segments.append((part, False))
+    return segments
+
+
+def format_optional(ipa, mode):
+    """Show the optional sounds of ipa as mode says.
+
+    "keep" leaves the parentheses, "with" and "without" pick one
+    pronunciation and "both" lists them as variants.
+    """
+    if mode == "keep" or "(" not in ipa:
+        return ipa
+    result = []
+    for variant in variants(ipa):
+        segments = optional_segments(variant)
+        full = "".join(text for text, _optional in segments)
+        short = "".join(text for text, optional in segments if not optional)
+        if mode == "with" or (mode == "both" and full == short):
+            result.append(full)
+        elif mode == "without":
+            result.append(short)
+        else:
+            result += [full, short]
+    return ", ".join(result)
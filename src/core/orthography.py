@@ -0,0 +1,88 @@
+# orthography.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Spelling rules of a user dictionary: which letters stand for which
+sounds, for languages whose spelling is regular, like most constructed
+ones.
+
+Rules are written one per line as "letters = sounds":
+
+    >>> rules = parse_rules("sh = ʃ\\na = a\\nt = t")
+    >>> transcribe("tasha", rules)
+    ('/taʃa/', [])
+
+The longest letters matching at each point win, so "sh" is read before
+"s". Letters without a rule are returned so they can be pointed out.
+"""
+
+SEPARATORS = ["=", "→", "->"]
+
+
+class RuleError(Exception):
+    """Raised for a line without a separator; line is its number."""
+
+    def __init__(self, line):
+        super().__init__(f"line {line}: expected letters = sounds")
+        self.line = line
+
+
+def parse_rules(text):
+    """Parse "letters = sounds" lines into a letters -> sounds dict,
+    skipping blank lines and ones starting with "#".
+    """
+    rules = {}
+    for number, line in enumerate(text.splitlines(), 1):
+        line = line.strip()
+        if not line or line.startswith("#"):
+            continue
+        for separator in SEPARATORS:
+            if separator in line:
+                letters, _separator, sounds = line.partition(separator)
+                break
+        else:
+            raise RuleError(number)
+        letters = letters.strip().casefold()
+        if not letters:
+            raise RuleError(number)
+        # "h = ∅" for silent letters
+        rules[letters] = sounds.strip().replace(" ", "").replace("∅", "")
+    return rules
+
+
+def transcribe(word, rules):
+    """Return (ipa, letters without a rule) for word spelled by rules.
+    Spaces and hyphens are kept as they are.
+    """
+    word = word.casefold()
+    longest = max((len(letters) for letters in rules), default=0)
+    sounds = []
+    unknown = []
+    i = 0
+    while i < len(word):
+        for length in range(min(longest, len(word) - i), 0, -1):
+            if (letters := word[i:i + length]) in rules:
+                sounds.append(rules[letters])
+                i += length
+                break
+        else:
+            if word[i] in " -":
+                sounds.append(" ")
+            elif word[i] not in unknown:
+                unknown.append(word[i])
+            i += 1
+    return f"/{''.join(sounds).strip()}/", unknown
@@ -0,0 +1,326 @@
+# phonemes.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+import unicodedata
+
+STRESS_MARKS = "ˈˌ"
+# between the parts of a compound or derived word, as in /ˈbʊk+ʃɒp/
+MORPHEME_BOUNDARY = "+"
+# characters that only separate things and are not sounds themselves
+SEPARATORS = "/[]. -‿" + STRESS_MARKS + MORPHEME_BOUNDARY
+# modifier letters that belong to the symbol before them
+MODIFIERS = "ːˑʰʷʲˠˤ˞ⁿˡ"
+TIE_BARS = "͜͡"
+# sequences that are taught as a single sound
+MULTI_CHAR = [
+    "eɪ", "aɪ", "ɔɪ", "aʊ", "əʊ", "oʊ", "ɪə", "eə", "ʊə",
+    "tʃ", "dʒ",
+]
+VOWELS = "aeiouyæɑɒɐɔəɘɛɜɞɤɨɪʉʊʌʏøœɶɯɵɚɝ"
+SYLLABIC = "\u0329"
+# the letters of the IPA chart, and the g most fonts draw for ɡ
+CONSONANTS = ("pbtdʈɖcɟkɡgqɢʔmɱnɳɲŋɴʙrʀⱱɾɽɸβfvθðszʃʒʂʐçʝxɣχʁħʕhɦɬɮʋɹɻjɰlɭʎʟ"
+              "ʘǀǃǂǁɓɗʄɠʛʍwɥʜʢʡɕʑɺɧɫ")
+# tone letters and arrows, and the bars between groups
+PROSODY = "˥˦˧˨˩꜒꜓꜔꜕꜖↗↘ꜛꜜ|‖"
+# raised letters for breathy voice, prenasalization and the like
+SUPERSCRIPTS = "ʱᵝᵐᵑᶬᶮᶯᵊᶿˣʼ"
+# consonants that can start a syllable together with a following liquid or glide
+OBSTRUENTS = "pbtdkɡgfvθʃs"
+LIQUIDS = "lɹrjw"
+
+
+def variants(ipa):
+    """Split a dictionary value like "/a/, /b/" into its variants."""
+    return [variant.strip() for variant in ipa.split(",") if variant.strip()]
+
+
+def merge_variants(*values):
+    """Join the variants of dictionary values into one, each once."""
+    merged = []
+    for ipa in values:
+        merged += [variant for variant in variants(ipa) if variant not in merged]
+    return ", ".join(merged)
+
+
+def tokens(ipa):
+    """Split a transcription into (text, is_sound) pieces that together
+    make up all of ipa, so slashes and stress marks are kept.
+    """
+    pieces = []
+    i = 0
+    while i < len(ipa):
+        char = ipa[i]
+        if char in SEPARATORS or char == ",":
+            pieces.append((char, False))
+            i += 1
+            continue
+
+        for seq in MULTI_CHAR:
+            if ipa.startswith(seq, i):
+                segment = seq
+                break
+        else:
+            segment = char
+        i += len(segment)
+
+        # pull in diacritics, length marks and tied characters
+        while i < len(ipa):
+            next_char = ipa[i]
+            if next_char in TIE_BARS and i + 1 < len(ipa):
+                segment += ipa[i:i+2]
+                i += 2
+            elif next_char in MODIFIERS or unicodedata.combining(next_char):
+                segment += next_char
+                i += 1
+            else:
+                break
+        pieces.append((segment, True))
+    return pieces
+
+
+def split(ipa):
+    """Split a transcription into its sounds, dropping slashes and stress."""
+    return [text for text, is_sound in tokens(ipa) if is_sound]
+
+
+def base(sound):
+    """sound without length marks, diacritics and tie bars, e.g. iː -> i."""
+    return "".join(char for char in sound
+                   if char not in MODIFIERS and char not in TIE_BARS
+                   and not unicodedata.combining(char))
+
+
+def inventory(transcriptions):
+    """The set of sounds used by any variant of the transcriptions."""
+    sounds = set()
+    for ipa in transcriptions:
+        sounds.update(split(ipa))
+    return sounds
+
+
+def outside_inventory(ipa, inventory):
+    """The sounds of ipa that aren't in inventory, in order and once
+    each. A long or otherwise modified sound counts as in inventory if
+    its base sound is.
+    """
+    outside = []
+    for sound in split(ipa):
+        if sound not in inventory and base(sound) not in inventory and sound not in outside:
+            outside.append(sound)
+    return outside
+
+
+def unknown_symbols(ipa):
+    """The characters of ipa that aren't IPA, in order and once each.
+    Diacritics are all accepted, also precomposed as in "ã".
+    """
+    known = set(VOWELS + CONSONANTS + MODIFIERS + SUPERSCRIPTS + TIE_BARS + SEPARATORS + PROSODY + "(),\u200d")
+    unknown = []
+    for char in ipa:
+        parts = unicodedata.normalize("NFD", char)
+        if any(part not in known and unicodedata.category(part) != "Mn" for part in parts) and char not in unknown:
+            unknown.append(char)
+    return unknown
+
+
+def is_vowel(segment):
+    return segment[0] in VOWELS
+
+
+def is_nucleus(segment):
+    return is_vowel(segment) or SYLLABIC in segment
+
+
+def syllables(ipa):
+    """Split one transcription into (marks, sounds) syllables.
+
+    marks are the stress mark and morpheme boundary in front of the
+    syllable, like "ˈ", "+" or "", and are kept as boundaries like
+    dots are. Elsewhere a single consonant between two vowels starts
+    the next syllable and a cluster is split before its last consonant,
+    or before an obstruent followed by a liquid (ˈeɪ.prəl).
+    """
+    items = [(text, is_sound) for text, is_sound in tokens(ipa)
+             if is_sound or text in STRESS_MARKS + MORPHEME_BOUNDARY + "."]
+    nuclei = [i for i, (text, is_sound) in enumerate(items) if is_sound and is_nucleus(text)]
+    cuts = []
+    for a, b in zip(nuclei, nuclei[1:]):
+        marks = [i for i in range(a + 1, b) if not items[i][1]]
+        if marks:
+            cuts.append(marks[0])
+        elif b - a <= 2:
+            cuts.append(a + 1)
+        elif base(items[b - 1][0]) in LIQUIDS and base(items[b - 2][0]) in OBSTRUENTS:
+            cuts.append(b - 2)
+        else:
+            cuts.append(b - 1)
+
+    result = []
+    for start, end in zip([0] + cuts, cuts + [len(items)]):
+        marks = "".join(text for text, is_sound in items[start:end] if text in MORPHEME_BOUNDARY)
+        marks += "".join(text for text, is_sound in items[start:end] if text in STRESS_MARKS)[:1]
+        sounds = [text for text, is_sound in items[start:end] if is_sound]
+        if sounds:
+            result.append((marks, sounds))
+    return result
+
+
+def format_syllables(ipa, dots=True, render=None):
+    """ipa with syllables separated by dots, or stress marks where a
+    syllable is stressed, e.g. /prəˌnʌn.siˈeɪ.ʃən/.
+
+    render(marks, sounds) returns the text of one syllable, by default
+    its sounds joined together.
+    """
+    render = render or (lambda marks, sounds: "".join(sounds))
+    formatted = []
+    for variant in variants(ipa):
+        opening = variant[0] if variant[0] in "/[" else ""
+        closing = variant[-1] if variant[-1] in "/]" else ""
+        words = []
+        for word in variant[len(opening):len(variant) - len(closing)].split(" "):
+            text = ""
+            for i, (marks, sounds) in enumerate(syllables(word)):
+                text += marks or ("." if dots and i else "")
+                text += render(marks, sounds)
+            words.append(text)
+        formatted.append(opening + " ".join(words) + closing)
+    return ", ".join(formatted)
+
+
+def strip_boundaries(ipa):
+    return ipa.replace(MORPHEME_BOUNDARY, "")
+
+
+def mark_boundary(ipa, count):
+    """Put a morpheme boundary into the first variant of ipa after its
+    first count sounds, in front of any stress mark that follows.
+    """
+    found = variants(ipa)
+    if not found:
+        return ipa
+    marked = ""
+    seen = 0
+    for text, is_sound in tokens(found[0]):
+        if seen == count and (is_sound or text in STRESS_MARKS):
+            marked += MORPHEME_BOUNDARY
+            count = None
+        seen += is_sound
+        marked += text
+    return ", ".join([marked] + found[1:])
+
+
+def rhyme_key(ipa):
+    """Return the sounds words must end in to rhyme with ipa.
+
+    That is the last stressed vowel and everything after it, or the
+    last vowel if there is no stress mark. None if there is no vowel.
+    """
+    found = variants(ipa)
+    if not found:
+        return None
+    ipa = found[0].replace("(", "").replace(")", "")
+    stressed = "ˈ" in ipa
+    segments = split(ipa[ipa.rfind("ˈ"):] if stressed else ipa)
+    vowels = [i for i, segment in enumerate(segments) if is_vowel(segment)]
+    if not vowels:
+        return None
+    return "".join(segments[vowels[0] if stressed else vowels[-1]:])
+
+
+def sounds(ipa):
+    """The sounds of the first variant of ipa."""
+    found = variants(ipa)
+    return split(found[0]) if found else []
+
+
+def minimal_pair(first, second):
+    """Return the (first, second) sounds two transcriptions differ in if
+    they differ in exactly one sound, otherwise None.
+    """
+    a, b = sounds(first), sounds(second)
+    if len(a) != len(b):
+        return None
+    differences = [(x, y) for x, y in zip(a, b) if x != y]
+    return differences[0] if len(differences) == 1 else None
+
+
+def align(expected, answer):
+    """Align two transcriptions sound by sound.
+
+    Returns a list of (expected, given) segment pairs where a missing
+    side is None, using the minimal number of edits.
+    """
+    a, b = split(expected), split(answer)
+    # cost[i][j]: edits needed to turn a[:i] into b[:j]
+    cost = [[0] * (len(b) + 1) for _ in range(len(a) + 1)]
+    for i in range(len(a) + 1):
+        cost[i][0] = i
+    for j in range(len(b) + 1):
+        cost[0][j] = j
+    for i in range(1, len(a) + 1):
+        for j in range(1, len(b) + 1):
+            cost[i][j] = min(cost[i-1][j] + 1,
+                             cost[i][j-1] + 1,
+                             cost[i-1][j-1] + (a[i-1] != b[j-1]))
+
+    pairs = []
+    i, j = len(a), len(b)
+    while i or j:
+        if i and j and cost[i][j] == cost[i-1][j-1] + (a[i-1] != b[j-1]):
+            pairs.append((a[i-1], b[j-1]))
+            i, j = i - 1, j - 1
+        elif i and cost[i][j] == cost[i-1][j] + 1:
+            pairs.append((a[i-1], None))
+            i -= 1
+        else:
+            pairs.append((None, b[j-1]))
+            j -= 1
+    pairs.reverse()
+    return pairs
+
+
+def align_cells(first, other):
+    """other's sounds in one cell per sound of first, for showing them
+    in columns. Sounds of other with no counterpart join the cell of a
+    neighbour and cells other has no sound for are None.
+    """
+    cells = []
+    extra = ""
+    for a, b in align(first, other):
+        if a is None and cells:
+            cells[-1] = (cells[-1] or "") + b
+        elif a is None:
+            extra += b
+        else:
+            cells.append(extra + b if b else extra or None)
+            extra = ""
+    return cells
+
+
+def shared_sounds(transcriptions):
+    """The sounds every one of transcriptions has."""
+    found = [set(split(ipa)) for ipa in transcriptions]
+    return set.intersection(*found) if found else set()
+
+
+def distance(expected, answer):
+    """Number of sounds that have to be inserted, removed or replaced."""
+    return sum(1 for a, b in align(expected, answer) if a != b)
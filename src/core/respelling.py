@@ -0,0 +1,85 @@
+# respelling.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""English-style respellings of IPA, like "pruh-nun-see-AY-shun".
+
+They are meant for readers who don't know IPA, so each sound gets the
+letters an English speaker would read it with, syllables are joined
+with hyphens and the stressed one is written in capitals.
+"""
+
+from . import phonemes
+
+# languages whose speakers read the respellings as intended
+LANGUAGES = {"en_UK", "en_US"}
+
+# sound (without length marks) -> letters
+SPELLINGS = {
+    "p": "p", "b": "b", "t": "t", "d": "d", "k": "k", "g": "g", "ɡ": "g",
+    "f": "f", "v": "v", "θ": "th", "ð": "dh", "s": "s", "z": "z",
+    "ʃ": "sh", "ʒ": "zh", "h": "h", "x": "kh", "tʃ": "ch", "dʒ": "j",
+    "m": "m", "n": "n", "ŋ": "ng", "l": "l", "ɫ": "l", "r": "r", "ɹ": "r",
+    "j": "y", "w": "w", "ɾ": "t", "ʔ": "",
+    "æ": "a", "a": "a", "ɑ": "ah", "ɒ": "o", "ɔ": "aw", "ɛ": "e", "e": "e",
+    "ɪ": "i", "i": "ee", "ʊ": "uu", "u": "oo", "ɜ": "ur", "ɝ": "ur", "ɚ": "er",
+    "eɪ": "ay", "aɪ": "y", "ɔɪ": "oy", "aʊ": "ow", "oʊ": "oh", "əʊ": "oh", "o": "oh",
+    "ɪə": "eer", "eə": "air", "ɛə": "air", "ʊə": "oor",
+}
+# spelled differently when the syllable is closed by a consonant
+OPEN = {"ə": "uh", "ʌ": "uh"}
+CLOSED = {"ə": "u", "ʌ": "u"}
+RHOTIC = "rɹ"
+
+
+def respell_syllable(sounds):
+    bases = [phonemes.base(sound) for sound in sounds]
+    letters = []
+    i = 0
+    while i < len(bases):
+        sound = bases[i]
+        following = bases[i + 1:]
+        if sound in "əʌɜ" and following and following[0] in RHOTIC:
+            # r-coloured vowels: nurse, letter
+            letters.append("ur" if sound == "ɜ" else "er")
+            i += 2
+            continue
+        if sound in OPEN:
+            letters.append(CLOSED[sound] if following else OPEN[sound])
+        elif sound == "aɪ" and i == 0:
+            letters.append("eye")
+        else:
+            letters.append(SPELLINGS.get(sound, sound))
+        i += 1
+    return "".join(letters)
+
+
+def respell(ipa):
+    """Respell the first variant of ipa, e.g. /prəˌnʌnsiˈeɪʃən/ as
+    pruh-nun-see-AY-shun. Sounds without a spelling are kept as they are.
+    """
+    found = phonemes.variants(ipa)
+    if not found:
+        return ""
+    words = []
+    for word in found[0].strip("/[]").split(" "):
+        respelled = []
+        for marks, sounds in phonemes.syllables(word):
+            syllable = respell_syllable(sounds)
+            respelled.append(syllable.upper() if "ˈ" in marks else syllable)
+        words.append("-".join(respelled))
+    return " ".join(words)
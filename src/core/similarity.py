@@ -0,0 +1,238 @@
+# similarity.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""How alike two sounds or two transcriptions are.
+
+Sounds are compared by their articulatory features: voicing, place and
+manner of articulation for consonants, height, backness and rounding
+for vowels, and length for both. How much each feature counts can be
+changed by passing weights, a dict with some of the keys of
+DEFAULT_WEIGHTS:
+
+    >>> from word2ipa.core import similarity
+    >>> similarity.sound_distance("p", "b") < similarity.sound_distance("p", "s")
+    True
+    >>> round(similarity.similarity("/ʃɪp/", "/ʃiːp/", weights={"length": 0}), 2)
+    0.98
+
+sound_distance() is between 0 for the same sound and 1 for sounds
+with nothing in common, like a vowel and a consonant. distance() is
+the weighted number of edits between two transcriptions, where an
+added or dropped sound costs 1 and a replaced one its sound_distance(),
+and similarity() scales that to between 0 and 1 for the same sounds.
+"""
+
+from . import phonemes
+
+DEFAULT_WEIGHTS = {
+    "voicing": 1.0,
+    "place": 1.0,
+    "manner": 1.0,
+    "height": 1.0,
+    "backness": 1.0,
+    "rounding": 0.5,
+    "length": 0.5,
+}
+
+# front to back of the mouth, so neighbouring places are close; the
+# labial-velar ⟨w⟩ counts as bilabial, the nearer of its two places
+PLACES = ["bilabial", "labiodental", "dental", "alveolar", "postalveolar", "retroflex",
+          "palatal", "velar", "uvular", "pharyngeal", "glottal"]
+# manners that are partly the same, like the stop and friction of an affricate
+RELATED_MANNERS = {
+    frozenset({"plosive", "affricate"}),
+    frozenset({"fricative", "affricate"}),
+    frozenset({"tap", "trill"}),
+    frozenset({"approximant", "lateral approximant"}),
+    frozenset({"fricative", "lateral fricative"}),
+}
+HEIGHTS = ["close", "near-close", "close-mid", "mid", "open-mid", "near-open", "open"]
+BACKNESSES = ["front", "central", "back"]
+
+# symbol: (voiced, place, manner)
+CONSONANTS = {
+    "p": (False, "bilabial", "plosive"), "b": (True, "bilabial", "plosive"),
+    "t": (False, "alveolar", "plosive"), "d": (True, "alveolar", "plosive"),
+    "ʈ": (False, "retroflex", "plosive"), "ɖ": (True, "retroflex", "plosive"),
+    "c": (False, "palatal", "plosive"), "ɟ": (True, "palatal", "plosive"),
+    "k": (False, "velar", "plosive"), "ɡ": (True, "velar", "plosive"), "g": (True, "velar", "plosive"),
+    "q": (False, "uvular", "plosive"), "ɢ": (True, "uvular", "plosive"),
+    "ʔ": (False, "glottal", "plosive"),
+    "m": (True, "bilabial", "nasal"), "ɱ": (True, "labiodental", "nasal"),
+    "n": (True, "alveolar", "nasal"), "ɳ": (True, "retroflex", "nasal"),
+    "ɲ": (True, "palatal", "nasal"), "ŋ": (True, "velar", "nasal"), "ɴ": (True, "uvular", "nasal"),
+    "r": (True, "alveolar", "trill"), "ʀ": (True, "uvular", "trill"), "ʙ": (True, "bilabial", "trill"),
+    "ɾ": (True, "alveolar", "tap"), "ɽ": (True, "retroflex", "tap"),
+    "ɸ": (False, "bilabial", "fricative"), "β": (True, "bilabial", "fricative"),
+    "f": (False, "labiodental", "fricative"), "v": (True, "labiodental", "fricative"),
+    "θ": (False, "dental", "fricative"), "ð": (True, "dental", "fricative"),
+    "s": (False, "alveolar", "fricative"), "z": (True, "alveolar", "fricative"),
+    "ʃ": (False, "postalveolar", "fricative"), "ʒ": (True, "postalveolar", "fricative"),
+    "ʂ": (False, "retroflex", "fricative"), "ʐ": (True, "retroflex", "fricative"),
+    "ç": (False, "palatal", "fricative"), "ʝ": (True, "palatal", "fricative"),
+    "x": (False, "velar", "fricative"), "ɣ": (True, "velar", "fricative"),
+    "χ": (False, "uvular", "fricative"), "ʁ": (True, "uvular", "fricative"),
+    "ħ": (False, "pharyngeal", "fricative"), "ʕ": (True, "pharyngeal", "fricative"),
+    "h": (False, "glottal", "fricative"), "ɦ": (True, "glottal", "fricative"),
+    "ɬ": (False, "alveolar", "lateral fricative"), "ɮ": (True, "alveolar", "lateral fricative"),
+    "tʃ": (False, "postalveolar", "affricate"), "dʒ": (True, "postalveolar", "affricate"),
+    "ʋ": (True, "labiodental", "approximant"), "ɹ": (True, "alveolar", "approximant"),
+    "ɻ": (True, "retroflex", "approximant"), "j": (True, "palatal", "approximant"),
+    "ɰ": (True, "velar", "approximant"), "w": (True, "bilabial", "approximant"),
+    "l": (True, "alveolar", "lateral approximant"), "ɫ": (True, "alveolar", "lateral approximant"),
+    "ɭ": (True, "retroflex", "lateral approximant"), "ʎ": (True, "palatal", "lateral approximant"),
+    "ʟ": (True, "velar", "lateral approximant"),
+}
+# symbol: (height, backness, rounded)
+VOWELS = {
+    "i": ("close", "front", False), "y": ("close", "front", True),
+    "ɨ": ("close", "central", False), "ʉ": ("close", "central", True),
+    "ɯ": ("close", "back", False), "u": ("close", "back", True),
+    "ɪ": ("near-close", "front", False), "ʏ": ("near-close", "front", True),
+    "ʊ": ("near-close", "back", True),
+    "e": ("close-mid", "front", False), "ø": ("close-mid", "front", True),
+    "ɘ": ("close-mid", "central", False), "ɵ": ("close-mid", "central", True),
+    "ɤ": ("close-mid", "back", False), "o": ("close-mid", "back", True),
+    "ə": ("mid", "central", False), "ɚ": ("mid", "central", False),
+    "ɛ": ("open-mid", "front", False), "œ": ("open-mid", "front", True),
+    "ɜ": ("open-mid", "central", False), "ɝ": ("open-mid", "central", False),
+    "ɞ": ("open-mid", "central", True),
+    "ʌ": ("open-mid", "back", False), "ɔ": ("open-mid", "back", True),
+    "æ": ("near-open", "front", False), "ɐ": ("near-open", "central", False),
+    "a": ("open", "front", False), "ɶ": ("open", "front", True),
+    "ɑ": ("open", "back", False), "ɒ": ("open", "back", True),
+}
+
+
+def _features(sound):
+    """(kind, features, parts) of sound; parts are the vowels of a diphthong."""
+    bare = phonemes.base(sound)
+    if bare in CONSONANTS:
+        return "consonant", CONSONANTS[bare], None
+    if bare in VOWELS:
+        return "vowel", VOWELS[bare], None
+    parts = [char for char in bare if char in VOWELS]
+    if len(parts) > 1:
+        return "diphthong", None, [parts[0], parts[-1]]
+    return None, None, None
+
+
+def features(sound):
+    """The distinctive features of sound, keyed like the entries of the
+    IPA lookup table: "voiced", "place" and "manner" for a consonant,
+    "height", "backness" and "rounded" for a vowel, "diphthong" for a
+    diphthong, with "long" added for a long sound. Empty for sounds
+    not in the feature tables.
+
+    >>> features("dʒ")
+    {'voiced': True, 'place': 'postalveolar', 'manner': 'affricate'}
+    """
+    kind, values, _parts = _features(sound)
+    if kind == "consonant":
+        found = dict(zip(("voiced", "place", "manner"), values))
+    elif kind == "vowel":
+        found = dict(zip(("height", "backness", "rounded"), values))
+    elif kind == "diphthong":
+        found = {"diphthong": True}
+    else:
+        return {}
+    if "ː" in sound:
+        found["long"] = True
+    return found
+
+
+def _weighted(pairs, weights):
+    """Weighted mean of (feature, difference between 0 and 1) pairs."""
+    total = sum(weights[feature] for feature, _difference in pairs)
+    if not total:
+        return 0.0
+    return sum(weights[feature] * difference for feature, difference in pairs) / total
+
+
+def sound_distance(a, b, weights=None):
+    """How different two sounds are, from 0 for the same sound to 1.
+
+    Sounds not in the feature tables are 0 apart if they are the same
+    symbol and 1 otherwise.
+    """
+    weights = {**DEFAULT_WEIGHTS, **(weights or {})}
+    if a == b:
+        return 0.0
+    length = ("length", float(("ː" in a) != ("ː" in b)))
+    kind_a, features_a, parts_a = _features(a)
+    kind_b, features_b, parts_b = _features(b)
+    if kind_a is None or kind_b is None:
+        return 0.0 if phonemes.base(a) == phonemes.base(b) else 1.0
+
+    if "consonant" in (kind_a, kind_b):
+        if kind_a != kind_b:
+            return 1.0
+        (voiced_a, place_a, manner_a), (voiced_b, place_b, manner_b) = features_a, features_b
+        if manner_a == manner_b:
+            manner = 0.0
+        else:
+            manner = 0.5 if frozenset({manner_a, manner_b}) in RELATED_MANNERS else 1.0
+        return _weighted([
+            ("voicing", float(voiced_a != voiced_b)),
+            ("place", abs(PLACES.index(place_a) - PLACES.index(place_b)) / (len(PLACES) - 1)),
+            ("manner", manner),
+            length,
+        ], weights)
+
+    # a diphthong is compared by where it starts and where it ends
+    starts = [parts_a[0] if parts_a else phonemes.base(a), parts_b[0] if parts_b else phonemes.base(b)]
+    ends = [parts_a[-1] if parts_a else phonemes.base(a), parts_b[-1] if parts_b else phonemes.base(b)]
+    differences = []
+    for first, second in (starts, ends):
+        (height_a, back_a, round_a), (height_b, back_b, round_b) = VOWELS[first], VOWELS[second]
+        differences.append(_weighted([
+            ("height", abs(HEIGHTS.index(height_a) - HEIGHTS.index(height_b)) / (len(HEIGHTS) - 1)),
+            ("backness", abs(BACKNESSES.index(back_a) - BACKNESSES.index(back_b)) / (len(BACKNESSES) - 1)),
+            ("rounding", float(round_a != round_b)),
+            length,
+        ], weights))
+    # a monophthong is never quite the same as a diphthong
+    glide = 0.25 if (kind_a == "diphthong") != (kind_b == "diphthong") else 0.0
+    return min(1.0, sum(differences) / 2 + glide)
+
+
+def distance(first, second, weights=None):
+    """The weighted edit distance between the first variants of two
+    transcriptions, see the module documentation.
+    """
+    a, b = phonemes.sounds(first), phonemes.sounds(second)
+    # cost[i][j]: cost of turning a[:i] into b[:j]
+    cost = [[0.0] * (len(b) + 1) for _ in range(len(a) + 1)]
+    for i in range(len(a) + 1):
+        cost[i][0] = float(i)
+    for j in range(len(b) + 1):
+        cost[0][j] = float(j)
+    for i in range(1, len(a) + 1):
+        for j in range(1, len(b) + 1):
+            cost[i][j] = min(cost[i-1][j] + 1,
+                             cost[i][j-1] + 1,
+                             cost[i-1][j-1] + sound_distance(a[i-1], b[j-1], weights))
+    return cost[len(a)][len(b)]
+
+
+def similarity(first, second, weights=None):
+    """How alike two transcriptions sound, from 0 to 1 for the same sounds."""
+    longest = max(len(phonemes.sounds(first)), len(phonemes.sounds(second)))
+    if not longest:
+        return 1.0
+    return 1.0 - distance(first, second, weights) / longest
@@ -0,0 +1,208 @@
+# sound_change.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Rewrite rules for transcriptions, as phonology students and
+conlangers write sound changes:
+
+    >>> apply_rules([parse_rule("t → ɾ / V_V")], "/ˈwɔtəɹ/")
+    '/ˈwɔɾəɹ/'
+
+A rule is "target → replacement", optionally followed by "/ before_after"
+for where it applies. ">" and "->" work as arrows too. Target and
+replacement can be lists of sounds changed pairwise, like "p t k → b d ɡ";
+"∅" or "0" as the replacement drops the target. In targets and
+environments V stands for any vowel, C for any consonant and # for the
+start or end of the word.
+"""
+
+from . import phonemes
+
+ARROWS = ["→", "->", ">"]
+EMPTY = {"∅", "0", "Ø"}
+CLASSES = {"V", "C"}
+BOUNDARY = "#"
+
+
+class RuleError(Exception):
+    """Raised when a rule can't be parsed. reason is one of "no-arrow",
+    "no-target", "boundary-in-change", "no-replacement", "count-mismatch",
+    "environment" or "boundary-position"; line is set by parse_rules().
+    """
+
+    def __init__(self, reason, line=None):
+        super().__init__(reason if line is None else f"line {line}: {reason}")
+        self.reason = reason
+        self.line = line
+
+
+class Rule:
+    """One parsed sound change, see the module documentation."""
+
+    def __init__(self, text, targets, replacements, before, after):
+        self.text = text
+        self.targets = targets
+        # None drops the target
+        self.replacements = replacements
+        self.before = before
+        self.after = after
+
+    def __repr__(self):
+        return f"Rule({self.text!r})"
+
+
+def _symbols(text):
+    """Split one side of a rule into sounds, classes and boundaries."""
+    found = []
+    for part in text.split():
+        found.extend([part] if part in CLASSES or part == BOUNDARY else phonemes.split(part))
+    return found
+
+
+def _context(text):
+    """Like _symbols() but an environment side has no spaces, so V and C
+    may be written right next to sounds, as in "Vn_".
+    """
+    found = []
+    sounds = ""
+    for char in text.replace(" ", "") + BOUNDARY:
+        if char in CLASSES or char == BOUNDARY:
+            found.extend(phonemes.split(sounds))
+            found.append(char)
+            sounds = ""
+        else:
+            sounds += char
+    # drop the boundary added to flush the last sounds
+    return found[:-1]
+
+
+def parse_rule(text):
+    """Parse a rule like "t → ɾ / V_V", raising RuleError if it is malformed."""
+    for arrow in ARROWS:
+        if arrow in text:
+            change, _arrow, rest = text.partition(arrow)
+            break
+    else:
+        raise RuleError("no-arrow")
+    replacement, _slash, environment = rest.partition("/")
+    targets = _symbols(change)
+    if not targets:
+        raise RuleError("no-target")
+    if BOUNDARY in targets:
+        raise RuleError("boundary-in-change")
+
+    if replacement.strip() in EMPTY:
+        replacements = None
+    else:
+        replacements = _symbols(replacement)
+        if not replacements:
+            raise RuleError("no-replacement")
+        if len(replacements) == 1:
+            replacements = replacements * len(targets)
+        if len(replacements) != len(targets):
+            raise RuleError("count-mismatch")
+
+    before, after = [], []
+    if environment.strip():
+        if environment.count("_") != 1:
+            raise RuleError("environment")
+        before_text, after_text = environment.split("_")
+        before, after = _context(before_text), _context(after_text)
+        if BOUNDARY in before[1:] or BOUNDARY in after[:-1]:
+            raise RuleError("boundary-position")
+    return Rule(text.strip(), targets, replacements, before, after)
+
+
+def parse_rules(text):
+    """Parse one rule per line, skipping blank lines and lines starting
+    with "#" followed by a space. Raises RuleError with the line number.
+    """
+    rules = []
+    for number, line in enumerate(text.splitlines(), 1):
+        if not line.strip() or line.startswith("# "):
+            continue
+        try:
+            rules.append(parse_rule(line))
+        except RuleError as e:
+            raise RuleError(e.reason, number) from None
+    return rules
+
+
+def _matches(symbol, sound):
+    if symbol == "V":
+        return phonemes.is_vowel(sound)
+    if symbol == "C":
+        return not phonemes.is_vowel(sound)
+    return symbol == sound
+
+
+def _context_matches(pattern, sounds, start, step):
+    """Whether pattern matches sounds going from start by step (-1 for
+    what comes before, reading the pattern backwards).
+    """
+    position = start
+    for symbol in (reversed(pattern) if step < 0 else pattern):
+        if symbol == BOUNDARY:
+            return position < 0 or position >= len(sounds)
+        if not 0 <= position < len(sounds) or not _matches(symbol, sounds[position]):
+            return False
+        position += step
+    return True
+
+
+def apply_rule(rule, ipa):
+    """Apply rule to every variant of ipa at once, keeping its slashes,
+    stress marks and other separators.
+    """
+    pieces = phonemes.tokens(ipa)
+    result = []
+    # environments are checked within a variant
+    variant = []
+    for text, is_sound in pieces + [(",", False)]:
+        if text == ",":
+            result.extend(_apply_to_variant(rule, variant))
+            result.append(text)
+            variant = []
+        else:
+            variant.append((text, is_sound))
+    return "".join(result[:-1])
+
+
+def _apply_to_variant(rule, pieces):
+    indexes = [i for i, (_text, is_sound) in enumerate(pieces) if is_sound]
+    sounds = [pieces[i][0] for i in indexes]
+    texts = [text for text, _is_sound in pieces]
+    # matched against the sounds before the rule, so it applies everywhere at once
+    for position, sound in enumerate(sounds):
+        for number, target in enumerate(rule.targets):
+            if not _matches(target, sound):
+                continue
+            if (_context_matches(rule.before, sounds, position - 1, -1)
+                    and _context_matches(rule.after, sounds, position + 1, 1)):
+                if rule.replacements is None:
+                    texts[indexes[position]] = ""
+                elif rule.replacements[number] not in CLASSES:
+                    texts[indexes[position]] = rule.replacements[number]
+            break
+    return texts
+
+
+def apply_rules(rules, ipa):
+    """Apply rules to ipa one after another, in order."""
+    for rule in rules:
+        ipa = apply_rule(rule, ipa)
+    return ipa
@@ -0,0 +1,92 @@
+# wikitext.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Reading pronunciations out of the wikitext of Wiktionary pages."""
+
+import re
+
+# dictionary codes, or their language part, to the headings Wiktionary
+# gives the languages on a page
+LANGUAGE_NAMES = {
+    "ar": "Arabic",
+    "de": "German",
+    "en": "English",
+    "eo": "Esperanto",
+    "es": "Spanish",
+    "fa": "Persian",
+    "fi": "Finnish",
+    "fr": "French",
+    "ja": "Japanese",
+    "jam": "Jamaican Creole",
+    "ma": "Marathi",
+    "nb": "Norwegian Bokmål",
+    "or": "Odia",
+    "sv": "Swedish",
+    "sw": "Swahili",
+    "vi": "Vietnamese",
+    "yue": "Cantonese",
+    "zh": "Chinese",
+}
+# the accent labels ({{IPA|...|a=...}}) preferred for a dictionary
+ACCENTS = {
+    "en_US": {"US", "GA", "GenAm"},
+    "en_UK": {"UK", "RP"},
+}
+
+IPA_TEMPLATE = re.compile(r"\{\{IPA\|([^{}]*)\}\}")
+HEADING = re.compile(r"^==([^=].*?)==\s*$", re.MULTILINE)
+
+
+def language_name(lang):
+    return LANGUAGE_NAMES.get(lang) or LANGUAGE_NAMES.get(lang.split("_")[0])
+
+
+def language_section(wikitext, name):
+    """The part of a page about one language, or None."""
+    headings = list(HEADING.finditer(wikitext))
+    for i, heading in enumerate(headings):
+        if heading.group(1).strip() == name:
+            end = headings[i + 1].start() if i + 1 < len(headings) else len(wikitext)
+            return wikitext[heading.end():end]
+    return None
+
+
+def find_ipa(wikitext, lang):
+    """The transcriptions of the first {{IPA}} template in the section
+    of lang, preferring the accents of ACCENTS, as a dictionary value
+    like "/a/, /b/"; None if the page has none.
+    """
+    name = language_name(lang)
+    section = language_section(wikitext, name) if name else None
+    if section is None:
+        return None
+    found = []
+    for template in IPA_TEMPLATE.finditer(section):
+        parts = [part.strip() for part in template.group(1).split("|")]
+        named = dict(part.split("=", 1) for part in parts if "=" in part)
+        # the first positional argument is the language code
+        transcriptions = [part for part in parts[1:] if "=" not in part and part.startswith(("/", "["))]
+        if transcriptions:
+            found.append((set(named.get("a", "").replace(",", " ").split()), transcriptions))
+    if not found:
+        return None
+    preferred = ACCENTS.get(lang, set())
+    _accents, transcriptions = next((entry for entry in found if entry[0] & preferred), found[0])
+    # phonemic transcriptions are what the dictionaries have
+    phonemic = [ipa for ipa in transcriptions if ipa.startswith("/")]
+    return ", ".join(phonemic or transcriptions)
@@ -0,0 +1,144 @@
+# csv_import.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Adw, Gtk
+from .core import csv_import, encoding
+from .userdict import UserDictionary
+
+# rows shown before importing
+PREVIEW_ROWS = 8
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/csv_import.ui')
+class Word2ipaCsvImportDialog(Adw.Dialog):
+    """Imports entries of lang's user dictionary from a CSV file of any
+    layout, once its columns are picked. on_imported(count) is called
+    after the import.
+    """
+    __gtype_name__ = 'Word2ipaCsvImportDialog'
+    import_button = Gtk.Template.Child()
+    encoding_row = Gtk.Template.Child()
+    delimiter_row = Gtk.Template.Child()
+    header_row = Gtk.Template.Child()
+    strict_row = Gtk.Template.Child()
+    word_column_row = Gtk.Template.Child()
+    ipa_column_row = Gtk.Template.Child()
+    preview_group = Gtk.Template.Child()
+    preview_list = Gtk.Template.Child()
+    # the rows are changed in code, not by the user, until set up
+    filling = True
+
+    def __init__(self, data, name, lang, on_imported, **kwargs):
+        super().__init__(**kwargs)
+        self.data = data
+        # of the file, kept as the source of its entries
+        self.name = name
+        self.lang = lang
+        self.on_imported = on_imported
+        self.rows = []
+        self.entries = []
+        # {word: symbols that aren't IPA} of the entries to review
+        self.unknown = {}
+        # start from what the file most likely is
+        text, _replaced = encoding.decode(data)
+        self.delimiter_row.set_selected(csv_import.DELIMITERS.index(csv_import.sniff_delimiter(text)))
+        self.filling = False
+        self.read_file(guess=True)
+
+    def read_file(self, guess=False):
+        # the first choice is Automatic
+        codec = (None,) + encoding.ENCODINGS
+        text, replaced = encoding.decode(self.data, codec[self.encoding_row.get_selected()])
+        self.encoding_row.set_subtitle(ngettext("{} character could not be read", "{} characters could not be read",
+                                                replaced).format(replaced) if replaced else "")
+        if replaced:
+            self.encoding_row.add_css_class("warning")
+        else:
+            self.encoding_row.remove_css_class("warning")
+        delimiter = csv_import.DELIMITERS[self.delimiter_row.get_selected()]
+        self.rows = csv_import.read_rows(text, delimiter)
+        word, ipa, header = csv_import.guess_columns(self.rows)
+        if not guess:
+            word, ipa, header = (self.word_column_row.get_selected(), self.ipa_column_row.get_selected(),
+                                 self.header_row.get_active())
+
+        # columns are named by the first row, header or not
+        columns = max((len(row) for row in self.rows), default=0)
+        first = self.rows[0] if self.rows else []
+        names = [_("Column {number}: {cell}").format(number=i + 1, cell=first[i].strip())
+                 if i < len(first) and first[i].strip() else _("Column {}").format(i + 1) for i in range(columns)]
+        self.filling = True
+        for row, selected in [(self.word_column_row, word), (self.ipa_column_row, ipa)]:
+            row.set_model(Gtk.StringList.new(names))
+            row.set_selected(min(selected, max(columns - 1, 0)))
+        self.header_row.set_active(header)
+        self.filling = False
+        self.update_preview()
+
+    @Gtk.Template.Callback()
+    def on_file_settings_changed(self, *args):
+        if not self.filling:
+            self.read_file()
+
+    @Gtk.Template.Callback()
+    def on_mapping_changed(self, *args):
+        if not self.filling:
+            self.update_preview()
+
+    def update_preview(self):
+        self.entries, skipped = csv_import.map_rows(self.rows, self.word_column_row.get_selected(),
+                                                    self.ipa_column_row.get_selected(),
+                                                    self.header_row.get_active())
+        if self.word_column_row.get_selected() == self.ipa_column_row.get_selected():
+            self.entries = []
+        # the second choice is strict
+        self.entries, self.unknown, rejected = csv_import.check_symbols(self.entries,
+                                                                        strict=self.strict_row.get_selected() == 1)
+        description = ngettext("{} entry", "{} entries", len(self.entries)).format(len(self.entries))
+        if skipped:
+            description += " · " + ngettext("{} row without a word or IPA is skipped",
+                                            "{} rows without a word or IPA are skipped", skipped).format(skipped)
+        if rejected:
+            description += " · " + ngettext("{} entry with symbols that aren’t IPA is skipped",
+                                            "{} entries with symbols that aren’t IPA are skipped",
+                                            rejected).format(rejected)
+        if self.unknown:
+            description += " · " + ngettext("{} entry with symbols that aren’t IPA is kept for review",
+                                            "{} entries with symbols that aren’t IPA are kept for review",
+                                            len(self.unknown)).format(len(self.unknown))
+        self.preview_group.set_description(description)
+        self.preview_list.remove_all()
+        for word, ipa in self.entries[:PREVIEW_ROWS]:
+            row = Adw.ActionRow(title=word, subtitle=ipa, use_markup=False, css_classes=["property"])
+            if word in self.unknown:
+                row.add_suffix(Gtk.Image(icon_name="dialog-warning-symbolic", css_classes=["warning"],
+                                         tooltip_text=_("Not IPA: {}").format(" ".join(self.unknown[word]))))
+            self.preview_list.append(row)
+        self.import_button.set_sensitive(bool(self.entries))
+
+    @Gtk.Template.Callback()
+    def on_cancel_clicked(self, button):
+        self.close()
+
+    @Gtk.Template.Callback()
+    def on_import_clicked(self, button):
+        UserDictionary.get_default().add_many(self.lang, self.entries, self.name,
+                                              {word: {"unknown": symbols} for word, symbols in self.unknown.items()})
+        self.close()
+        self.on_imported(len(self.entries))
@@ -0,0 +1,92 @@
+# dbus_service.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""The io.github.mohfy.word2ipa D-Bus interface, so other apps can look
+up transcriptions, for example with
+
+    gdbus call --session --dest io.github.mohfy.word2ipa \
+        --object-path /io/github/mohfy/word2ipa \
+        --method io.github.mohfy.word2ipa.Lookup hello en_US
+
+The service file starts the app without a window for a call when it
+isn't running yet.
+"""
+
+from gi.repository import Gio, GLib
+import sqlite3
+from . import backends, dictionaries, languages
+from .core import DictionaryError, lookup_word, phonemes
+from .state import AppState
+
+INTERFACE_XML = """
+<node>
+  <interface name="io.github.mohfy.word2ipa">
+    <!-- The pronunciations of word in lang, like "en_US", or in the
+         language selected in the app if lang is empty. Empty if the
+         word isn't found. -->
+    <method name="Lookup">
+      <arg type="s" name="word" direction="in"/>
+      <arg type="s" name="lang" direction="in"/>
+      <arg type="as" name="variants" direction="out"/>
+    </method>
+  </interface>
+</node>
+"""
+INTERFACE = Gio.DBusNodeInfo.new_for_xml(INTERFACE_XML).interfaces[0]
+
+
+def lookup(word, lang):
+    """The IPA variants of word in lang as the app would show them."""
+    state = AppState.get_default()
+    lang = lang or state.lang
+    if lang not in dict(languages.available()):
+        raise ValueError(f"unknown language {lang}")
+    sources = [backends.chain(lang, dictionaries.load(lang), online=False).for_language(lang)]
+    ipa = lookup_word(word.strip(), sources)[0]
+    return phonemes.variants(state.display(ipa, lang=lang)) if ipa is not None else []
+
+
+class LookupService:
+    """Exports the interface on the application's object path."""
+
+    def __init__(self, application):
+        self.application = application
+        self.registrations = []
+
+    def register(self, connection, object_path):
+        self.registrations.append((connection, connection.register_object(
+            object_path, INTERFACE, self.on_method_call, None, None)))
+
+    def unregister(self):
+        for connection, registration in self.registrations:
+            connection.unregister_object(registration)
+        self.registrations = []
+
+    def on_method_call(self, connection, sender, object_path, interface_name, method_name, parameters,
+                       invocation):
+        # each call keeps a service started for it running a while longer
+        self.application.hold()
+        try:
+            word, lang = parameters.unpack()
+            invocation.return_value(GLib.Variant("(as)", (lookup(word, lang),)))
+        except ValueError as e:
+            invocation.return_dbus_error("org.freedesktop.DBus.Error.InvalidArgs", str(e))
+        except (OSError, sqlite3.Error, DictionaryError) as e:
+            invocation.return_dbus_error("org.freedesktop.DBus.Error.Failed", f"could not load the dictionary: {e}")
+        finally:
+            self.application.release()
@@ -0,0 +1,93 @@
+# dictation.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Adw, GLib, Gtk
+from . import speech
+from .practice import PracticeSession
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/dictation.ui')
+class Word2ipaDictationDialog(Adw.Dialog):
+    """Speaks a dictionary word and asks for its spelling."""
+    __gtype_name__ = 'Word2ipaDictationDialog'
+    status_page = Gtk.Template.Child()
+    answer_entry = Gtk.Template.Child()
+    feedback_label = Gtk.Template.Child()
+    next_button = Gtk.Template.Child()
+
+    def __init__(self, lang, dictionary, words, on_finished, **kwargs):
+        """dictionary is the loaded one of lang, words the ones of the
+        round, see practice_words().
+        """
+        super().__init__(**kwargs)
+        self.lang = lang
+        self.on_finished = on_finished
+        self.dictionary = dictionary
+        self.words = words
+        self.session = PracticeSession("dictation", lang)
+        self.index = -1
+        self.next_word()
+
+    def next_word(self):
+        self.index += 1
+        if self.index >= len(self.words):
+            self.close()
+            return
+
+        self.word = self.words[self.index]
+        self.answered = False
+        self.status_page.set_description(_("Word {} of {}").format(self.index + 1, len(self.words)))
+        self.answer_entry.set_text("")
+        self.answer_entry.set_sensitive(True)
+        self.answer_entry.grab_focus()
+        self.feedback_label.set_label("")
+        self.next_button.set_visible(False)
+        speech.speak_word(self.word, self.lang)
+
+    @Gtk.Template.Callback()
+    def on_play_clicked(self, button):
+        speech.speak_word(self.word, self.lang)
+
+    @Gtk.Template.Callback()
+    def on_answer_applied(self, entry):
+        answer = entry.get_text().strip()
+        if self.answered or not answer:
+            return
+        self.answered = True
+
+        ipa = self.dictionary.lookup(self.word)
+        correct = answer.casefold() == self.word.casefold()
+        self.session.record(self.word, ipa, answer, correct)
+
+        if correct:
+            self.feedback_label.set_markup(f'<b>{_("Correct!")}</b> {GLib.markup_escape_text(ipa)}')
+        else:
+            self.feedback_label.set_markup(_("It was <b>{}</b> {}").format(
+                GLib.markup_escape_text(self.word), GLib.markup_escape_text(ipa)))
+        self.answer_entry.set_sensitive(False)
+        self.next_button.set_visible(True)
+        self.next_button.grab_focus()
+
+    @Gtk.Template.Callback()
+    def on_next_clicked(self, button):
+        self.next_word()
+
+    @Gtk.Template.Callback()
+    def on_closed(self, dialog):
+        self.on_finished(self.session)
@@ -0,0 +1,85 @@
+# dictionaries.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GLib
+import os
+import sqlite3
+import threading
+from . import languages
+from .core import DictionaryError, load_dictionary
+from .langpacks import LanguagePacks
+
+# the compiled bundled dictionaries are installed next to the package
+DICTS_DIR = os.path.join(os.path.dirname(os.path.dirname(os.path.abspath(__file__))), 'dicts')
+
+_cache = {}
+_cache_lock = threading.Lock()
+
+
+def load(code):
+    """Return the dictionary of a bundled or downloaded language."""
+    with _cache_lock:
+        if code not in _cache:
+            if code in languages.BUNDLED:
+                _cache[code] = load_dictionary(code, os.path.join(DICTS_DIR, f"{code}.db"))
+            else:
+                _cache[code] = LanguagePacks.get_default().load(code)
+        return _cache[code]
+
+
+def load_async(code, callback):
+    """Open a dictionary on a worker thread.
+
+    callback(dictionary, error) runs on the main loop once it is ready.
+    """
+    def worker():
+        try:
+            dictionary = load(code)
+            # the first query opens the file and reads its index
+            dictionary.lookup("")
+        except (OSError, sqlite3.Error, DictionaryError) as e:
+            GLib.idle_add(callback, None, str(e))
+            return
+        GLib.idle_add(callback, dictionary, None)
+
+    threading.Thread(target=worker, daemon=True).start()
+
+
+def attribution(codes):
+    """Citation lines for the dictionaries of codes, for exported files."""
+    lines = []
+    for code in sorted(set(codes) - {""}):
+        try:
+            line = load(code).attribution()
+        except (OSError, sqlite3.Error, DictionaryError):
+            continue
+        if line:
+            lines.append(line)
+    return lines
+
+
+def _on_packs_changed(packs):
+    # downloaded packs may have been removed or replaced by a newer version
+    with _cache_lock:
+        for code in list(_cache):
+            if code not in languages.BUNDLED:
+                _cache.pop(code).close()
+
+
+LanguagePacks.get_default().connect('changed', _on_packs_changed)
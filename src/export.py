@@ -0,0 +1,167 @@
+# export.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gio, GLib, Gtk
+import csv
+import datetime
+import json
+from . import dictionaries
+
+HISTORY_FIELDS = ["word", "ipa", "language", "timestamp", "lookups"]
+
+
+def history_rows(items):
+    """Turn history items into plain dicts with an ISO 8601 timestamp."""
+    return [{
+        "word": item.word,
+        "ipa": item.ipa,
+        "language": item.lang,
+        "timestamp": datetime.datetime.fromtimestamp(item.timestamp).astimezone().isoformat(),
+        "lookups": item.count,
+    } for item in items]
+
+
+def write_history(path, items):
+    """Write history as JSON if path ends in .json, CSV otherwise.
+
+    The sources of the dictionaries used are cited, in an "attribution"
+    list for JSON and in leading # lines for CSV.
+    """
+    rows = history_rows(items)
+    attribution = dictionaries.attribution(row["language"] for row in rows)
+    with open(path, 'w', encoding='utf-8', newline='') as f:
+        if path.lower().endswith('.json'):
+            json.dump({"attribution": attribution, "entries": rows},
+                      f, ensure_ascii=False, indent=2)
+        else:
+            for line in attribution:
+                f.write(f'# {line}\n')
+            writer = csv.DictWriter(f, fieldnames=HISTORY_FIELDS)
+            writer.writeheader()
+            writer.writerows(rows)
+
+
+def write_anki_tsv(path, items, audio=None):
+    """Write word/IPA pairs as a tab separated file Anki can import.
+
+    The word goes on the front of the card, the IPA on the back and the
+    language code becomes a tag. audio optionally maps (word, lang) to
+    the name of a sound file, played from the back of the card. Dictionary
+    sources are cited in comment lines, which Anki skips.
+    """
+    audio = audio or {}
+    with open(path, 'w', encoding='utf-8') as f:
+        f.write('#separator:tab\n')
+        f.write('#html:false\n')
+        f.write('#tags column:3\n')
+        for line in dictionaries.attribution(item.get("lang", "") for item in items):
+            f.write(f'# {line}\n')
+        for item in items:
+            word = item["word"].replace('\t', ' ')
+            ipa = item["ipa"].replace('\t', ' ')
+            if sound := audio.get((item["word"], item.get("lang", ""))):
+                ipa += f' [sound:{sound}]'
+            f.write(f'{word}\t{ipa}\t{item.get("lang", "")}\n')
+
+
+def write_transcriptions(path, results, lang):
+    """Write (word, ipa) pairs tab separated, leaving out words not found."""
+    with open(path, 'w', encoding='utf-8') as f:
+        for line in dictionaries.attribution([lang]):
+            f.write(f'# {line}\n')
+        for word, ipa in results:
+            if ipa is not None:
+                f.write(f'{word}\t{ipa}\n')
+
+
+def write_dictionary(path, lang, entries, metadata=None, entry_info=None):
+    """Write (word, ipa) entries as JSON in the format of the bundled
+    dictionaries and language packs if path ends in .json, tab separated
+    otherwise. The JSON also keeps entry_info, word -> {"source",
+    "modified", "tags"}, so importing it again restores them.
+    """
+    if path.lower().endswith('.json'):
+        data = {"language": lang, "metadata": metadata or {}, "entries": [dict(entries)]}
+        if entry_info:
+            data["entry_info"] = entry_info
+        with open(path, 'w', encoding='utf-8') as f:
+            json.dump(data, f, ensure_ascii=False, indent=2)
+    else:
+        write_transcriptions(path, entries, lang)
+
+
+def write_comparison(path, items, rows):
+    """Write compared results as CSV, each followed by its sounds in the
+    columns of the first result's sounds.
+    """
+    with open(path, 'w', encoding='utf-8', newline='') as f:
+        for line in dictionaries.attribution(item["lang"] for item in items):
+            f.write(f'# {line}\n')
+        writer = csv.writer(f)
+        columns = max(len(cells) for cells in rows)
+        writer.writerow(["word", "ipa", "language"] + [f"sound {number}" for number in range(1, columns + 1)])
+        for item, cells in zip(items, rows):
+            writer.writerow([item["word"], item["ipa"], item["lang"]] + [cell or "" for cell in cells])
+
+
+def file_filter(name, *patterns):
+    file_filter = Gtk.FileFilter(name=name)
+    for pattern in patterns:
+        file_filter.add_pattern(pattern)
+    return file_filter
+
+
+def choose_save_path(parent, initial_name, callback, filters=None):
+    """Ask where to save a file; callback(path) is only called on success."""
+    dialog = Gtk.FileDialog(initial_name=initial_name)
+    if filters:
+        store = Gio.ListStore(item_type=Gtk.FileFilter)
+        for file_filter in filters:
+            store.append(file_filter)
+        dialog.set_filters(store)
+
+    def on_finish(dialog, result):
+        try:
+            file = dialog.save_finish(result)
+        except GLib.Error:
+            # dismissed by the user
+            return
+        callback(file.get_path())
+
+    dialog.save(parent, None, on_finish)
+
+
+def choose_open_path(parent, callback, filters=None):
+    """Ask for a file to open; callback(path) is only called on success."""
+    dialog = Gtk.FileDialog()
+    if filters:
+        store = Gio.ListStore(item_type=Gtk.FileFilter)
+        for file_filter in filters:
+            store.append(file_filter)
+        dialog.set_filters(store)
+
+    def on_finish(dialog, result):
+        try:
+            file = dialog.open_finish(result)
+        except GLib.Error:
+            # dismissed by the user
+            return
+        callback(file.get_path())
+
+    dialog.open(parent, None, on_finish)
@@ -0,0 +1,96 @@
+# favorites.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gio, GObject
+import time
+from . import storage
+from .history import HistoryItem
+
+FAVORITES_FILE = 'favorites.json'
+
+
+class Favorites(GObject.Object):
+    """Words the user starred, kept across sessions, newest first."""
+    __gtype_name__ = 'Word2ipaFavorites'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.store = Gio.ListStore(item_type=HistoryItem)
+        for entry in storage.load_json(FAVORITES_FILE, []):
+            self.store.append(HistoryItem(**entry))
+
+    def reload(self):
+        """Read the favorites again after the file was replaced."""
+        self.store.splice(0, len(self), [HistoryItem(**entry)
+                                         for entry in storage.load_json(FAVORITES_FILE, [])])
+        self.emit('changed')
+
+    def __len__(self):
+        return self.store.get_n_items()
+
+    def __iter__(self):
+        return iter(self.store)
+
+    def find(self, word, lang):
+        for item in self.store:
+            if item.word == word and item.lang == lang:
+                return item
+        return None
+
+    def contains(self, word, lang):
+        return self.find(word, lang) is not None
+
+    def add(self, word, ipa, lang):
+        if self.contains(word, lang):
+            return
+        entry = {"word": word, "ipa": ipa, "lang": lang, "timestamp": int(time.time())}
+
+        def change(entries):
+            if not any((other["word"], other["lang"]) == (word, lang) for other in entries):
+                entries.insert(0, entry)
+            return entries
+        self._save(change)
+
+    def remove(self, word, lang):
+        if self.contains(word, lang):
+            self._save(lambda entries: [entry for entry in entries
+                                        if (entry["word"], entry["lang"]) != (word, lang)])
+
+    def toggle(self, word, ipa, lang):
+        if self.contains(word, lang):
+            self.remove(word, lang)
+        else:
+            self.add(word, ipa, lang)
+
+    def _save(self, change):
+        # applied to the file as it is now, keeping what other instances starred
+        entries = storage.update_json(FAVORITES_FILE, [], change)
+        self.store.splice(0, len(self), [HistoryItem(**entry) for entry in entries])
+        self.emit('changed')
@@ -0,0 +1,66 @@
+# favorites_page.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Adw, GLib, Gtk
+from . import languages
+from .core import collation
+from .favorites import Favorites
+from .state import AppState
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/favorites_page.ui')
+class Word2ipaFavoritesPage(Adw.Bin):
+    __gtype_name__ = 'Word2ipaFavoritesPage'
+    favorites_list = Gtk.Template.Child()
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self.favorites = Favorites.get_default()
+        self.favorites_list.set_placeholder(
+            Gtk.Label(label=_("No favorites yet"), margin_top=12,
+                      margin_bottom=12, css_classes=["dim-label"]))
+        # alphabetical in the order of the selected language
+        self.state = AppState.get_default()
+        self.sort_key = collation.sort_key(self.state.lang)
+        self.sorter = Gtk.CustomSorter.new(self.compare_favorites, None)
+        self.state.connect("notify::lang", self.on_lang_changed)
+        self.favorites_list.bind_model(Gtk.SortListModel(model=self.favorites.store, sorter=self.sorter),
+                                       self.create_favorite_row)
+
+    def compare_favorites(self, a, b, _data):
+        a_key, b_key = self.sort_key(a.word), self.sort_key(b.word)
+        return (a_key > b_key) - (a_key < b_key)
+
+    def on_lang_changed(self, state, pspec):
+        self.sort_key = collation.sort_key(state.lang)
+        self.sorter.changed(Gtk.SorterChange.DIFFERENT)
+
+    def create_favorite_row(self, item):
+        row = Adw.ActionRow(title=item.ipa, subtitle=item.word, activatable=True,
+                            action_name="win.look-up",
+                            action_target=GLib.Variant("(ss)", (item.word, item.lang)))
+        row.add_suffix(Gtk.Label(label=languages.display_name(item.lang)))
+
+        unstar_button = Gtk.Button(icon_name="starred-symbolic",
+                                   tooltip_text=_("Remove from Favorites"),
+                                   valign=Gtk.Align.CENTER,
+                                   css_classes=["flat", "circular"])
+        unstar_button.connect("clicked", lambda _b: self.favorites.remove(item.word, item.lang))
+        row.add_suffix(unstar_button)
+        return row
@@ -0,0 +1,40 @@
+# features.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio
+
+# name -> (title, description) of the features that ship turned off;
+# they can be turned on in the Experimental group of Preferences, which
+# only shows up once the "show-experimental" setting is set
+FEATURES = {
+    "sound-changes": (_("Sound Change Sandbox"),
+                      _("Try out rewrite rules like t → ɾ / V_V on words, from the main menu")),
+}
+
+
+def is_enabled(name):
+    settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+    return name in settings.get_strv('experimental-features')
+
+
+def set_enabled(name, enabled):
+    settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+    features = [feature for feature in settings.get_strv('experimental-features') if feature != name]
+    if enabled:
+        features.append(name)
+    settings.set_strv('experimental-features', features)
@@ -0,0 +1,150 @@
+# forvo.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+import gi
+try:
+    gi.require_version('Secret', '1')
+    from gi.repository import Secret
+except (ValueError, ImportError):
+    # without libsecret there is nowhere to keep the key, so Forvo stays off
+    Secret = None
+from gi.repository import Gio, GLib, Gtk
+import hashlib
+import json
+import os
+import threading
+import urllib.parse
+import urllib.request
+
+API_URL = "https://apifree.forvo.com/key/{key}/format/json/action/word-pronunciations/word/{word}/language/{lang}"
+
+# the API key is kept in the keyring rather than in GSettings
+SCHEMA = Secret.Schema.new("io.github.mohfy.word2ipa.Forvo", Secret.SchemaFlags.NONE,
+                           {"service": Secret.SchemaAttributeType.STRING}) if Secret else None
+ATTRIBUTES = {"service": "forvo"}
+
+# the recording being played, it stops if the media file is collected
+_playing = None
+
+
+def get_key():
+    """The user's Forvo API key, or None."""
+    if Secret is None:
+        return None
+    try:
+        return Secret.password_lookup_sync(SCHEMA, ATTRIBUTES, None)
+    except GLib.Error as e:
+        print(f"could not read the Forvo key: {e.message}")
+        return None
+
+
+def set_key(key):
+    """Store key in the keyring, or remove it if key is empty."""
+    if Secret is None:
+        print("could not store the Forvo key: libsecret is not available")
+        return
+    try:
+        if key:
+            Secret.password_store_sync(SCHEMA, ATTRIBUTES, Secret.COLLECTION_DEFAULT,
+                                       _("Forvo API Key"), key, None)
+        else:
+            Secret.password_clear_sync(SCHEMA, ATTRIBUTES, None)
+    except GLib.Error as e:
+        print(f"could not store the Forvo key: {e.message}")
+
+
+def has_keyring():
+    return Secret is not None
+
+
+def is_enabled():
+    settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+    return settings.get_boolean('forvo-audio') and bool(get_key())
+
+
+def cache_path(word, lang):
+    """Where the recording of word is kept once downloaded."""
+    name = hashlib.sha1(word.encode("utf-8")).hexdigest()
+    return os.path.join(GLib.get_user_cache_dir(), 'word2ipa', 'forvo', lang, f'{name}.mp3')
+
+
+def _download(word, lang, key, path):
+    url = API_URL.format(key=urllib.parse.quote(key), word=urllib.parse.quote(word),
+                         lang=lang.split("_")[0])
+    with urllib.request.urlopen(url, timeout=30) as response:
+        items = json.loads(response.read().decode('utf-8')).get("items", [])
+    if not items:
+        return False
+    # the best rated recording comes first
+    items.sort(key=lambda item: item.get("num_positive_votes", 0), reverse=True)
+    os.makedirs(os.path.dirname(path), exist_ok=True)
+    with urllib.request.urlopen(items[0]["pathmp3"], timeout=30) as response, \
+            open(f'{path}.part', 'wb') as f:
+        f.write(response.read())
+    os.replace(f'{path}.part', path)
+    return True
+
+
+def _play_file(path):
+    global _playing
+    _playing = Gtk.MediaFile.new_for_filename(path)
+    _playing.play()
+
+
+def fetch(word, lang):
+    """Return the path of the recording of word, downloading it if it
+    isn't cached yet, or None if Forvo has none. Blocks, so it is meant
+    for worker threads; raises OSError or ValueError on failure.
+    """
+    path = cache_path(word, lang)
+    if os.path.exists(path) or _download(word, lang, get_key(), path):
+        return path
+    return None
+
+
+def play(word, lang, done_cb):
+    """Play a native speaker's recording of word.
+
+    done_cb(error) runs on the main loop; error is None once playing,
+    "not-found" if Forvo has no recording and a message otherwise.
+    """
+    path = cache_path(word, lang)
+    if os.path.exists(path):
+        _play_file(path)
+        done_cb(None)
+        return
+    key = get_key()
+
+    def on_downloaded(found, error):
+        if error is not None:
+            done_cb(error)
+        elif not found:
+            done_cb("not-found")
+        else:
+            _play_file(path)
+            done_cb(None)
+
+    def worker():
+        try:
+            found = _download(word, lang, key, path)
+        except (OSError, ValueError, KeyError) as e:
+            GLib.idle_add(on_downloaded, False, str(e))
+            return
+        GLib.idle_add(on_downloaded, found, None)
+
+    threading.Thread(target=worker, daemon=True).start()
@@ -0,0 +1,115 @@
+# global_search.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, Gtk
+import sqlite3
+import threading
+from . import backends, dictionaries, languages
+from .core import DictionaryError, clean_input, lookup_word
+from .state import AppState
+
+
+def prepare_search():
+    """(code, make(dictionary)) of every installed language, in the order
+    they are offered, with make building its backend chain. Made on the
+    main thread, for search_all() on a worker thread.
+    """
+    chains = backends.Backends.get_default()
+    return [(code, chains.prepare(code, online=False)) for code, _name in languages.available()]
+
+
+def search_all(word, prepared):
+    """Return (code, ipa) of every language of prepare_search() that has
+    word, in the same order.
+    """
+    hits = []
+    for code, make in prepared:
+        try:
+            chain = make(dictionaries.load(code))
+            ipa, _form, _match = lookup_word(word, [chain.for_language(code)])
+        except (OSError, sqlite3.Error, DictionaryError) as e:
+            print(f"could not search the {code} dictionary: {e}")
+            continue
+        if ipa is not None:
+            hits.append((code, ipa))
+    return hits
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/global_search.ui')
+class Word2ipaGlobalSearchDialog(Adw.Dialog):
+    """Looks a word up in every installed language at once, for loanwords
+    or words of an unknown language.
+    """
+    __gtype_name__ = 'Word2ipaGlobalSearchDialog'
+    search_entry = Gtk.Template.Child()
+    stack = Gtk.Template.Child()
+    status_page = Gtk.Template.Child()
+    results_group = Gtk.Template.Child()
+    results_list = Gtk.Template.Child()
+
+    def __init__(self, text="", **kwargs):
+        super().__init__(**kwargs)
+        self.state = AppState.get_default()
+        # only the results of the latest search are shown
+        self.generation = 0
+        self.search_entry.set_text(text)
+        self.search_entry.grab_focus()
+
+    @Gtk.Template.Callback()
+    def on_search_changed(self, entry):
+        self.generation += 1
+        word, problem = clean_input(entry.get_text())
+        if problem:
+            self.show_status("edit-find-symbolic", _("Search All Languages"),
+                             _("Find the languages that have a word and how it is pronounced in each"))
+            return
+        self.stack.set_visible_child_name("searching")
+        generation = self.generation
+        prepared = prepare_search()
+
+        def worker():
+            hits = search_all(word, prepared)
+            GLib.idle_add(self.on_searched, generation, word, hits)
+
+        # every dictionary is opened, which can take a while the first time
+        threading.Thread(target=worker, daemon=True).start()
+
+    def on_searched(self, generation, word, hits):
+        if generation != self.generation:
+            return
+        if not hits:
+            self.show_status("edit-find-symbolic", _("No Results"),
+                             _("“{}” is not in any installed dictionary").format(word))
+            return
+        self.results_group.set_title(
+            ngettext("Found in {} Language", "Found in {} Languages", len(hits)).format(len(hits)))
+        self.results_list.remove_all()
+        for code, ipa in hits:
+            row = Adw.ActionRow(title=languages.display_name(code), subtitle=self.state.display(ipa, code),
+                                use_markup=False, activatable=True, action_name="win.look-up",
+                                action_target=GLib.Variant("(ss)", (word, code)))
+            row.add_suffix(Gtk.Image(icon_name="go-next-symbolic"))
+            row.connect("activated", lambda _r: self.close())
+            self.results_list.append(row)
+        self.stack.set_visible_child_name("results")
+
+    def show_status(self, icon_name, title, description):
+        self.status_page.set_icon_name(icon_name)
+        self.status_page.set_title(title)
+        self.status_page.set_description(GLib.markup_escape_text(description))
+        self.stack.set_visible_child_name("status")
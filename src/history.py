@@ -0,0 +1,157 @@
+# history.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gio, GObject
+import time
+from . import storage
+
+HISTORY_FILE = 'history.json'
+
+
+def insert_item(items, item):
+    """Put item on top of a list of items, taking over the lookups of the same word."""
+    for position, other in enumerate(items):
+        if other.word == item.word and other.lang == item.lang:
+            item.count += other.count
+            del items[position]
+            break
+    items.insert(0, item)
+
+
+def apply_change(items, change):
+    """Replay a journaled change on a list of items."""
+    if change["op"] == "add":
+        insert_item(items, HistoryItem(**change["item"]))
+    elif change["op"] == "remove":
+        items[:] = [item for item in items if (item.word, item.lang)
+                    != (change["item"]["word"], change["item"]["lang"])]
+    elif change["op"] == "prune":
+        del items[change["limit"]:]
+    elif change["op"] == "clear":
+        items.clear()
+
+
+class HistoryItem(GObject.Object):
+    __gtype_name__ = 'Word2ipaHistoryItem'
+
+    word = GObject.Property(type=str)
+    ipa = GObject.Property(type=str)
+    lang = GObject.Property(type=str)
+    timestamp = GObject.Property(type=GObject.TYPE_INT64)
+    # how many times the word was looked up
+    count = GObject.Property(type=int, default=1)
+
+    def to_json(self):
+        return {"word": self.word, "ipa": self.ipa, "lang": self.lang, "timestamp": self.timestamp,
+                "count": self.count}
+
+
+class History(GObject.Object):
+    """Words looked up so far, most recently looked up first.
+
+    Each word of a language is in it once; looking it up again moves it
+    to the top and counts the lookup. Saved through a write-behind
+    journal since every lookup changes it.
+    """
+    __gtype_name__ = 'Word2ipaHistory'
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        self.store = Gio.ListStore(item_type=HistoryItem)
+        self.writer = storage.WriteBehind(HISTORY_FILE, self.to_json, self.reload)
+        with storage.lock(HISTORY_FILE):
+            self.reload(storage.load_json(HISTORY_FILE, []), self.writer.replay())
+        # fold what the journal had into the file
+        self.writer.flush()
+        self.settings.connect('changed::history-limit', lambda *_args: self.prune())
+
+    def __len__(self):
+        return self.store.get_n_items()
+
+    def __iter__(self):
+        return iter(self.store)
+
+    def to_json(self):
+        return [item.to_json() for item in self]
+
+    def reload(self, data, changes):
+        """Rebuild the history from saved data and the journaled changes,
+        which may include lookups made in other instances.
+        """
+        items = []
+        # oldest first, so repeated lookups saved before they were merged end up on top
+        for entry in reversed(data):
+            insert_item(items, HistoryItem(**entry))
+        for change in changes:
+            apply_change(items, change)
+        limit = self.settings.get_int('history-limit')
+        if limit > 0:
+            del items[limit:]
+        # rows are only rebuilt if another instance changed something
+        if [item.to_json() for item in items] != self.to_json():
+            self.store.splice(0, len(self), items)
+
+    def find(self, word, lang):
+        for position, item in enumerate(self):
+            if item.word == word and item.lang == lang:
+                return position
+        return None
+
+    def _insert(self, item):
+        """Put item on top, taking over the lookups of the same word."""
+        if (position := self.find(item.word, item.lang)) is not None:
+            item.count += self.store.get_item(position).count
+            self.store.remove(position)
+        self.store.insert(0, item)
+
+    def add(self, word, ipa, lang):
+        item = HistoryItem(word=word, ipa=ipa, lang=lang, timestamp=int(time.time()))
+        self._insert(item)
+        self.writer.record({"op": "add", "item": {"word": word, "ipa": ipa, "lang": lang,
+                                                  "timestamp": item.timestamp}})
+        self.prune()
+        return item
+
+    def prune(self):
+        """Remove the oldest words beyond the "history-limit" setting."""
+        limit = self.settings.get_int('history-limit')
+        if limit > 0 and len(self) > limit:
+            self.store.splice(limit, len(self) - limit, [])
+            self.writer.record({"op": "prune", "limit": limit})
+
+    def remove(self, item):
+        if (position := self.find(item.word, item.lang)) is not None:
+            self.store.remove(position)
+            self.writer.record({"op": "remove", "item": item.to_json()})
+
+    def clear(self):
+        self.store.remove_all()
+        self.writer.record({"op": "clear"})
+
+    def flush(self):
+        self.writer.flush()
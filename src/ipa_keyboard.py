@@ -0,0 +1,83 @@
+# ipa_keyboard.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GObject, Gtk
+
+SYMBOLS = [
+    "i", "ɪ", "e", "ɛ", "æ", "ʌ", "ɑ", "ɒ", "ɔ", "ʊ", "u", "ə", "ɜ", "ɚ", "ɝ", "o", "a",
+    "θ", "ð", "ʃ", "ʒ", "ŋ", "ɹ", "ɾ", "ʔ", "ɡ", "j", "ɫ", "ʍ", "ç", "x", "ʁ", "ɲ",
+    "ˈ", "ˌ", "ː", "͡", "̃",
+]
+
+
+class Word2ipaIpaKeyboard(Gtk.FlowBox):
+    """Buttons that type IPA symbols into the target editable.
+
+    Without a target they type into whichever editable has the focus,
+    as the buttons never take it themselves.
+    """
+    __gtype_name__ = 'Word2ipaIpaKeyboard'
+
+    target = GObject.Property(type=Gtk.Editable)
+
+    def __init__(self, **kwargs):
+        super().__init__(selection_mode=Gtk.SelectionMode.NONE,
+                         homogeneous=True,
+                         min_children_per_line=6,
+                         max_children_per_line=12,
+                         column_spacing=4,
+                         row_spacing=4,
+                         **kwargs)
+        for symbol in SYMBOLS:
+            # show combining marks on a dotted circle so they are visible
+            label = f"◌{symbol}" if symbol in "̃͡" else symbol
+            button = Gtk.Button(label=label, css_classes=["flat"], can_focus=False)
+            button.connect("clicked", self.on_symbol_clicked, symbol)
+            self.append(button)
+
+    def on_symbol_clicked(self, button, symbol):
+        editable = self.target
+        if editable is None:
+            editable = self.get_root().get_focus()
+        if not isinstance(editable, Gtk.Editable):
+            return
+        editable.delete_selection()
+        position = editable.insert_text(symbol, -1, editable.get_position())
+        editable.set_position(position)
+        editable.grab_focus()
+
+
+class Word2ipaIpaKeyboardButton(Gtk.ToggleButton):
+    """Shows and hides an IPA keyboard kept in a revealer."""
+    __gtype_name__ = 'Word2ipaIpaKeyboardButton'
+
+    revealer = GObject.Property(type=Gtk.Revealer)
+
+    def __init__(self, **kwargs):
+        super().__init__(icon_name="input-keyboard-symbolic",
+                         tooltip_text=_("IPA Keyboard"),
+                         valign=Gtk.Align.CENTER,
+                         can_focus=False,
+                         css_classes=["flat"],
+                         **kwargs)
+        self.connect("toggled", self.on_toggled)
+
+    def on_toggled(self, button):
+        if self.revealer is not None:
+            self.revealer.set_reveal_child(self.get_active())
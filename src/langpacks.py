@@ -0,0 +1,224 @@
+# langpacks.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gio, GLib, GObject
+import contextlib
+import json
+import os
+import re
+import sqlite3
+import threading
+import urllib.parse
+import urllib.request
+from . import storage
+from .core import Dictionary, DictionaryError, compile_dictionary, load_dictionary
+
+PACKS_DIR = 'dicts'
+INSTALLED_FILE = 'installed_languages.json'
+CHUNK_SIZE = 64 * 1024
+# codes of constructed languages start with this, so they never clash
+# with the ones of the index
+CONLANG_PREFIX = 'x_'
+# like en_US or fa; codes end up in file names, so nothing else is taken
+CODE_PATTERN = re.compile(r'[a-z]{2,3}(_[A-Z]{1,2})?')
+
+
+def packs_dir():
+    path = storage.data_path(PACKS_DIR)
+    os.makedirs(path, exist_ok=True)
+    return path
+
+
+def is_valid_pack(pack):
+    """Whether an entry of the remote index can be offered: it has a
+    name, a code like CODE_PATTERN and an https url.
+    """
+    return (isinstance(pack, dict) and isinstance(pack.get('name'), str)
+            and isinstance(pack.get('code'), str) and CODE_PATTERN.fullmatch(pack['code']) is not None
+            and isinstance(pack.get('url'), str) and urllib.parse.urlsplit(pack['url']).scheme == 'https')
+
+
+class LanguagePacks(GObject.Object):
+    """Dictionaries downloaded by the user into the data dir.
+
+    The remote index is a JSON list of {"code", "name", "url", "size",
+    "version"} objects; its location is the "language-index-url" setting.
+    A pack may carry a "metadata" object citing its source and license.
+
+    Constructed languages made by the user are installed the same way,
+    as empty dictionaries whose metadata lists the sounds they use.
+    """
+    __gtype_name__ = 'Word2ipaLanguagePacks'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        # code -> name of every installed pack
+        self.installed = storage.load_json(INSTALLED_FILE, {})
+
+    def path(self, code):
+        return os.path.join(packs_dir(), f'{code}.db')
+
+    def is_installed(self, code):
+        return code in self.installed and os.path.exists(self.path(code))
+
+    def load(self, code):
+        """Open an installed pack, compiled like the bundled ones. The
+        caller closes it, dictionaries.load() keeps the ones in use.
+        """
+        return load_dictionary(code, self.path(code))
+
+    def has_update(self, pack):
+        """Whether the index offers a different version than the installed one."""
+        if not self.is_installed(pack['code']) or 'version' not in pack:
+            return False
+        try:
+            with contextlib.closing(self.load(pack['code'])) as dictionary:
+                installed = dictionary.metadata.get('version')
+        except sqlite3.Error:
+            return True
+        return installed != str(pack['version'])
+
+    def fetch_index(self, callback):
+        """Download the remote index; callback(index, error) runs on the main loop."""
+        url = self.settings.get_string('language-index-url')
+
+        def worker():
+            try:
+                with urllib.request.urlopen(url, timeout=30) as response:
+                    index = json.loads(response.read().decode('utf-8'))
+                GLib.idle_add(callback, index, None)
+            except (OSError, ValueError) as e:
+                GLib.idle_add(callback, None, str(e))
+
+        threading.Thread(target=worker, daemon=True).start()
+
+    def download(self, pack, progress_cb, done_cb, watched=()):
+        """Download pack into the data dir, replacing an older version.
+
+        progress_cb(fraction) and done_cb(error, changes) run on the main
+        loop. changes lists (word, old, new) for the watched words whose
+        transcription differs from the replaced version, it is None if
+        the pack wasn't installed before.
+        """
+        if not is_valid_pack(pack):
+            GLib.idle_add(done_cb, f"not a valid language pack: {pack!r}", None)
+            return
+        code = pack['code']
+        tmp_path = os.path.join(packs_dir(), f'{code}.json.part')
+
+        def worker():
+            try:
+                with urllib.request.urlopen(pack['url'], timeout=30) as response, \
+                        open(tmp_path, 'wb') as f:
+                    total = int(response.headers.get('Content-Length') or pack.get('size') or 0)
+                    received = 0
+                    while chunk := response.read(CHUNK_SIZE):
+                        f.write(chunk)
+                        received += len(chunk)
+                        if total:
+                            GLib.idle_add(progress_cb, min(received / total, 1.0))
+                # parsing also makes sure we got a usable dictionary
+                with open(tmp_path, 'rb') as f:
+                    dictionary = Dictionary.parse(code, f.read())
+                if 'version' in pack:
+                    dictionary.metadata['version'] = str(pack['version'])
+                changes = None
+                if self.is_installed(code):
+                    with contextlib.closing(self.load(code)) as installed:
+                        changes = self.changes(installed, dictionary, watched)
+                compile_dictionary(dictionary, self.path(code))
+            except (OSError, sqlite3.Error, DictionaryError) as e:
+                GLib.idle_add(done_cb, str(e), None)
+                return
+            finally:
+                if os.path.exists(tmp_path):
+                    os.remove(tmp_path)
+            GLib.idle_add(self._on_installed, pack, done_cb, changes)
+
+        threading.Thread(target=worker, daemon=True).start()
+
+    @staticmethod
+    def changes(old, new, words):
+        """(word, old ipa, new ipa) for the words transcribed differently."""
+        changes = []
+        for word in dict.fromkeys(words):
+            before, after = old.lookup(word), new.lookup(word)
+            if before != after:
+                changes.append((word, before, after))
+        return changes
+
+    def _on_installed(self, pack, done_cb, changes):
+        self.installed[pack['code']] = pack['name']
+        storage.save_json(INSTALLED_FILE, self.installed)
+        self.emit('changed')
+        done_cb(None, changes)
+
+    def create_conlang(self, name, inventory):
+        """Install an empty dictionary for a constructed language made of
+        the sounds of inventory and return its code. Its words are added
+        as user dictionary entries.
+        """
+        slug = re.sub(r'\W+', '_', name.casefold()).strip('_') or 'language'
+        code = f'{CONLANG_PREFIX}{slug}'
+        number = 2
+        while code in self.installed:
+            code = f'{CONLANG_PREFIX}{slug}_{number}'
+            number += 1
+        dictionary = Dictionary(code, {}, {"conlang": name, "inventory": " ".join(inventory)})
+        compile_dictionary(dictionary, self.path(code))
+        self._on_installed({"code": code, "name": name}, lambda *_args: None, None)
+        return code
+
+    def is_conlang(self, code):
+        return code.startswith(CONLANG_PREFIX) and self.is_installed(code)
+
+    def conlangs(self):
+        """(code, name) of the constructed languages, by name."""
+        return sorted(((code, name) for code, name in self.installed.items() if self.is_conlang(code)),
+                      key=lambda conlang: conlang[1])
+
+    def inventory(self, code):
+        """The sounds of a constructed language, or None for other languages."""
+        if not self.is_conlang(code):
+            return None
+        try:
+            with contextlib.closing(self.load(code)) as dictionary:
+                return dictionary.metadata.get("inventory", "").split()
+        except sqlite3.Error as e:
+            print(f"could not read the inventory of {code}: {e}")
+            return None
+
+    def remove(self, code):
+        if os.path.exists(self.path(code)):
+            os.remove(self.path(code))
+        if self.installed.pop(code, None) is not None:
+            storage.save_json(INSTALLED_FILE, self.installed)
+            self.emit('changed')
@@ -0,0 +1,78 @@
+# languages.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from .langpacks import LanguagePacks
+
+# (code, name) of every bundled dictionary, in the order they are offered
+LANGUAGES = [
+    ("en_US", "American English"),
+    ("ar", "Arabic"),
+    ("de", "German"),
+    ("en_UK", "British English"),
+    ("eo", "Esperanto"),
+    ("es_ES", "Spanish - Spain"),
+    ("es_MX", "Spanish - Mexico"),
+    ("fa", "Persian"),
+    ("fi", "Finnish"),
+    ("fr_FR", "French - France"),
+    ("fr_QC", "French - Quebec"),
+    ("ja", "Japanese"),
+    ("jam", "Jamaican Patois"),
+    ("ma", "Marathi"),
+    ("nb", "Norwegian Bokmål"),
+    ("or", "Odia"),
+    ("sv", "Swedish"),
+    ("sw", "Swahili"),
+    ("vi_C", "Vietnamese - Central"),
+    ("vi_N", "Vietnamese - Northern"),
+    ("vi_S", "Vietnamese - Southern"),
+    ("yue", "Cantonese"),
+    ("zh_hans", "Chinese - Simplified"),
+    ("zh_hant", "Chinese - Traditional"),
+]
+
+
+BUNDLED = {code for code, _name in LANGUAGES}
+
+
+def available():
+    """Return (code, name) of the bundled and downloaded dictionaries."""
+    installed = LanguagePacks.get_default().installed
+    return LANGUAGES + sorted(
+        ((code, name) for code, name in installed.items() if code not in BUNDLED),
+        key=lambda lang: lang[1])
+
+
+def display_name(code):
+    """Return the "Name (code)" label shown in language selectors."""
+    for lang_code, name in available():
+        if lang_code == code:
+            return f"{name} ({code})"
+    return code
+
+
+def parse_code(label):
+    """Extract the language code from a "Name (code)" label."""
+    if "(" in label and ")" in label:
+        return label[label.find("(")+1 : label.find(")")]
+    return label.split()[-1]
+
+
+def display_names():
+    return [f"{name} ({code})" for code, name in available()]
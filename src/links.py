@@ -0,0 +1,46 @@
+# links.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio, GLib, Gtk
+import urllib.parse
+
+# site -> settings key of its URL template
+SITES = {
+    "wiktionary": "wiktionary-url",
+    "forvo": "forvo-url",
+}
+
+
+def url_for(site, word, lang):
+    """Fill the URL template of site with word and the language part
+    of lang, for templates like "https://forvo.com/word/{word}/#{lang}".
+    """
+    settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+    template = settings.get_string(SITES[site])
+    return template.replace("{word}", urllib.parse.quote(word)).replace("{lang}", lang.split("_")[0])
+
+
+def open_word(parent, site, word, lang):
+    """Open the page of word on site in the browser."""
+    def on_finish(launcher, result):
+        try:
+            launcher.launch_finish(result)
+        except GLib.Error as e:
+            print(f"could not open {launcher.get_uri()}: {e.message}")
+
+    Gtk.UriLauncher.new(url_for(site, word, lang)).launch(parent, None, on_finish)
@@ -18,13 +18,34 @@
 # SPDX-License-Identifier: GPL-3.0-or-later
 
 import sys
+import time
 import gi
 
 gi.require_version('Gtk', '4.0')
 gi.require_version('Adw', '1')
 
-from gi.repository import Gtk, Gio, Adw
+from gi.repository import Gtk, Gio, GLib, Adw
+from . import backups, export, languages, pipe, printing
+from .anki_export import Word2ipaAnkiExportDialog
+from .batch import Word2ipaBatchDialog, read_word_list
+from .dbus_service import LookupService
+from .history import History
 from .window import Word2ipaWindow
+from .preferences import Word2ipaPreferencesDialog
+from .state import AppState
+from .stats import Stats
+
+WEEK = 7 * 24 * 3600
+# how long a service started only for D-Bus calls waits for more of them, in ms
+SERVICE_TIMEOUT = 30000
+
+# --page name -> page of the window's stack
+PAGES = {
+    "transcribe": "Word to IPA",
+    "dictionary": "page2",
+    "favorites": "favorites",
+    "practice": "practice",
+}
 
 
 class Word2ipaApplication(Adw.Application):
@@ -32,12 +53,37 @@ class Word2ipaApplication(Adw.Application):
 
     def __init__(self):
         super().__init__(application_id='io.github.mohfy.word2ipa',
-                         flags=Gio.ApplicationFlags.DEFAULT_FLAGS,
+                         flags=Gio.ApplicationFlags.HANDLES_COMMAND_LINE | Gio.ApplicationFlags.HANDLES_OPEN,
                          resource_base_path='/io/github/mohfy/word2ipa')
         self.create_action('quit', lambda *_: self.quit(), ['<primary>q'])
         self.create_action('about', self.on_about_action)
         self.create_action('preferences', self.on_preferences_action)
-
+        self.create_action('show-statistics', self.on_show_statistics_action)
+        self.create_action('export-history', self.on_export_history_action)
+        self.create_action('export-anki', self.on_export_anki_action)
+        self.create_action('print-history', self.on_print_history_action)
+        self.create_action('transcribe-file', self.on_transcribe_file_action)
+        self.set_accels_for_action('win.focus-entry', ['<primary>l'])
+        # Ctrl+C stays the copy of selected text everywhere
+        self.set_accels_for_action('win.copy-result', ['<primary><shift>c'])
+        self.set_accels_for_action('win.paste-look-up', ['<primary><shift>v'])
+        self.set_accels_for_action('win.search-symbols', ['<primary>f'])
+        self.set_accels_for_action('win.search-all-languages', ['<primary><shift>f'])
+        for number, page in enumerate(PAGES.values(), 1):
+            self.set_accels_for_action(f"win.show-page('{page}')", [f'<primary>{number}'])
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        self.lookup_service = LookupService(self)
+        self.set_inactivity_timeout(SERVICE_TIMEOUT)
+        self.add_main_option('lang', 0, GLib.OptionFlags.NONE, GLib.OptionArg.STRING,
+                             _('Start with the dictionary of a language, like fr_FR'), 'CODE')
+        self.add_main_option('page', 0, GLib.OptionFlags.NONE, GLib.OptionArg.STRING,
+                             _('Start on a page: transcribe, dictionary, favorites or practice'), 'PAGE')
+        self.add_main_option('pipe', 0, GLib.OptionFlags.NONE, GLib.OptionArg.NONE,
+                             _('Transcribe the words read from standard input, a line each'), None)
+        self.add_main_option('format', 0, GLib.OptionFlags.NONE, GLib.OptionArg.STRING,
+                             _('How --pipe writes its lines: tsv, json or plain'), 'FORMAT')
+        self.add_main_option(GLib.OPTION_REMAINING, 0, GLib.OptionFlags.NONE, GLib.OptionArg.STRING_ARRAY,
+                             _('Text files to transcribe'), 'FILE…')
 
 
     def do_activate(self):
@@ -49,8 +95,139 @@ class Word2ipaApplication(Adw.Application):
         win = self.props.active_window
         if not win:
             win = Word2ipaWindow(application=self)
+            try:
+                backups.make_backup()
+            except OSError as e:
+                print(f"could not back up user data: {e}")
+            self.check_weekly_summary()
+            GLib.timeout_add_seconds(3600, self.check_weekly_summary)
         win.present()
 
+    def do_handle_local_options(self, options):
+        """Run --pipe right here, without a window or a running instance.
+
+        options is only read: it is passed on to do_command_line() of the
+        primary instance afterwards.
+        """
+        def value(name, default=None):
+            found = options.lookup_value(name, None)
+            return found.unpack() if found is not None else default
+
+        if not options.contains('pipe'):
+            if options.contains('format'):
+                sys.stderr.write("--format only applies to --pipe\n")
+                return 1
+            return -1
+        output_format = value('format', 'tsv')
+        if output_format not in pipe.FORMATS:
+            sys.stderr.write(f"unknown format {output_format}, expected one of {', '.join(pipe.FORMATS)}\n")
+            return 1
+        lang = value('lang', languages.available()[0][0])
+        if lang not in dict(languages.available()):
+            sys.stderr.write(f"unknown language {lang}\n")
+            return 1
+        return pipe.run(lang, sys.stdin, sys.stdout, sys.stderr, output_format)
+
+    def do_dbus_register(self, connection, object_path):
+        """Export the lookup interface next to the application's own."""
+        Adw.Application.do_dbus_register(self, connection, object_path)
+        self.lookup_service.register(connection, object_path)
+        return True
+
+    def do_dbus_unregister(self, connection, object_path):
+        self.lookup_service.unregister()
+        Adw.Application.do_dbus_unregister(self, connection, object_path)
+
+    def do_command_line(self, command_line):
+        """Open the window, switched to what --lang and --page ask for,
+        and transcribe the text files given.
+
+        This also runs in the already open instance, so a second launch
+        switches the running window.
+        """
+        options = command_line.get_options_dict().end().unpack()
+        if 'page' in options and options['page'] not in PAGES:
+            command_line.printerr(f"unknown page {options['page']}, expected one of {', '.join(PAGES)}\n")
+            return 1
+        if 'lang' in options and options['lang'] not in dict(languages.available()):
+            command_line.printerr(f"unknown language {options['lang']}\n")
+            return 1
+
+        self.activate()
+        win = self.props.active_window
+        if 'lang' in options:
+            win.set_language(options['lang'])
+        if 'page' in options:
+            win.show_page(PAGES[options['page']])
+        if files := options.get(GLib.OPTION_REMAINING):
+            self.open([command_line.create_file_for_arg(arg) for arg in files], "")
+        return 0
+
+    def do_open(self, files, n_files, hint):
+        """Transcribe text files opened with the app, one dialog each."""
+        self.activate()
+        for file in files:
+            self.transcribe_file(file.get_path())
+
+    def check_weekly_summary(self):
+        """Send the opt-in weekly summary notification when it is due."""
+        now = int(time.time())
+        if not self.settings.get_boolean('weekly-summary'):
+            return GLib.SOURCE_CONTINUE
+        if self.settings.get_int64('last-weekly-summary') == 0:
+            # first week after opting in
+            self.settings.set_int64('last-weekly-summary', now)
+        elif now - self.settings.get_int64('last-weekly-summary') >= WEEK:
+            summary = Stats.get_default().week_summary(now)
+            body = _("{} lookups, {} practice sessions, {} new sounds").format(
+                summary["lookups"], summary["sessions"], len(summary["symbols"]))
+            notification = Gio.Notification.new(_("Your Week in Word2IPA"))
+            notification.set_body(body)
+            notification.set_default_action('app.show-statistics')
+            self.send_notification('weekly-summary', notification)
+            self.settings.set_int64('last-weekly-summary', now)
+        return GLib.SOURCE_CONTINUE
+
+    def on_export_history_action(self, *args):
+        """Callback for the app.export-history action."""
+        export.choose_save_path(
+            self.props.active_window, "history.csv",
+            lambda path: export.write_history(path, History.get_default()),
+            filters=[export.file_filter(_("CSV"), "*.csv"),
+                     export.file_filter(_("JSON"), "*.json")])
+
+    def on_print_history_action(self, *args):
+        """Callback for the app.print-history action."""
+        history = History.get_default()
+        printing.print_transcriptions(self.props.active_window, _("History"),
+                                      [(item.word, item.ipa) for item in history],
+                                      {item.lang for item in history})
+
+    def on_export_anki_action(self, *args):
+        """Callback for the app.export-anki action."""
+        Word2ipaAnkiExportDialog().present(self.props.active_window)
+
+    def on_transcribe_file_action(self, *args):
+        """Callback for the app.transcribe-file action."""
+        export.choose_open_path(self.props.active_window, self.transcribe_file,
+                                filters=[export.file_filter(_("Text File"), "*.txt")])
+
+    def transcribe_file(self, path):
+        """Show the transcriptions of the words of a text file."""
+        try:
+            words, unreadable = read_word_list(path)
+        except (OSError, TypeError) as e:
+            # TypeError for files without a local path
+            print(f"could not read {path}: {e}")
+            self.props.active_window.show_toast(_("Could not read the file"))
+            return
+        Word2ipaBatchDialog(words, AppState.get_default().lang, unreadable).present(self.props.active_window)
+
+    def on_show_statistics_action(self, *args):
+        """Callback for the app.show-statistics action."""
+        self.activate()
+        self.props.active_window.show_page('practice')
+
     def on_about_action(self, *args):
         """Callback for the app.about action."""
         about = Adw.AboutDialog(application_name='word2ipa',
@@ -66,7 +243,9 @@ class Word2ipaApplication(Adw.Application):
 
     def on_preferences_action(self, widget, _):
         """Callback for the app.preferences action."""
-        print('app.preferences action activated')
+        win = self.props.active_window
+        preferences = Word2ipaPreferencesDialog(lang=AppState.get_default().lang)
+        preferences.present(win)
 
     def create_action(self, name, callback, shortcuts=None):
         """Add an application action.
@@ -0,0 +1,115 @@
+# minimal_pairs.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, Gtk
+import sqlite3
+import threading
+from . import dictionaries, languages
+from .core import clean_input, collation, phonemes
+from .ipa_keyboard import Word2ipaIpaKeyboard, Word2ipaIpaKeyboardButton
+
+
+def parse_query(text):
+    """Return ("sounds", (first, second)) for two single sounds like
+    "ɪ iː", ("word", word) for anything else or None if text is unusable.
+    """
+    query, problem = clean_input(text.replace("/", " "))
+    if problem:
+        return None
+    parts = query.split()
+    if len(parts) == 2 and all(len(phonemes.split(part)) == 1 for part in parts):
+        return "sounds", tuple(phonemes.split(part)[0] for part in parts)
+    return "word", query.lower()
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/minimal_pairs.ui')
+class Word2ipaMinimalPairsDialog(Adw.Dialog):
+    """Lists words that differ in a single sound, like ship and sheep."""
+    __gtype_name__ = 'Word2ipaMinimalPairsDialog'
+    query_row = Gtk.Template.Child()
+    results_group = Gtk.Template.Child()
+    results_list = Gtk.Template.Child()
+    spinner = Gtk.Template.Child()
+
+    def __init__(self, lang, **kwargs):
+        super().__init__(**kwargs)
+        self.lang = lang
+        self.dictionary = None
+        self.query_row.set_sensitive(False)
+        self.results_group.set_description(languages.display_name(lang))
+        dictionaries.load_async(lang, self.on_dictionary_loaded)
+
+    def on_dictionary_loaded(self, dictionary, error):
+        self.spinner.set_visible(False)
+        if error:
+            self.results_group.set_description(_("Could not load the dictionary"))
+            print(f"could not load {self.lang} dictionary: {error}")
+            return
+        self.dictionary = dictionary
+        self.query_row.set_sensitive(True)
+        self.query_row.grab_focus()
+
+    @Gtk.Template.Callback()
+    def on_query_apply(self, row):
+        query = parse_query(row.get_text())
+        self.results_list.remove_all()
+        if query is None:
+            return
+        self.spinner.set_visible(True)
+        self.query_row.set_sensitive(False)
+
+        def worker():
+            kind, value = query
+            try:
+                if kind == "sounds":
+                    pairs = collation.sort_words(self.dictionary.sound_pairs(*value), self.lang,
+                                                 word=lambda pair: pair[0][0])
+                else:
+                    # hardest to tell apart first
+                    pairs = self.dictionary.minimal_pairs(value)
+            except sqlite3.Error as e:
+                print(f"could not search {self.lang} dictionary: {e}")
+                pairs = []
+            GLib.idle_add(self.show_results, kind, pairs)
+
+        # every entry of the dictionary is compared, which takes a moment
+        threading.Thread(target=worker, daemon=True).start()
+
+    def on_row_activated(self, row, word):
+        self.activate_action("win.look-up", GLib.Variant("(ss)", (word, self.lang)))
+        self.close()
+
+    def show_results(self, kind, pairs):
+        self.spinner.set_visible(False)
+        self.query_row.set_sensitive(True)
+        for pair in pairs:
+            if kind == "sounds":
+                (word, ipa), (other, other_ipa) = pair
+                title = f"{word} / {other}"
+                subtitle = f"{ipa} · {other_ipa}"
+            else:
+                other, other_ipa, (sound, other_sound) = pair
+                title = other
+                subtitle = f"{other_ipa} · {sound} → {other_sound}"
+            row = Adw.ActionRow(title=title, subtitle=subtitle, use_markup=False)
+            if kind == "word":
+                row.set_activatable(True)
+                row.connect("activated", self.on_row_activated, other)
+            self.results_list.append(row)
+        self.results_group.set_description(
+            ngettext("{} pair found", "{} pairs found", len(pairs)).format(len(pairs)))
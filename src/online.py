@@ -0,0 +1,130 @@
+# online.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio, GLib, GObject
+import json
+import threading
+import urllib.parse
+import urllib.request
+from . import storage
+from .core import Dictionary, wikitext
+
+API_URL = ("https://en.wiktionary.org/w/api.php?action=parse&format=json&formatversion=2"
+           "&prop=wikitext&redirects=1&page={page}")
+# Wikimedia asks API clients to say who they are
+USER_AGENT = "Word2ipa (https://github.com/mohfy/word2ipa)"
+ONLINE_FILE = 'online_results.json'
+
+
+def is_enabled():
+    """Whether the user allowed sending missing words to Wiktionary."""
+    return Gio.Settings.new('io.github.mohfy.word2ipa').get_boolean('online-fallback')
+
+
+def _page_wikitext(page):
+    request = urllib.request.Request(API_URL.format(page=urllib.parse.quote(page)),
+                                     headers={"User-Agent": USER_AGENT})
+    with urllib.request.urlopen(request, timeout=30) as response:
+        data = json.loads(response.read().decode('utf-8'))
+    # a missing page is an "error" in the reply, not an HTTP error
+    return data.get("parse", {}).get("wikitext")
+
+
+def fetch(word, lang):
+    """Return the IPA of word from Wiktionary, or None if it has none.
+    Blocks, so it is meant for worker threads; raises OSError or
+    ValueError on failure.
+    """
+    # page titles are case sensitive, sentence case input is common
+    for page in dict.fromkeys([word, word.lower()]):
+        text = _page_wikitext(page)
+        if text and (ipa := wikitext.find_ipa(text, lang)):
+            return ipa
+    return None
+
+
+class OnlineResults(GObject.Object):
+    """Pronunciations found on Wiktionary for words missing from the
+    dictionaries. They are consulted after the dictionaries and shown
+    as online results.
+
+    They are kept in XDG_DATA_HOME, so a word is only fetched once and
+    stays available offline.
+    """
+    __gtype_name__ = 'Word2ipaOnlineResults'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.languages = storage.load_json(ONLINE_FILE, {})
+
+    def count(self):
+        return sum(len(entries) for entries in self.languages.values())
+
+    def lookup(self, lang, word):
+        return self.languages.get(lang, {}).get(word)
+
+    def as_dictionary(self, lang):
+        return Dictionary(lang, self.languages.get(lang, {}))
+
+    def add(self, lang, word, ipa):
+        def change(languages):
+            languages.setdefault(lang, {})[word] = ipa
+            return languages
+        self._save(change)
+
+    def clear(self):
+        self._save(lambda _languages: {})
+
+    def _save(self, change):
+        try:
+            self.languages = storage.update_json(ONLINE_FILE, {}, change)
+        except OSError as e:
+            # still use it for this session
+            print(f"could not save online results: {e}")
+            self.languages = change(self.languages)
+        self.emit('changed')
+
+    def look_up_async(self, word, lang, callback):
+        """Fetch word from Wiktionary on a worker thread; callback(ipa,
+        error) runs on the main loop, and a found ipa is saved first.
+        """
+        def worker():
+            try:
+                ipa = fetch(word, lang)
+            except (OSError, ValueError) as e:
+                GLib.idle_add(callback, None, str(e))
+                return
+            GLib.idle_add(on_fetched, ipa)
+
+        def on_fetched(ipa):
+            if ipa is not None:
+                self.add(lang, word, ipa)
+            callback(ipa, None)
+
+        threading.Thread(target=worker, daemon=True).start()
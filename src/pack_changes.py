@@ -0,0 +1,59 @@
+# pack_changes.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, Gtk
+from .core import phonemes
+
+
+def diff_markup(old, new):
+    """Markup for "old → new" with the sounds that changed in bold."""
+    before, after = [], []
+    for a, b in phonemes.align(old, new):
+        a, b = GLib.markup_escape_text(a or ""), GLib.markup_escape_text(b or "")
+        if a == b:
+            before.append(a)
+            after.append(b)
+        else:
+            before.append(f"<b>{a}</b>" if a else "")
+            after.append(f"<b>{b}</b>" if b else "")
+    return f'/{"".join(before)}/ → /{"".join(after)}/'
+
+
+def present_changes(parent, name, changes):
+    """List the user's words whose transcription changed in an update."""
+    status = Adw.StatusPage(icon_name="software-update-available-symbolic",
+                            title=_("What Changed"),
+                            description=ngettext("{name} was updated and {count} of your words is transcribed differently now",
+                                                 "{name} was updated and {count} of your words are transcribed differently now",
+                                                 len(changes)).format(name=name, count=len(changes)))
+    words = Gtk.ListBox(selection_mode=Gtk.SelectionMode.NONE, css_classes=["boxed-list"])
+    for word, old, new in changes:
+        if old is None:
+            subtitle = _("Added: {}").format(GLib.markup_escape_text(new))
+        elif new is None:
+            subtitle = _("Removed: {}").format(GLib.markup_escape_text(old))
+        else:
+            subtitle = diff_markup(old, new)
+        words.append(Adw.ActionRow(title=GLib.markup_escape_text(word), subtitle=subtitle))
+    status.set_child(words)
+
+    toolbar = Adw.ToolbarView(content=status)
+    toolbar.add_top_bar(Adw.HeaderBar())
+    dialog = Adw.Dialog(title=_("Dictionary Updated"), child=toolbar,
+                        content_width=400, content_height=520)
+    dialog.present(parent)
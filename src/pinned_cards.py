@@ -0,0 +1,72 @@
+# pinned_cards.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gtk
+from . import languages
+
+MAX_CARDS = 3
+
+
+class Word2ipaPinnedCards(Gtk.Box):
+    """Small cards that keep results visible while looking up other words.
+
+    Pinning more than MAX_CARDS results drops the oldest card.
+    """
+    __gtype_name__ = 'Word2ipaPinnedCards'
+
+    def __init__(self, **kwargs):
+        super().__init__(orientation=Gtk.Orientation.VERTICAL,
+                         spacing=6,
+                         halign=Gtk.Align.END,
+                         valign=Gtk.Align.END,
+                         margin_end=12,
+                         margin_bottom=12,
+                         **kwargs)
+        self.cards = []
+
+    def pin(self, word, ipa, lang):
+        for card in self.cards:
+            if card.key == (word, lang):
+                return
+        if len(self.cards) >= MAX_CARDS:
+            self.unpin(self.cards[0])
+
+        card = Gtk.Box(spacing=12, css_classes=["card"])
+        card.key = (word, lang)
+        text = Gtk.Box(orientation=Gtk.Orientation.VERTICAL,
+                       margin_top=6, margin_bottom=6, margin_start=12)
+        text.append(Gtk.Label(label=ipa, xalign=0, selectable=True, css_classes=["title-4"]))
+        text.append(Gtk.Label(label=f"{word} · {languages.display_name(lang)}",
+                              xalign=0, css_classes=["dim-label", "caption"]))
+        card.append(text)
+
+        close_button = Gtk.Button(icon_name="window-close-symbolic",
+                                  tooltip_text=_("Unpin"),
+                                  valign=Gtk.Align.CENTER,
+                                  margin_end=6,
+                                  css_classes=["flat", "circular"])
+        close_button.connect("clicked", lambda _b: self.unpin(card))
+        card.append(close_button)
+
+        self.cards.append(card)
+        self.append(card)
+
+    def unpin(self, card):
+        self.cards.remove(card)
+        self.remove(card)
@@ -0,0 +1,73 @@
+# pipe.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""word2ipa --pipe: transcribe the words read from stdin for shell
+pipelines, without opening a window:
+
+    $ echo "hello world" | word2ipa --pipe --lang en_US
+    hello	/həˈloʊ/	en_US
+    world	/wɝld/	en_US
+
+Words that aren't found, or can't be like "42" or "…", are written with
+an empty IPA, so every word of the input has a line. --format picks how the lines are written:
+
+tsv     word, its pronunciations separated by ", " and the language
+json    a JSON object a line, {"word", "lang", "variants": [...]}
+plain   only the pronunciations
+"""
+
+import json
+import sqlite3
+from . import backends, dictionaries
+from .core import DictionaryError, clean_input, lookup_word, notation, phonemes
+
+FORMATS = {
+    "tsv": lambda word, lang, variants: f"{word}\t{', '.join(variants)}\t{lang}",
+    "json": lambda word, lang, variants: json.dumps({"word": word, "lang": lang, "variants": variants},
+                                                    ensure_ascii=False),
+    "plain": lambda word, lang, variants: ", ".join(variants),
+}
+
+
+def words(lines):
+    """(word, whether it can be looked up) for the words of the lines,
+    each line may hold several. Ones without letters can't, see
+    clean_input().
+    """
+    for line in lines:
+        for text in line.split():
+            word, problem = clean_input(text)
+            yield word, problem is None
+
+
+def run(lang, stdin, stdout, stderr, output_format="tsv"):
+    """Write a line in output_format, one of FORMATS, for each word of
+    stdin; return the exit status.
+    """
+    try:
+        sources = [backends.chain(lang, dictionaries.load(lang), online=False).for_language(lang)]
+    except (OSError, sqlite3.Error, DictionaryError) as e:
+        stderr.write(f"could not load the {lang} dictionary: {e}\n")
+        return 1
+    for word, lookable in words(stdin):
+        ipa = lookup_word(word, sources)[0] if lookable else None
+        variants = phonemes.variants(notation.canonicalize(ipa)) if ipa is not None else []
+        # flushed right away so a reader waiting on each line gets it
+        stdout.write(FORMATS[output_format](word, lang, variants) + "\n")
+        stdout.flush()
+    return 0
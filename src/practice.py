@@ -0,0 +1,161 @@
+# practice.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GObject
+from collections import Counter
+import time
+from . import content_filter, storage
+from .core import phonemes
+from .stats import Stats
+from .word_lists import WordLists
+
+MISTAKES_FILE = 'mistakes.json'
+ROUND_LENGTH = 10
+
+
+def practice_words(dictionary, lang, containing=None):
+    """Up to ROUND_LENGTH random words of dictionary for a round, from
+    the word list and level chosen for lang and without blocked words.
+    containing optionally limits them to words with these sounds.
+    """
+    word_lists = WordLists.get_default()
+    return dictionary.random_words(ROUND_LENGTH, containing=containing, exclude=content_filter.blocked_words(lang),
+                                   within=word_lists.selected_words(lang), level=word_lists.selected_level(lang))
+
+
+class PracticeSession:
+    """The answers given during one run of a practice mode."""
+
+    def __init__(self, mode, lang):
+        self.mode = mode
+        self.lang = lang
+        self.started = time.time()
+        self.finished = None
+        self.attempts = []
+
+    def record(self, word, ipa, answer, correct, answer_is_ipa=False):
+        self.attempts.append({
+            "word": word,
+            "ipa": ipa,
+            "answer": answer,
+            "correct": correct,
+            "answer_is_ipa": answer_is_ipa,
+        })
+
+    def finish(self):
+        """End the session, keep its mistakes and store its summary."""
+        self.finished = time.time()
+        MistakeList.get_default().add_session(self)
+        summary = self.summary()
+        if summary["total"]:
+            Stats.get_default().add_session(summary, self.confusions())
+        return summary
+
+    @property
+    def mistakes(self):
+        return [attempt for attempt in self.attempts if not attempt["correct"]]
+
+    def hardest_phonemes(self, count=3):
+        """The sounds that appeared most often in wrongly answered items."""
+        missed = Counter()
+        for attempt in self.mistakes:
+            missed.update(set(phonemes.split(attempt["ipa"])))
+        return [phoneme for phoneme, _count in missed.most_common(count)]
+
+    def confusions(self):
+        """Count which sound was typed in place of which, e.g. (ɪ, iː)."""
+        confused = Counter()
+        for attempt in self.mistakes:
+            if not attempt["answer_is_ipa"]:
+                continue
+            for expected, given in phonemes.align(attempt["ipa"], attempt["answer"]):
+                if expected and given and expected != given:
+                    confused[(expected, given)] += 1
+        return confused
+
+    def summary(self):
+        correct = len(self.attempts) - len(self.mistakes)
+        return {
+            "mode": self.mode,
+            "lang": self.lang,
+            "date": int(self.started),
+            "duration": int((self.finished or time.time()) - self.started),
+            "total": len(self.attempts),
+            "correct": correct,
+            "hardest": self.hardest_phonemes(),
+        }
+
+
+class MistakeList(GObject.Object):
+    """Items answered wrongly across practice sessions, for later review."""
+    __gtype_name__ = 'Word2ipaMistakeList'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        # each item is {"word", "ipa", "lang", "count"}
+        self.items = storage.load_json(MISTAKES_FILE, [])
+
+    def __len__(self):
+        return len(self.items)
+
+    def __iter__(self):
+        return iter(self.items)
+
+    def add_session(self, session):
+        if not session.mistakes:
+            return
+
+        def change(items):
+            for attempt in session.mistakes:
+                for item in items:
+                    if item["word"] == attempt["word"] and item["lang"] == session.lang:
+                        item["count"] += 1
+                        break
+                else:
+                    items.append({
+                        "word": attempt["word"],
+                        "ipa": attempt["ipa"],
+                        "lang": session.lang,
+                        "count": 1,
+                    })
+            return items
+        self._save(change)
+
+    def remove(self, item):
+        self._save(lambda items: [other for other in items
+                                  if (other["word"], other["lang"]) != (item["word"], item["lang"])])
+
+    def clear(self):
+        self._save(lambda items: [])
+
+    def _save(self, change):
+        # applied to the file as it is now, keeping other instances' mistakes
+        self.items = storage.update_json(MISTAKES_FILE, [], change)
+        self.emit('changed')
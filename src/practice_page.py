@@ -0,0 +1,325 @@
+# practice_page.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Adw, GLib, Gtk
+from . import export, languages, speech
+from .dictation import Word2ipaDictationDialog
+from .favorites import Favorites
+from .minimal_pairs import Word2ipaMinimalPairsDialog
+from .practice import MistakeList, practice_words
+from .reading_quiz import Word2ipaReadingQuizDialog
+from .state import AppState
+from .symbol_quiz import Word2ipaSymbolQuizDialog
+from .typing_trainer import Word2ipaTypingTrainerDialog
+from .stats import Stats
+from .word_lists import WordLists
+
+
+def format_duration(seconds):
+    minutes, seconds = divmod(seconds, 60)
+    return f"{minutes}:{seconds:02d}"
+
+
+def present_summary(parent, summary):
+    """Show how a finished practice session went."""
+    accuracy = summary["correct"] / summary["total"] if summary["total"] else 0
+
+    status = Adw.StatusPage(icon_name="emblem-ok-symbolic",
+                            title=_("Session Complete"),
+                            description=_("{} of {} correct").format(summary["correct"], summary["total"]))
+    details = Gtk.ListBox(selection_mode=Gtk.SelectionMode.NONE, css_classes=["boxed-list"])
+    for title, value in [
+        (_("Accuracy"), f"{accuracy:.0%}"),
+        (_("Time"), format_duration(summary["duration"])),
+        (_("Hardest Sounds"), "  ".join(summary["hardest"]) or _("None")),
+    ]:
+        details.append(Adw.ActionRow(title=title, subtitle=value, css_classes=["property"]))
+    status.set_child(details)
+
+    toolbar = Adw.ToolbarView(content=status)
+    toolbar.add_top_bar(Adw.HeaderBar())
+    dialog = Adw.Dialog(title=_("Summary"), child=toolbar,
+                        content_width=360, content_height=480)
+    dialog.present(parent)
+
+
+def present_confusion_matrix(parent, confusions, size=10):
+    """Show a grid of expected sounds (rows) against typed sounds (columns)."""
+    totals = {}
+    for expected, row in confusions.items():
+        for given, count in row.items():
+            totals[expected] = totals.get(expected, 0) + count
+            totals[given] = totals.get(given, 0) + count
+    symbols = sorted(totals, key=totals.get, reverse=True)[:size]
+
+    grid = Gtk.Grid(row_spacing=6, column_spacing=12, halign=Gtk.Align.CENTER,
+                    margin_top=12, margin_bottom=12, margin_start=12, margin_end=12)
+    grid.attach(Gtk.Label(label=_("Expected ↓ Typed →"), css_classes=["dim-label", "caption"]), 0, 0, 1, 1)
+    for column, symbol in enumerate(symbols, 1):
+        grid.attach(Gtk.Label(label=symbol, css_classes=["heading"]), column, 0, 1, 1)
+    for row_index, expected in enumerate(symbols, 1):
+        grid.attach(Gtk.Label(label=expected, css_classes=["heading"]), 0, row_index, 1, 1)
+        for column, given in enumerate(symbols, 1):
+            count = confusions.get(expected, {}).get(given, 0)
+            if expected == given:
+                label = Gtk.Label(label="·", css_classes=["dim-label"])
+            elif count:
+                label = Gtk.Label(label=str(count), css_classes=["numeric", "error"])
+            else:
+                label = Gtk.Label(label="0", css_classes=["numeric", "dim-label"])
+            grid.attach(label, column, row_index, 1, 1)
+
+    toolbar = Adw.ToolbarView(content=Gtk.ScrolledWindow(child=grid, propagate_natural_width=True,
+                                                          propagate_natural_height=True))
+    toolbar.add_top_bar(Adw.HeaderBar())
+    dialog = Adw.Dialog(title=_("Confusion Matrix"), child=toolbar)
+    dialog.present(parent)
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/practice_page.ui')
+class Word2ipaPracticePage(Adw.Bin):
+    __gtype_name__ = 'Word2ipaPracticePage'
+    dictation_row = Gtk.Template.Child()
+    word_list_row = Gtk.Template.Child()
+    level_row = Gtk.Template.Child()
+    sessions_row = Gtk.Template.Child()
+    accuracy_row = Gtk.Template.Child()
+    trend_row = Gtk.Template.Child()
+    trend_label = Gtk.Template.Child()
+    trend_icon = Gtk.Template.Child()
+    confusion_matrix_button = Gtk.Template.Child()
+    confusions_list = Gtk.Template.Child()
+    mistakes_list = Gtk.Template.Child()
+    favorite_mistakes_button = Gtk.Template.Child()
+    export_mistakes_button = Gtk.Template.Child()
+    clear_mistakes_button = Gtk.Template.Child()
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        if not speech.can_speak_words():
+            self.dictation_row.set_sensitive(False)
+            self.dictation_row.set_subtitle(_("Needs espeak-ng or speech-dispatcher"))
+
+        self.mistakes = MistakeList.get_default()
+        self.mistakes_list.set_placeholder(
+            Gtk.Label(label=_("No mistakes recorded yet"), margin_top=12,
+                      margin_bottom=12, css_classes=["dim-label"]))
+        self.mistakes.connect("changed", lambda *_: self.fill_mistakes())
+        self.fill_mistakes()
+
+        self.confusions_list.set_placeholder(
+            Gtk.Label(label=_("No confused sounds yet"), margin_top=12,
+                      margin_bottom=12, css_classes=["dim-label"]))
+
+        self.stats = Stats.get_default()
+        self.stats.connect("changed", lambda *_: self.fill_stats())
+        self.fill_stats()
+
+        self.word_lists = WordLists.get_default()
+        self.word_lists.connect("changed", lambda *_: self.fill_word_lists())
+        AppState.get_default().connect("notify::lang", lambda *_: self.fill_word_lists())
+        self.fill_word_lists()
+        self.word_list_row.connect("notify::selected", self.on_word_list_selected)
+
+        # the levels are only known once the dictionary is loaded
+        AppState.get_default().connect("notify::dictionary", lambda *_: self.fill_levels())
+        self.fill_levels()
+        self.level_row.connect("notify::selected", self.on_level_selected)
+
+    def finish_session(self, session):
+        """Called by practice modes once a session is over."""
+        summary = session.finish()
+        if summary["total"]:
+            present_summary(self.get_root(), summary)
+
+    @property
+    def lang(self):
+        return AppState.get_default().lang
+
+    def loaded_dictionary(self):
+        """The dictionary of the current language, or None with a toast
+        while it is still loading or if it couldn't be.
+        """
+        dictionary = AppState.get_default().dictionary
+        if dictionary is None:
+            self.get_root().show_toast(_("The dictionary isn't loaded"))
+        return dictionary
+
+    def start_round(self, dialog_class, containing=None):
+        """Present a practice dialog with the words of a round, unless
+        no word matches the chosen list, level or sounds.
+        """
+        if (dictionary := self.loaded_dictionary()) is None:
+            return
+        words = practice_words(dictionary, self.lang, containing)
+        if not words:
+            self.get_root().show_toast(_("No words match the chosen list or level"))
+            return
+        dialog_class(self.lang, dictionary, words, self.finish_session).present(self.get_root())
+
+    @Gtk.Template.Callback()
+    def on_dictation_activated(self, row):
+        self.start_round(Word2ipaDictationDialog)
+
+    @Gtk.Template.Callback()
+    def on_typing_trainer_activated(self, row):
+        self.start_round(Word2ipaTypingTrainerDialog)
+
+    @Gtk.Template.Callback()
+    def on_reading_quiz_activated(self, row):
+        self.start_round(Word2ipaReadingQuizDialog)
+
+    @Gtk.Template.Callback()
+    def on_minimal_pairs_activated(self, row):
+        Word2ipaMinimalPairsDialog(self.lang).present(self.get_root())
+
+    @Gtk.Template.Callback()
+    def on_symbol_quiz_activated(self, row):
+        Word2ipaSymbolQuizDialog(self.finish_session).present(self.get_root())
+
+    def fill_word_lists(self):
+        # "" stands for the whole dictionary
+        self.word_list_ids = [""]
+        names = [_("Whole Dictionary")]
+        for list_id, name in self.word_lists.lists_for(self.lang):
+            self.word_list_ids.append(list_id)
+            names.append(name)
+        selected = self.word_lists.selected(self.lang)
+        self.filling_word_lists = True
+        self.word_list_row.set_model(Gtk.StringList.new(names))
+        self.word_list_row.set_selected(self.word_list_ids.index(selected)
+                                        if selected in self.word_list_ids else 0)
+        self.filling_word_lists = False
+
+    def on_word_list_selected(self, row, _pspec):
+        if self.filling_word_lists or row.get_selected() == Gtk.INVALID_LIST_POSITION:
+            return
+        self.word_lists.select(self.lang, self.word_list_ids[row.get_selected()])
+
+    def fill_levels(self):
+        dictionary = AppState.get_default().dictionary
+        levels = dictionary.levels() if dictionary is not None else []
+        self.level_row.set_visible(bool(levels))
+        # None stands for every level
+        self.level_values = [None] + levels
+        selected = self.word_lists.selected_level(self.lang)
+        self.filling_levels = True
+        self.level_row.set_model(Gtk.StringList.new([_("All Levels")] + levels))
+        self.level_row.set_selected(self.level_values.index(selected)
+                                    if selected in self.level_values else 0)
+        self.filling_levels = False
+
+    def on_level_selected(self, row, _pspec):
+        if self.filling_levels or row.get_selected() == Gtk.INVALID_LIST_POSITION:
+            return
+        self.word_lists.select_level(self.lang, self.level_values[row.get_selected()])
+
+    @Gtk.Template.Callback()
+    def on_import_word_list_clicked(self, button):
+        def on_path(path):
+            try:
+                list_id, unreadable = self.word_lists.import_file(path, self.lang)
+            except OSError as e:
+                print(f"could not read {path}: {e}")
+                self.get_root().show_toast(_("Could not read the word list"))
+                return
+            self.word_lists.select(self.lang, list_id)
+            self.fill_word_lists()
+            if unreadable:
+                self.get_root().show_toast(ngettext("{} character of the list could not be read",
+                                                    "{} characters of the list could not be read",
+                                                    unreadable).format(unreadable))
+
+        export.choose_open_path(self.get_root(), on_path,
+                                filters=[export.file_filter(_("Text File"), "*.txt")])
+
+    def fill_confusions(self):
+        self.confusions_list.remove_all()
+        for expected, given, count in self.stats.top_confusions():
+            row = Adw.ActionRow(
+                title=GLib.markup_escape_text(f"{expected} → {given}"),
+                subtitle=ngettext("Typed instead once", "Typed instead {} times", count).format(count))
+            drill_button = Gtk.Button(label=_("Drill"), valign=Gtk.Align.CENTER)
+            drill_button.connect("clicked", self.on_drill_clicked, [expected, given])
+            row.add_suffix(drill_button)
+            self.confusions_list.append(row)
+        self.confusion_matrix_button.set_sensitive(bool(self.stats.confusions))
+
+    def on_drill_clicked(self, button, sounds):
+        self.start_round(Word2ipaTypingTrainerDialog, containing=sounds)
+
+    @Gtk.Template.Callback()
+    def on_confusion_matrix_clicked(self, button):
+        present_confusion_matrix(self.get_root(), self.stats.confusions)
+
+    def fill_stats(self):
+        self.fill_confusions()
+        self.sessions_row.set_subtitle(str(len(self.stats.sessions)))
+        accuracy = self.stats.average_accuracy()
+        self.accuracy_row.set_subtitle(f"{accuracy:.0%}" if accuracy is not None else "—")
+
+        trend = self.stats.trend()
+        if trend is None:
+            self.trend_row.set_visible(False)
+            return
+        self.trend_row.set_visible(True)
+        if trend >= 0:
+            icon, tooltip = "go-up-symbolic", _("Improving")
+        else:
+            icon, tooltip = "go-down-symbolic", _("Declining")
+        self.trend_label.set_label(f"{trend:+.0%}")
+        self.trend_icon.set_from_icon_name(icon)
+        self.trend_icon.set_tooltip_text(tooltip)
+
+    def fill_mistakes(self):
+        self.mistakes_list.remove_all()
+        for item in self.mistakes:
+            row = Adw.ActionRow(title=item["word"], subtitle=item["ipa"], activatable=True,
+                                action_name="win.look-up",
+                                action_target=GLib.Variant("(ss)", (item["word"], item["lang"])))
+            row.add_suffix(Gtk.Label(label=languages.display_name(item["lang"]),
+                                     css_classes=["dim-label"]))
+
+            remove_button = Gtk.Button(icon_name="object-select-symbolic",
+                                       tooltip_text=_("Mark as Learned"),
+                                       valign=Gtk.Align.CENTER,
+                                       css_classes=["flat"])
+            remove_button.connect("clicked", lambda _b, i=item: self.mistakes.remove(i))
+            row.add_suffix(remove_button)
+
+            self.mistakes_list.append(row)
+
+        self.favorite_mistakes_button.set_sensitive(len(self.mistakes) > 0)
+        self.export_mistakes_button.set_sensitive(len(self.mistakes) > 0)
+        self.clear_mistakes_button.set_sensitive(len(self.mistakes) > 0)
+
+    @Gtk.Template.Callback()
+    def on_favorite_mistakes_clicked(self, button):
+        favorites = Favorites.get_default()
+        for item in self.mistakes:
+            favorites.add(item["word"], item["ipa"], item["lang"])
+
+    @Gtk.Template.Callback()
+    def on_export_mistakes_clicked(self, button):
+        export.choose_save_path(self.get_root(), "mistakes.txt",
+                                lambda path: export.write_anki_tsv(path, self.mistakes))
+
+    @Gtk.Template.Callback()
+    def on_clear_mistakes_clicked(self, button):
+        self.mistakes.clear()
@@ -0,0 +1,858 @@
+# preferences.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+import datetime
+import os
+import re
+
+from gi.repository import Adw, Gio, GLib, Gtk, Pango
+from . import backups, content_filter, export, features, forvo, langpacks, languages
+from .accent_comparison import accent_label, accent_languages
+from .conlang import Word2ipaConlangDialog
+from .csv_import import Word2ipaCsvImportDialog
+from .core import DictionaryError, find_duplicates, find_replace, notation, orthography, phonemes
+from .favorites import Favorites
+from .history import History
+from .ipa_keyboard import Word2ipaIpaKeyboard, Word2ipaIpaKeyboardButton
+from .langpacks import LanguagePacks
+from .online import OnlineResults
+from .pack_changes import present_changes
+from .userdict import KEEP_DELETED_DAYS, SOURCE_MANUAL, SOURCE_ONLINE, UserDictionary, UserEntry
+
+# choices of history_limit_row, 0 is unlimited
+HISTORY_LIMITS = [50, 200, 1000, 0]
+# differences listed before asking to restore a backup
+PREVIEW_LINES = 15
+# duplicates an entry being added is warned about
+MAX_DUPLICATES = 3
+
+
+def present_entry_dialog(parent, lang, word="", ipa="", source=None):
+    """Ask for a word and its IPA and store them in lang's user dictionary,
+    as coming from source if given, see UserDictionary.set().
+    """
+    dialog = Adw.AlertDialog(heading=_("Edit Entry") if word else _("Add Entry"),
+                             body=languages.display_name(lang))
+    dialog.add_response("cancel", _("_Cancel"))
+    dialog.add_response("merge", _("_Merge"))
+    dialog.set_response_enabled("merge", False)
+    dialog.add_response("save", _("_Save"))
+    dialog.set_response_appearance("save", Adw.ResponseAppearance.SUGGESTED)
+    dialog.set_default_response("save")
+    dialog.set_close_response("cancel")
+
+    fields = Gtk.ListBox(selection_mode=Gtk.SelectionMode.NONE)
+    fields.add_css_class("boxed-list")
+    word_row = Adw.EntryRow(title=_("Word"), text=word)
+    ipa_row = Adw.EntryRow(title=_("IPA"), text=ipa)
+    fields.append(word_row)
+    fields.append(ipa_row)
+    keyboard = Gtk.Revealer(child=Word2ipaIpaKeyboard(target=ipa_row, margin_top=12))
+    ipa_row.add_suffix(Word2ipaIpaKeyboardButton(revealer=keyboard))
+    content = Gtk.Box(orientation=Gtk.Orientation.VERTICAL)
+    content.append(fields)
+    content.append(keyboard)
+    dialog.set_extra_child(content)
+
+    # constructed languages only use the sounds they were made with
+    inventory = LanguagePacks.get_default().inventory(lang)
+    if inventory is not None:
+        warning = Gtk.Label(wrap=True, xalign=0, margin_top=12, visible=False, css_classes=["warning"])
+        content.append(warning)
+
+        def on_ipa_changed(row):
+            outside = phonemes.outside_inventory(row.get_text(), inventory)
+            warning.set_label(_("Not a sound of this language: {}").format(" ".join(outside)))
+            warning.set_visible(bool(outside))
+        ipa_row.connect("changed", on_ipa_changed)
+        on_ipa_changed(ipa_row)
+
+    # with spelling rules the IPA follows the word until it is edited
+    user_dict = UserDictionary.get_default()
+    generated = None
+
+    def on_word_changed(row):
+        nonlocal generated
+        spelled = user_dict.spell(lang, row.get_text().strip())
+        if spelled is None or ipa_row.get_text() not in ("", generated):
+            return
+        generated = spelled[0] if not spelled[1] and row.get_text().strip() else ""
+        ipa_row.set_text(generated)
+    word_row.connect("changed", on_word_changed)
+
+    # the same word twice, or a word sounding like another, is often a slip
+    duplicate_label = Gtk.Label(wrap=True, xalign=0, margin_top=12, visible=False, css_classes=["warning"])
+    content.append(duplicate_label)
+    duplicates = []
+
+    def on_entry_changed(row):
+        nonlocal duplicates
+        duplicates = find_duplicates(word_row.get_text(), ipa_row.get_text(), user_dict.entries(lang),
+                                     editing=word or None)
+        messages = {
+            "word": _("Already in the dictionary as {ipa}, saving replaces it"),
+            "case": _("Also in the dictionary as “{word}” {ipa}"),
+            "ipa": _("“{word}” is pronounced the same"),
+        }
+        duplicate_label.set_label("\n".join(messages[kind].format(word=other, ipa=other_ipa)
+                                             for other, other_ipa, kind in duplicates[:MAX_DUPLICATES]))
+        duplicate_label.set_visible(bool(duplicates))
+        # only entries of the same word can become one
+        dialog.set_response_enabled("merge", bool(duplicates) and duplicates[0][2] != "ipa")
+    word_row.connect("changed", on_entry_changed)
+    ipa_row.connect("changed", on_entry_changed)
+    on_entry_changed(word_row)
+
+    def on_response(dialog, response):
+        new_word = word_row.get_text().strip()
+        new_ipa = ipa_row.get_text().strip()
+        if response == "save" and new_word and new_ipa:
+            user_dict.set(lang, new_word, new_ipa, old_word=word or None, source=source)
+        elif response == "merge" and new_ipa:
+            # the variants of both are kept under the word already there
+            other, other_ipa, _kind = duplicates[0]
+            user_dict.set(lang, other, phonemes.merge_variants(other_ipa, new_ipa), old_word=word or None)
+
+    dialog.connect("response", on_response)
+    dialog.present(parent)
+
+
+def describe_differences(differences):
+    """Lines telling what restoring a backup changes, for previewing it."""
+    kinds = {"entry": _("Entry"), "favorite": _("Favorite"), "rule": _("Spelling Rule")}
+    lines = []
+    for kind, lang, word, ipa in differences["added"]:
+        lines.append(_("+ {kind} {word} {ipa} ({lang})").format(kind=kinds[kind], word=word, ipa=ipa, lang=lang))
+    for kind, lang, word, ipa in differences["removed"]:
+        lines.append(_("− {kind} {word} {ipa} ({lang})").format(kind=kinds[kind], word=word, ipa=ipa, lang=lang))
+    for kind, lang, word, old, new in differences["changed"]:
+        lines.append(_("~ {kind} {word} {old} → {new} ({lang})").format(
+            kind=kinds[kind], word=word, old=old, new=new, lang=lang))
+    return lines
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/preferences.ui')
+class Word2ipaPreferencesDialog(Adw.PreferencesDialog):
+    __gtype_name__ = 'Word2ipaPreferencesDialog'
+    weekly_summary_row = Gtk.Template.Child()
+    dictionary_examples_row = Gtk.Template.Child()
+    notation_row = Gtk.Template.Child()
+    optional_sounds_row = Gtk.Template.Child()
+    normalization_row = Gtk.Template.Child()
+    history_limit_row = Gtk.Template.Child()
+    syllable_dots_row = Gtk.Template.Child()
+    morpheme_boundaries_row = Gtk.Template.Child()
+    respelling_row = Gtk.Template.Child()
+    wiktionary_url_row = Gtk.Template.Child()
+    forvo_url_row = Gtk.Template.Child()
+    forvo_audio_row = Gtk.Template.Child()
+    online_fallback_row = Gtk.Template.Child()
+    clear_online_row = Gtk.Template.Child()
+    forvo_key_row = Gtk.Template.Child()
+    accents_group = Gtk.Template.Child()
+    experimental_group = Gtk.Template.Child()
+    content_filter_row = Gtk.Template.Child()
+    filtered_words_row = Gtk.Template.Child()
+    filtered_word_entry = Gtk.Template.Child()
+    user_dict_group = Gtk.Template.Child()
+    user_dict_lang = Gtk.Template.Child()
+    user_dict_view = Gtk.Template.Child()
+    user_dict_scrolled = Gtk.Template.Child()
+    user_dict_empty_label = Gtk.Template.Child()
+    delete_selected_button = Gtk.Template.Child()
+    review_group = Gtk.Template.Child()
+    review_list = Gtk.Template.Child()
+    languages_page = Gtk.Template.Child()
+    language_packs_spinner = Gtk.Template.Child()
+    language_packs_list = Gtk.Template.Child()
+    conlang_list = Gtk.Template.Child()
+
+    def __init__(self, lang="en_US", **kwargs):
+        super().__init__(**kwargs)
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        self.settings.bind('weekly-summary', self.weekly_summary_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('dictionary-examples', self.dictionary_examples_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('syllable-dots', self.syllable_dots_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('morpheme-boundaries', self.morpheme_boundaries_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('respelling', self.respelling_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('wiktionary-url', self.wiktionary_url_row, 'text',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('forvo-url', self.forvo_url_row, 'text',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('forvo-audio', self.forvo_audio_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.settings.bind('online-fallback', self.online_fallback_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        self.online_results = OnlineResults.get_default()
+        self.clear_online_row.set_sensitive(self.online_results.count() > 0)
+        self.forvo_key_row.set_text(forvo.get_key() or "")
+        self.forvo_key_row.set_sensitive(forvo.has_keyring())
+
+        # transcription standards offered for the current language, "" keeps the dictionary's
+        self.notation_lang = lang
+        self.notation_profiles = [""] + notation.profiles_for(lang)
+        self.notation_row.set_model(Gtk.StringList.new(
+            [_("As in Dictionary")] + [notation.PROFILES[p]["name"] for p in self.notation_profiles[1:]]))
+        self.notation_row.set_subtitle(languages.display_name(lang))
+        current = self.settings.get_value('transcription-profiles').unpack().get(lang, "")
+        if current in self.notation_profiles:
+            self.notation_row.set_selected(self.notation_profiles.index(current))
+        self.notation_row.set_visible(len(self.notation_profiles) > 1)
+        self.notation_row.connect("notify::selected", self.on_notation_changed)
+
+        self.optional_sounds_row.set_selected(
+            notation.OPTIONAL_MODES.index(self.settings.get_string('optional-sounds')))
+        self.optional_sounds_row.connect("notify::selected", self.on_optional_sounds_changed)
+        self.normalization_row.set_selected(
+            notation.NORMALIZATION_FORMS.index(self.settings.get_string('unicode-normalization')))
+        self.normalization_row.connect("notify::selected", self.on_normalization_changed)
+
+        limit = self.settings.get_int('history-limit')
+        self.history_limit_row.set_selected(HISTORY_LIMITS.index(limit) if limit in HISTORY_LIMITS
+                                            else HISTORY_LIMITS.index(0))
+        self.history_limit_row.connect("notify::selected", self.on_history_limit_changed)
+
+        self.settings.bind('content-filter', self.content_filter_row, 'active',
+                           Gio.SettingsBindFlags.DEFAULT)
+        # the user's own additions to the filter of the current language
+        self.filter_lang = lang
+        self.filtered_word_rows = []
+        self.filtered_words_row.set_subtitle(languages.display_name(lang))
+        self.fill_filtered_words()
+
+        for code in accent_languages():
+            row = Adw.SwitchRow(title=accent_label(code), subtitle=languages.display_name(code),
+                                active=code in self.settings.get_strv('accents'))
+            row.connect("notify::active", self.on_accent_toggled, code)
+            self.accents_group.add(row)
+
+        self.experimental_group.set_visible(self.settings.get_boolean('show-experimental')
+                                            and bool(features.FEATURES))
+        for name, (title, description) in features.FEATURES.items():
+            row = Adw.SwitchRow(title=title, subtitle=description, active=features.is_enabled(name))
+            row.connect("notify::active", lambda row, _pspec, n=name: features.set_enabled(n, row.get_active()))
+            self.experimental_group.add(row)
+
+        self.user_dict = UserDictionary.get_default()
+
+        self.fill_user_dict_langs(lang)
+
+        # show how many entries each language has in the dropdown
+        factory = Gtk.SignalListItemFactory()
+        factory.connect("setup", self.on_lang_item_setup)
+        factory.connect("bind", self.on_lang_item_bind)
+        self.user_dict_lang.set_list_factory(factory)
+
+        self.user_dict_store = Gio.ListStore(item_type=UserEntry)
+        sorted_entries = Gtk.SortListModel(model=self.user_dict_store, sorter=self.user_dict_view.get_sorter())
+        self.user_dict_selection = Gtk.MultiSelection(model=sorted_entries)
+        self.user_dict_selection.connect("selection-changed", self.on_user_dict_selection_changed)
+        self.user_dict_view.set_model(self.user_dict_selection)
+        for title, prop in [(_("Word"), "word"), (_("IPA"), "ipa"), (_("Variants"), "variants"),
+                            (_("Tags"), "tags"), (_("Source"), "source"), (_("Modified"), "modified")]:
+            factory = Gtk.SignalListItemFactory()
+            factory.connect("setup", self.on_entry_cell_setup, prop)
+            factory.connect("bind", self.on_entry_cell_bind, prop)
+            # ISO 8601 times sort in order as text
+            sorter = Gtk.StringSorter(expression=Gtk.PropertyExpression.new(UserEntry, None, prop))
+            column = Gtk.ColumnViewColumn(title=title, factory=factory, sorter=sorter, resizable=True,
+                                          expand=prop in ("word", "ipa"))
+            self.user_dict_view.append_column(column)
+        self.user_dict_view.sort_by_column(self.user_dict_view.get_columns().get_item(0), Gtk.SortType.ASCENDING)
+        self._changed_id = self.user_dict.connect("changed", self.on_user_dict_changed)
+        self.connect("closed", lambda *_: self.user_dict.disconnect(self._changed_id))
+        self.fill_user_dict()
+
+        self.packs = LanguagePacks.get_default()
+        self.pack_index = None
+        self.connect("notify::visible-page", self.on_visible_page_changed)
+        self._packs_changed_id = self.packs.connect("changed", self.on_packs_changed)
+        self.connect("closed", lambda *_: self.packs.disconnect(self._packs_changed_id))
+        self.fill_conlangs()
+
+    def on_user_dict_changed(self, user_dict):
+        self.fill_user_dict()
+        # the word counts of constructed languages
+        self.fill_conlangs()
+
+    def fill_user_dict_langs(self, lang):
+        codes = [code for code, _name in languages.available()]
+        self.user_dict_lang.set_model(Gtk.StringList.new(languages.display_names()))
+        if lang in codes:
+            self.user_dict_lang.set_selected(codes.index(lang))
+
+    def on_packs_changed(self, packs):
+        self.fill_user_dict_langs(self.selected_lang)
+        self.fill_conlangs()
+
+    def fill_conlangs(self):
+        self.conlang_list.remove_all()
+        conlangs = self.packs.conlangs()
+        for code, name in conlangs:
+            inventory = self.packs.inventory(code) or []
+            count = self.user_dict.count(code)
+            row = Adw.ActionRow(title=name, subtitle=" · ".join([
+                ngettext("{} sound", "{} sounds", len(inventory)).format(len(inventory)),
+                ngettext("{} word", "{} words", count).format(count)]))
+            button = Gtk.Button(icon_name="user-trash-symbolic",
+                                tooltip_text=_("Remove"),
+                                valign=Gtk.Align.CENTER,
+                                css_classes=["flat"])
+            button.connect("clicked", self.on_remove_conlang_clicked, code, name)
+            row.add_suffix(button)
+            self.conlang_list.append(row)
+        self.conlang_list.set_visible(bool(conlangs))
+
+    @Gtk.Template.Callback()
+    def on_new_conlang_activated(self, row):
+        def on_created(code):
+            # its words are added as user dictionary entries
+            self.fill_user_dict_langs(code)
+            self.add_toast(Adw.Toast(title=_("Add its words in the User Dictionary page")))
+        Word2ipaConlangDialog(on_created).present(self)
+
+    def on_remove_conlang_clicked(self, button, code, name):
+        dialog = Adw.AlertDialog(heading=_("Remove “{}”?").format(name),
+                                 body=_("Its words stay in the user dictionary and can be exported first"))
+        dialog.add_response("cancel", _("_Cancel"))
+        dialog.add_response("remove", _("_Remove"))
+        dialog.set_response_appearance("remove", Adw.ResponseAppearance.DESTRUCTIVE)
+        dialog.set_close_response("cancel")
+
+        def on_response(dialog, response):
+            if response == "remove":
+                self.packs.remove(code)
+
+        dialog.connect("response", on_response)
+        dialog.present(self)
+
+    @Gtk.Template.Callback()
+    def on_import_entries_activated(self, row):
+        lang = self.selected_lang
+
+        def on_imported(count):
+            self.add_toast(Adw.Toast(title=ngettext("{} entry imported", "{} entries imported",
+                                                    count).format(count)))
+
+        def on_path(path):
+            try:
+                with open(path, 'rb') as f:
+                    data = f.read()
+            except OSError as e:
+                print(f"could not read {path}: {e}")
+                self.add_toast(Adw.Toast(title=_("Could not read the file")))
+                return
+            name = os.path.basename(path)
+            if not path.lower().endswith('.json'):
+                Word2ipaCsvImportDialog(data, name, lang, on_imported).present(self)
+                return
+            # exported by Export Entries, with the sources and times of the entries
+            try:
+                on_imported(self.user_dict.import_dictionary(lang, data, name))
+            except DictionaryError as e:
+                print(f"could not import {path}: {e}")
+                self.add_toast(Adw.Toast(title=_("Not a dictionary")))
+
+        export.choose_open_path(self, on_path,
+                                filters=[export.file_filter(_("Spreadsheet or Dictionary"),
+                                                            "*.csv", "*.tsv", "*.txt", "*.json")])
+
+    @Gtk.Template.Callback()
+    def on_export_entries_activated(self, row):
+        lang = self.selected_lang
+        metadata = {}
+        if (inventory := self.packs.inventory(lang)) is not None:
+            # a constructed language is shared with the sounds it uses
+            metadata = {"conlang": self.packs.installed[lang], "inventory": " ".join(inventory)}
+
+        def on_path(path):
+            try:
+                entries = self.user_dict.entries(lang)
+                entry_info = {word: {key: value for key, value in self.user_dict.info(lang, word).items()
+                                     if key in ("source", "modified", "tags")} for word, _ipa in entries}
+                export.write_dictionary(path, lang, entries, metadata,
+                                        {word: info for word, info in entry_info.items() if info})
+            except OSError as e:
+                print(f"could not export {lang} entries: {e}")
+                self.add_toast(Adw.Toast(title=_("Could not export the entries")))
+
+        export.choose_save_path(self, f"{lang}.json", on_path,
+                                filters=[export.file_filter(_("Dictionary"), "*.json"),
+                                         export.file_filter(_("Tab Separated"), "*.tsv", "*.txt")])
+
+    @property
+    def selected_lang(self):
+        return languages.parse_code(self.user_dict_lang.get_selected_item().get_string())
+
+    def on_lang_item_setup(self, factory, list_item):
+        box = Gtk.Box(spacing=12)
+        box.append(Gtk.Label(xalign=0, hexpand=True))
+        box.append(Gtk.Label(css_classes=["dim-label", "numeric"]))
+        list_item.set_child(box)
+
+    def on_lang_item_bind(self, factory, list_item):
+        label = list_item.get_item().get_string()
+        name_label = list_item.get_child().get_first_child()
+        count_label = name_label.get_next_sibling()
+        name_label.set_label(label)
+        count = self.user_dict.count(languages.parse_code(label))
+        count_label.set_label(str(count) if count else "")
+
+    def fill_user_dict(self):
+        lang = self.selected_lang
+        count = self.user_dict.count(lang)
+        self.user_dict_group.set_description(
+            ngettext("{} entry", "{} entries", count).format(count))
+        entries = [UserEntry(word, ipa, self.user_dict.info(lang, word)) for word, ipa in self.user_dict.entries(lang)]
+        self.user_dict_store.splice(0, self.user_dict_store.get_n_items(), entries)
+        self.user_dict_scrolled.set_visible(bool(entries))
+        self.user_dict_empty_label.set_visible(not entries)
+        self.delete_selected_button.set_sensitive(False)
+        self.fill_review()
+
+    def fill_review(self):
+        lang = self.selected_lang
+        to_review = self.user_dict.to_review(lang)
+        self.review_list.remove_all()
+        for word, ipa, unknown in to_review:
+            row = Adw.ActionRow(title=word, subtitle=_("{ipa} · Not IPA: {symbols}").format(
+                ipa=ipa, symbols=" ".join(unknown)), use_markup=False)
+
+            edit_button = Gtk.Button(icon_name="document-edit-symbolic",
+                                     tooltip_text=_("Edit Entry"),
+                                     valign=Gtk.Align.CENTER,
+                                     css_classes=["flat"])
+            edit_button.connect("clicked", lambda _b, w=word, i=ipa: present_entry_dialog(self, lang, w, i))
+            row.add_suffix(edit_button)
+
+            reviewed_button = Gtk.Button(icon_name="object-select-symbolic",
+                                         tooltip_text=_("Mark as Reviewed"),
+                                         valign=Gtk.Align.CENTER,
+                                         css_classes=["flat"])
+            reviewed_button.connect("clicked", lambda _b, w=word: self.user_dict.mark_reviewed(lang, w))
+            row.add_suffix(reviewed_button)
+            self.review_list.append(row)
+        self.review_group.set_visible(bool(to_review))
+
+    def on_entry_cell_setup(self, factory, list_item, prop):
+        if prop in ("source", "modified"):
+            # kept by the app, not edited
+            list_item.set_child(Gtk.Label(xalign=0, ellipsize=Pango.EllipsizeMode.END, css_classes=["dim-label"]))
+            return
+        label = Gtk.EditableLabel()
+        label.connect("notify::editing", self.on_entry_cell_edited, list_item, prop)
+        list_item.set_child(label)
+
+    def on_entry_cell_bind(self, factory, list_item, prop):
+        entry = list_item.get_item()
+        if prop == "source":
+            text = {SOURCE_MANUAL: _("Typed In"), SOURCE_ONLINE: _("Wiktionary")}.get(entry.source, entry.source)
+        elif prop == "modified":
+            text = datetime.datetime.fromisoformat(entry.modified).strftime("%x") if entry.modified else ""
+        else:
+            text = entry.get_property(prop)
+        list_item.get_child().set_text(text)
+
+    def on_entry_cell_edited(self, label, pspec, list_item, prop):
+        entry = list_item.get_item()
+        if label.get_editing() or entry is None:
+            return
+        text = label.get_text().strip()
+        if text == entry.get_property(prop):
+            return
+        lang = self.selected_lang
+        if prop == "word":
+            if not text or self.user_dict.lookup(lang, text) is not None:
+                # no empty words, and another entry isn't overwritten
+                label.set_text(entry.word)
+                return
+            self.user_dict.set(lang, text, entry.joined(), old_word=entry.word)
+        elif prop == "ipa":
+            if not text:
+                label.set_text(entry.ipa)
+                return
+            self.user_dict.set(lang, entry.word, entry.joined(ipa=text))
+        elif prop == "tags":
+            self.user_dict.set_tags(lang, entry.word, [tag.strip() for tag in text.split(",") if tag.strip()])
+        else:
+            self.user_dict.set(lang, entry.word, entry.joined(variants=text))
+
+    def on_user_dict_selection_changed(self, selection, position, n_items):
+        self.delete_selected_button.set_sensitive(not selection.get_selection().is_empty())
+
+    def on_notation_changed(self, row, pspec):
+        profiles = self.settings.get_value('transcription-profiles').unpack()
+        profiles[self.notation_lang] = self.notation_profiles[row.get_selected()]
+        self.settings.set_value('transcription-profiles', GLib.Variant('a{ss}', profiles))
+
+    def on_optional_sounds_changed(self, row, pspec):
+        self.settings.set_string('optional-sounds', notation.OPTIONAL_MODES[row.get_selected()])
+
+    def on_normalization_changed(self, row, pspec):
+        self.settings.set_string('unicode-normalization', notation.NORMALIZATION_FORMS[row.get_selected()])
+
+    def on_history_limit_changed(self, row, pspec):
+        self.settings.set_int('history-limit', HISTORY_LIMITS[row.get_selected()])
+
+    def fill_filtered_words(self):
+        for row in self.filtered_word_rows:
+            self.filtered_words_row.remove(row)
+        self.filtered_word_rows = []
+        for word in content_filter.user_words(self.settings, self.filter_lang):
+            row = Adw.ActionRow(title=word, use_markup=False)
+            delete_button = Gtk.Button(icon_name="user-trash-symbolic",
+                                       tooltip_text=_("Remove Word"),
+                                       valign=Gtk.Align.CENTER,
+                                       css_classes=["flat"])
+            delete_button.connect("clicked", self.on_remove_filtered_word_clicked, word)
+            row.add_suffix(delete_button)
+            self.filtered_words_row.add_row(row)
+            self.filtered_word_rows.append(row)
+
+    @Gtk.Template.Callback()
+    def on_forvo_key_apply(self, entry):
+        forvo.set_key(entry.get_text().strip())
+
+    @Gtk.Template.Callback()
+    def on_filtered_word_apply(self, entry):
+        word = entry.get_text().strip().lower()
+        if not word:
+            return
+        words = content_filter.user_words(self.settings, self.filter_lang)
+        content_filter.set_user_words(self.settings, self.filter_lang, words + [word])
+        entry.set_text("")
+        self.fill_filtered_words()
+
+    def on_remove_filtered_word_clicked(self, button, word):
+        words = content_filter.user_words(self.settings, self.filter_lang)
+        content_filter.set_user_words(self.settings, self.filter_lang, [w for w in words if w != word])
+        self.fill_filtered_words()
+
+    def on_accent_toggled(self, row, pspec, code):
+        accents = [lang for lang in self.settings.get_strv('accents') if lang != code]
+        if row.get_active():
+            accents.append(code)
+        self.settings.set_strv('accents', accents)
+
+    @Gtk.Template.Callback()
+    def on_user_dict_lang_changed(self, combo_row, pspec):
+        self.fill_user_dict()
+
+    @Gtk.Template.Callback()
+    def on_add_clicked(self, button):
+        present_entry_dialog(self, self.selected_lang)
+
+    @Gtk.Template.Callback()
+    def on_delete_selected_clicked(self, button):
+        lang = self.selected_lang
+        selected = self.user_dict_selection.get_selection()
+        words = [self.user_dict_selection.get_item(selected.get_nth(i)).word for i in range(selected.get_size())]
+        self.user_dict.remove_many(lang, words)
+        if len(words) == 1:
+            title = _("“{}” deleted").format(words[0])
+        else:
+            title = ngettext("{} entry deleted", "{} entries deleted", len(words)).format(len(words))
+        toast = Adw.Toast(title=title, button_label=_("_Undo"))
+        toast.connect("button-clicked", lambda _t: self.user_dict.restore_many(lang, words))
+        self.add_toast(toast)
+
+    @Gtk.Template.Callback()
+    def on_clear_online_activated(self, row):
+        count = self.online_results.count()
+        self.online_results.clear()
+        row.set_sensitive(False)
+        self.add_toast(Adw.Toast(title=ngettext("{} online result removed", "{} online results removed",
+                                                count).format(count)))
+
+    @Gtk.Template.Callback()
+    def on_spelling_rules_activated(self, row):
+        lang = self.selected_lang
+        inventory = self.packs.inventory(lang)
+        rules_view = Gtk.TextView(monospace=True, wrap_mode=Gtk.WrapMode.WORD_CHAR, top_margin=6, bottom_margin=6,
+                                  left_margin=6, right_margin=6, height_request=200)
+        rules_view.get_buffer().set_text(self.user_dict.spelling_rules.get(lang, ""))
+        status = Gtk.Label(wrap=True, xalign=0, margin_top=6, visible=False)
+        rules_group = Adw.PreferencesGroup(
+            title=languages.display_name(lang),
+            description=_("One rule per line, like “sh = ʃ”. The IPA of new entries is then filled in from "
+                         "their spelling, the longest letters matching first. Use ∅ for silent letters."))
+        rules_group.add(Gtk.Frame(child=rules_view))
+        rules_group.add(status)
+
+        try_row = Adw.EntryRow(title=_("Try a Word"))
+        try_result = Gtk.Label(selectable=True, css_classes=["dim-label"])
+        try_row.add_suffix(try_result)
+        try_group = Adw.PreferencesGroup()
+        try_group.add(try_row)
+
+        def update():
+            try:
+                rules = orthography.parse_rules(rules_view.get_buffer().get_property("text"))
+            except orthography.RuleError as e:
+                status.set_label(_("Line {}: rules are written as letters = sounds").format(e.line))
+                status.set_css_classes(["error"])
+                status.set_visible(True)
+                return
+            text = rules_view.get_buffer().get_property("text")
+            if text != self.user_dict.spelling_rules.get(lang, ""):
+                self.user_dict.set_spelling_rules(lang, text)
+            # the rules of a constructed language may only use its sounds
+            outside = phonemes.outside_inventory("".join(rules.values()), inventory) if inventory else []
+            status.set_label(_("Not a sound of this language: {}").format(" ".join(outside)))
+            status.set_css_classes(["warning"])
+            status.set_visible(bool(outside))
+            ipa, unknown = orthography.transcribe(try_row.get_text(), rules)
+            if unknown:
+                ipa = _("No rule for: {}").format(" ".join(unknown))
+            try_result.set_label(ipa if try_row.get_text().strip() else "")
+
+        rules_view.get_buffer().connect("changed", lambda _b: update())
+        try_row.connect("changed", lambda _r: update())
+        update()
+        page = Adw.PreferencesPage()
+        page.add(rules_group)
+        page.add(try_group)
+        toolbar = Adw.ToolbarView(content=page)
+        toolbar.add_top_bar(Adw.HeaderBar())
+        self.push_subpage(Adw.NavigationPage(title=_("Spelling Rules"), child=toolbar))
+
+    @Gtk.Template.Callback()
+    def on_find_replace_activated(self, row):
+        lang = self.selected_lang
+        find_row = Adw.EntryRow(title=_("Find"))
+        replace_row = Adw.EntryRow(title=_("Replace With"))
+        regex_row = Adw.SwitchRow(title=_("Regular Expression"),
+                                  subtitle=_("Groups can be reused in the replacement, like \\1"))
+        fields_group = Adw.PreferencesGroup(title=languages.display_name(lang),
+                                            description=_("Changes the IPA of every matching entry"))
+        fields_group.add(find_row)
+        fields_group.add(replace_row)
+        fields_group.add(regex_row)
+
+        preview_list = Gtk.ListBox(selection_mode=Gtk.SelectionMode.NONE, css_classes=["boxed-list"])
+        replace_button = Gtk.Button(label=_("_Replace All"), use_underline=True, valign=Gtk.Align.CENTER,
+                                    sensitive=False, css_classes=["destructive-action"])
+        preview_group = Adw.PreferencesGroup(header_suffix=replace_button)
+        preview_group.add(preview_list)
+        changes = []
+
+        def update():
+            nonlocal changes
+            preview_list.remove_all()
+            try:
+                changes = find_replace(self.user_dict.entries(lang), find_row.get_text(), replace_row.get_text(),
+                                       regex=regex_row.get_active())
+            except re.error as e:
+                changes = []
+                preview_group.set_title(_("Not a valid regular expression: {}").format(e.msg))
+            else:
+                preview_group.set_title(ngettext("{} entry changes", "{} entries change",
+                                                 len(changes)).format(len(changes)))
+            preview_group.set_visible(bool(find_row.get_text()))
+            replace_button.set_sensitive(bool(changes))
+            for word, old, new in changes[:PREVIEW_LINES]:
+                preview_list.append(Adw.ActionRow(title=word, subtitle=f"{old} → {new}", use_markup=False,
+                                                  css_classes=["property"]))
+            if len(changes) > PREVIEW_LINES:
+                more = len(changes) - PREVIEW_LINES
+                preview_list.append(Adw.ActionRow(title=ngettext("{} more", "{} more", more).format(more)))
+
+        def on_replace_clicked(button):
+            replaced = list(changes)
+            self.user_dict.set_many(lang, {word: new for word, _old, new in replaced})
+            self.pop_subpage()
+            toast = Adw.Toast(title=ngettext("{} entry changed", "{} entries changed",
+                                             len(replaced)).format(len(replaced)), button_label=_("_Undo"))
+            toast.connect("button-clicked",
+                          lambda _t: self.user_dict.set_many(lang, {word: old for word, old, _new in replaced}))
+            self.add_toast(toast)
+
+        find_row.connect("changed", lambda _r: update())
+        replace_row.connect("changed", lambda _r: update())
+        regex_row.connect("notify::active", lambda _r, _p: update())
+        replace_button.connect("clicked", on_replace_clicked)
+        update()
+        page = Adw.PreferencesPage()
+        page.add(fields_group)
+        page.add(preview_group)
+        toolbar = Adw.ToolbarView(content=page)
+        toolbar.add_top_bar(Adw.HeaderBar())
+        self.push_subpage(Adw.NavigationPage(title=_("Find and Replace"), child=toolbar))
+
+    @Gtk.Template.Callback()
+    def on_recently_deleted_activated(self, row):
+        lang = self.selected_lang
+        group = Adw.PreferencesGroup(
+            description=ngettext("Deleted entries are kept for {} day", "Deleted entries are kept for {} days",
+                                 KEEP_DELETED_DAYS).format(KEEP_DELETED_DAYS))
+        deleted_list = Gtk.ListBox(selection_mode=Gtk.SelectionMode.NONE, css_classes=["boxed-list"])
+        deleted_list.set_placeholder(Gtk.Label(label=_("No recently deleted entries"), margin_top=12,
+                                               margin_bottom=12, css_classes=["dim-label"]))
+        for word, ipa, date in self.user_dict.deleted_entries(lang):
+            deleted_row = Adw.ActionRow(title=word, subtitle=f"{ipa} · {date.strftime('%x')}")
+            restore_button = Gtk.Button(icon_name="edit-undo-symbolic",
+                                        tooltip_text=_("Restore Entry"),
+                                        valign=Gtk.Align.CENTER,
+                                        css_classes=["flat"])
+            restore_button.connect("clicked", self.on_restore_deleted_clicked, deleted_list, deleted_row, lang, word)
+            deleted_row.add_suffix(restore_button)
+            deleted_list.append(deleted_row)
+        group.add(deleted_list)
+        page = Adw.PreferencesPage()
+        page.add(group)
+        toolbar = Adw.ToolbarView(content=page)
+        toolbar.add_top_bar(Adw.HeaderBar())
+        self.push_subpage(Adw.NavigationPage(title=_("Recently Deleted"), child=toolbar))
+
+    def on_restore_deleted_clicked(self, button, deleted_list, row, lang, word):
+        self.user_dict.restore_deleted(lang, word)
+        deleted_list.remove(row)
+
+    @Gtk.Template.Callback()
+    def on_restore_backup_activated(self, row):
+        group = Adw.PreferencesGroup(description=_("Backups are made at launch when your entries or favorites changed"))
+        backup_list = Gtk.ListBox(selection_mode=Gtk.SelectionMode.NONE, css_classes=["boxed-list"])
+        backup_list.set_placeholder(Gtk.Label(label=_("No backups yet"), margin_top=12,
+                                              margin_bottom=12, css_classes=["dim-label"]))
+        for name, date in backups.list_backups():
+            differences = backups.differences(name)
+            count = sum(len(changes) for changes in differences.values())
+            backup_row = Adw.ActionRow(title=date.strftime("%x %X"), activatable=count > 0,
+                                       subtitle=ngettext("{} difference", "{} differences", count).format(count)
+                                       if count else _("Same as now"))
+            backup_row.connect("activated", self.on_backup_activated, name, differences)
+            backup_list.append(backup_row)
+        group.add(backup_list)
+        page = Adw.PreferencesPage()
+        page.add(group)
+        toolbar = Adw.ToolbarView(content=page)
+        toolbar.add_top_bar(Adw.HeaderBar())
+        self.push_subpage(Adw.NavigationPage(title=_("Backups"), child=toolbar))
+
+    def on_backup_activated(self, row, name, differences):
+        lines = describe_differences(differences)
+        # a preview, not the whole list for big dictionaries
+        preview = "\n".join(lines[:PREVIEW_LINES])
+        if len(lines) > PREVIEW_LINES:
+            more = len(lines) - PREVIEW_LINES
+            preview += "\n" + ngettext("and {} more", "and {} more", more).format(more)
+        dialog = Adw.AlertDialog(heading=_("Restore {}?").format(row.get_title()), body=preview)
+        dialog.add_response("cancel", _("_Cancel"))
+        dialog.add_response("restore", _("_Restore"))
+        dialog.set_response_appearance("restore", Adw.ResponseAppearance.DESTRUCTIVE)
+        dialog.set_close_response("cancel")
+
+        def on_response(dialog, response):
+            if response != "restore":
+                return
+            try:
+                backups.restore(name)
+            except OSError as e:
+                print(f"could not restore backup {name}: {e}")
+                self.add_toast(Adw.Toast(title=_("Could not restore the backup")))
+                return
+            self.pop_subpage()
+            self.add_toast(Adw.Toast(title=_("Backup restored, the previous data was backed up")))
+
+        dialog.connect("response", on_response)
+        dialog.present(self)
+
+    def on_visible_page_changed(self, dialog, pspec):
+        # only contact the server once the user actually opens the page
+        if self.get_visible_page() == self.languages_page and self.pack_index is None:
+            self.pack_index = []
+            self.language_packs_spinner.set_visible(True)
+            self.packs.fetch_index(self.on_index_fetched)
+
+    def on_index_fetched(self, index, error):
+        self.language_packs_spinner.set_visible(False)
+        if error:
+            self.pack_index = None
+            self.language_packs_list.set_placeholder(
+                Gtk.Label(label=_("Could not fetch the language list"), margin_top=12,
+                          margin_bottom=12, css_classes=["dim-label"]))
+            print(f"could not fetch language index: {error}")
+            return
+
+        if not isinstance(index, list):
+            index = []
+        # entries without a usable code, name or url are left out
+        self.pack_index = [pack for pack in index if langpacks.is_valid_pack(pack)
+                           and pack["code"] not in languages.BUNDLED]
+        self.fill_language_packs()
+
+    def fill_language_packs(self):
+        self.language_packs_list.remove_all()
+        for pack in self.pack_index:
+            row = Adw.ActionRow(title=pack["name"], subtitle=pack["code"])
+            if size := pack.get("size"):
+                row.set_subtitle(f'{pack["code"]} · {GLib.format_size(size)}')
+
+            progress = Gtk.ProgressBar(visible=False, valign=Gtk.Align.CENTER, width_request=100)
+            row.add_suffix(progress)
+
+            if self.packs.has_update(pack):
+                update_button = Gtk.Button(icon_name="software-update-available-symbolic",
+                                           tooltip_text=_("Update"),
+                                           valign=Gtk.Align.CENTER,
+                                           css_classes=["flat"])
+                update_button.connect("clicked", self.on_download_pack_clicked, pack, progress)
+                row.add_suffix(update_button)
+
+            if self.packs.is_installed(pack["code"]):
+                button = Gtk.Button(icon_name="user-trash-symbolic",
+                                    tooltip_text=_("Remove"),
+                                    valign=Gtk.Align.CENTER,
+                                    css_classes=["flat"])
+                button.connect("clicked", self.on_remove_pack_clicked, pack)
+            else:
+                button = Gtk.Button(icon_name="folder-download-symbolic",
+                                    tooltip_text=_("Download"),
+                                    valign=Gtk.Align.CENTER,
+                                    css_classes=["flat"])
+                button.connect("clicked", self.on_download_pack_clicked, pack, progress)
+            row.add_suffix(button)
+
+            self.language_packs_list.append(row)
+
+    def on_download_pack_clicked(self, button, pack, progress):
+        button.set_visible(False)
+        progress.set_visible(True)
+
+        def on_done(error, changes):
+            if error:
+                self.add_toast(Adw.Toast(title=_("Could not download {}").format(pack["name"])))
+                print(f"could not download {pack['code']}: {error}")
+            elif changes:
+                present_changes(self, pack["name"], changes)
+            self.fill_language_packs()
+
+        # the words the user looked up or starred, to tell them what changed
+        watched = [item.word for items in (History.get_default(), Favorites.get_default())
+                   for item in items if item.lang == pack["code"]]
+        self.packs.download(pack, progress.set_fraction, on_done, watched)
+
+    def on_remove_pack_clicked(self, button, pack):
+        self.packs.remove(pack["code"])
+        self.fill_language_packs()
@@ -0,0 +1,105 @@
+# printing.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+"""Prints word lists as transcription sheets, or saves them as PDF
+through the print dialog.
+"""
+
+from gi.repository import GLib, Gtk, Pango, PangoCairo
+from . import dictionaries
+
+# fonts drawn for IPA, before the general ones with fewer of its symbols
+IPA_FONT = "Charis SIL, Doulos SIL, Gentium Plus, Noto Sans, DejaVu Sans"
+FONT_SIZE = 12
+TITLE_SIZE = 16
+ROW_SPACING = 6
+
+
+def layout(context, text, size=FONT_SIZE, bold=False):
+    layout = context.create_pango_layout()
+    layout.set_font_description(Pango.FontDescription.from_string(f"{IPA_FONT} {'Bold ' if bold else ''}{size}"))
+    layout.set_text(text, -1)
+    return layout
+
+
+def word_layout(context, line):
+    """The layout of the word of a (word, ipa) line, in the left half of
+    the page, or of an attribution line without ipa across all of it.
+    """
+    word, ipa = line
+    if ipa is None:
+        attribution = layout(context, word, FONT_SIZE - 3)
+        attribution.set_width(int(context.get_width() * Pango.SCALE))
+        attribution.set_wrap(Pango.WrapMode.WORD_CHAR)
+        return attribution
+    left = layout(context, word)
+    left.set_width(int(context.get_width() / 2 * Pango.SCALE))
+    left.set_ellipsize(Pango.EllipsizeMode.END)
+    return left
+
+
+def print_transcriptions(parent, title, rows, langs=()):
+    """Print (word, ipa) rows in two columns under title, a page after
+    another, with the sources of the dictionaries of langs at the end.
+    """
+    lines = [(word, ipa) for word, ipa in rows if ipa is not None]
+    lines += [(line, None) for line in dictionaries.attribution(langs)]
+    pages = []
+
+    def on_begin_print(operation, context):
+        # fill each page with the rows that fit under its title
+        height = context.get_height()
+        used = layout(context, title, TITLE_SIZE, bold=True).get_pixel_size()[1] + 2 * ROW_SPACING
+        page = []
+        for line in lines:
+            line_height = word_layout(context, line).get_pixel_size()[1] + ROW_SPACING
+            if page and used + line_height > height:
+                pages.append(page)
+                page = []
+                used = 0
+            page.append(line)
+            used += line_height
+        pages.append(page)
+        operation.set_n_pages(len(pages))
+
+    def on_draw_page(operation, context, number):
+        cr = context.get_cairo_context()
+        cr.set_source_rgb(0, 0, 0)
+        width = context.get_width()
+        y = 0
+        if number == 0:
+            heading = layout(context, title, TITLE_SIZE, bold=True)
+            cr.move_to(0, y)
+            PangoCairo.show_layout(cr, heading)
+            y += heading.get_pixel_size()[1] + 2 * ROW_SPACING
+        for word, ipa in pages[number]:
+            first = word_layout(context, (word, ipa))
+            cr.move_to(0, y)
+            PangoCairo.show_layout(cr, first)
+            if ipa is not None:
+                cr.move_to(width / 2, y)
+                PangoCairo.show_layout(cr, layout(context, ipa))
+            y += first.get_pixel_size()[1] + ROW_SPACING
+
+    operation = Gtk.PrintOperation(job_name=title, unit=Gtk.Unit.POINTS, embed_page_setup=True)
+    operation.connect("begin-print", on_begin_print)
+    operation.connect("draw-page", on_draw_page)
+    try:
+        operation.run(Gtk.PrintOperationAction.PRINT_DIALOG, parent)
+    except GLib.Error as e:
+        print(f"could not print {title}: {e.message}")
@@ -0,0 +1,106 @@
+# reading_quiz.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, Gtk
+from .core import phonemes
+from .practice import PracticeSession
+from .state import AppState
+
+
+def sounds_alike(dictionary, word, answer):
+    """Whether answer is word or a homophone of it, like "right" for "write"."""
+    if answer.casefold() == word.casefold():
+        return True
+    answer_ipa = dictionary.lookup(answer) or dictionary.lookup(answer.lower())
+    if answer_ipa is None:
+        return False
+    return not set(phonemes.variants(dictionary.lookup(word))).isdisjoint(phonemes.variants(answer_ipa))
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/reading_quiz.ui')
+class Word2ipaReadingQuizDialog(Adw.Dialog):
+    """Shows a transcription and asks for the word it belongs to."""
+    __gtype_name__ = 'Word2ipaReadingQuizDialog'
+    status_page = Gtk.Template.Child()
+    answer_entry = Gtk.Template.Child()
+    feedback_label = Gtk.Template.Child()
+    score_label = Gtk.Template.Child()
+    next_button = Gtk.Template.Child()
+
+    def __init__(self, lang, dictionary, words, on_finished, **kwargs):
+        """dictionary is the loaded one of lang, words the ones of the
+        round, see practice_words().
+        """
+        super().__init__(**kwargs)
+        self.lang = lang
+        self.on_finished = on_finished
+        self.dictionary = dictionary
+        self.words = words
+        self.session = PracticeSession("reading", lang)
+        self.score = 0
+        self.index = -1
+        self.next_word()
+
+    def next_word(self):
+        self.index += 1
+        if self.index >= len(self.words):
+            self.close()
+            return
+
+        self.word = self.words[self.index]
+        self.ipa = self.dictionary.lookup(self.word)
+        self.answered = False
+        self.status_page.set_title(AppState.get_default().display(self.ipa, self.lang))
+        self.status_page.set_description(_("Word {} of {}").format(self.index + 1, len(self.words)))
+        self.answer_entry.set_text("")
+        self.answer_entry.set_sensitive(True)
+        self.answer_entry.grab_focus()
+        self.feedback_label.set_label("")
+        self.next_button.set_visible(False)
+
+    @Gtk.Template.Callback()
+    def on_answer_applied(self, entry):
+        answer = entry.get_text().strip()
+        if self.answered or not answer:
+            return
+        self.answered = True
+
+        correct = sounds_alike(self.dictionary, self.word, answer)
+        self.session.record(self.word, self.ipa, answer, correct)
+        if correct:
+            self.score += 1
+            if answer.casefold() == self.word.casefold():
+                self.feedback_label.set_markup(f'<b>{_("Correct!")}</b>')
+            else:
+                # a homophone counts, but show the word that was meant
+                self.feedback_label.set_markup(_("<b>Correct!</b> It sounds like {}").format(
+                    GLib.markup_escape_text(self.word)))
+        else:
+            self.feedback_label.set_markup(_("It was <b>{}</b>").format(GLib.markup_escape_text(self.word)))
+        self.score_label.set_label(_("Score: {} of {}").format(self.score, self.index + 1))
+        self.answer_entry.set_sensitive(False)
+        self.next_button.set_visible(True)
+        self.next_button.grab_focus()
+
+    @Gtk.Template.Callback()
+    def on_next_clicked(self, button):
+        self.next_word()
+
+    @Gtk.Template.Callback()
+    def on_closed(self, dialog):
+        self.on_finished(self.session)
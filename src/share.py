@@ -0,0 +1,46 @@
+# share.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GLib, Gtk
+import urllib.parse
+
+
+def share_text(word, ipa):
+    return f"{word} — {ipa}"
+
+
+def share(parent, word, ipa, on_failed=None):
+    """Hand "word — /ipa/" to the desktop to send on, as a new message of
+    the default mail or chat app, through the OpenURI portal when
+    sandboxed. There is no portal just for sharing text yet, so this is
+    the closest the desktop offers; on_failed is called with the text if
+    nothing could take it.
+    """
+    text = share_text(word, ipa)
+
+    def on_finish(launcher, result):
+        try:
+            launcher.launch_finish(result)
+        except GLib.Error as e:
+            print(f"could not share {word}: {e.message}")
+            if on_failed is not None:
+                on_failed(text)
+
+    uri = "mailto:?" + urllib.parse.urlencode({"subject": word, "body": text}, quote_via=urllib.parse.quote)
+    Gtk.UriLauncher.new(uri).launch(parent, None, on_finish)
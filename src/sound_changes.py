@@ -0,0 +1,97 @@
+# sound_changes.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, Gtk
+from .core import clean_input, sound_change
+from .state import AppState
+
+# shown for sound_change.RuleError reasons
+RULE_ERRORS = {
+    "no-arrow": _("A rule needs an arrow, like “t → ɾ”"),
+    "no-target": _("Nothing to change before the arrow"),
+    "boundary-in-change": _("# can only be used in the environment"),
+    "no-replacement": _("Nothing to change into after the arrow, use ∅ to drop sounds"),
+    "count-mismatch": _("The arrow needs as many sounds after it as before it"),
+    "environment": _("The environment needs one _ for where the sound is, like “V_V”"),
+    "boundary-position": _("# can only be at the edges of the environment"),
+}
+EXAMPLE_RULES = "t → ɾ / V_V\n"
+EXAMPLE_WORDS = "water\nbutter\n/ˈsɪti/\n"
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/sound_changes.ui')
+class Word2ipaSoundChangesDialog(Adw.Dialog):
+    """An experimental sandbox for trying sound changes: rewrite rules
+    applied to transcriptions, with each word shown before and after.
+    """
+    __gtype_name__ = 'Word2ipaSoundChangesDialog'
+    rules_view = Gtk.Template.Child()
+    error_label = Gtk.Template.Child()
+    words_view = Gtk.Template.Child()
+    preview_group = Gtk.Template.Child()
+    preview_list = Gtk.Template.Child()
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self.state = AppState.get_default()
+        self.rules_view.get_buffer().set_text(EXAMPLE_RULES)
+        self.words_view.get_buffer().set_text(EXAMPLE_WORDS)
+        self.rules_view.get_buffer().connect("changed", lambda _b: self.update_preview())
+        self.words_view.get_buffer().connect("changed", lambda _b: self.update_preview())
+        self.update_preview()
+
+    def transcriptions(self):
+        """(label, ipa) of each line of the words, ipa None for words the
+        dictionary doesn't have.
+        """
+        buffer = self.words_view.get_buffer()
+        found = []
+        for line in buffer.get_text(buffer.get_start_iter(), buffer.get_end_iter(), False).splitlines():
+            line = line.strip()
+            if line.startswith(("/", "[")):
+                found.append((line, line))
+                continue
+            word, problem = clean_input(line)
+            if not problem:
+                found.append((word, self.state.lookup(word)))
+        return found
+
+    def update_preview(self):
+        buffer = self.rules_view.get_buffer()
+        try:
+            rules = sound_change.parse_rules(buffer.get_text(buffer.get_start_iter(), buffer.get_end_iter(), False))
+        except sound_change.RuleError as e:
+            self.error_label.set_label(_("Line {number}: {error}").format(number=e.line, error=RULE_ERRORS[e.reason]))
+            self.error_label.set_visible(True)
+            return
+        self.error_label.set_visible(False)
+
+        self.preview_list.remove_all()
+        words = self.transcriptions()
+        for label, ipa in words:
+            if ipa is None:
+                row = Adw.ActionRow(title=label, subtitle=_("Not in the dictionary"), use_markup=False,
+                                    css_classes=["dim-label"])
+            else:
+                after = sound_change.apply_rules(rules, ipa)
+                row = Adw.ActionRow(title=label, use_markup=False, subtitle_selectable=True,
+                                    subtitle=_("{before} → {after}").format(before=ipa, after=after))
+                if after != ipa:
+                    row.add_suffix(Gtk.Image(icon_name="object-select-symbolic", tooltip_text=_("Changed")))
+            self.preview_list.append(row)
+        self.preview_group.set_visible(bool(words))
@@ -0,0 +1,89 @@
+# speech.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gio, GLib
+from . import forvo
+
+
+def _voice(lang):
+    # espeak-ng and speech-dispatcher both want "en-us" style names
+    return lang.replace("_", "-").lower()
+
+
+def _command(text, lang):
+    if GLib.find_program_in_path("espeak-ng"):
+        return ["espeak-ng", "-v", _voice(lang), text]
+    if GLib.find_program_in_path("spd-say"):
+        return ["spd-say", "-l", _voice(lang), text]
+    return None
+
+
+def can_record():
+    """Whether speech can be written to audio files, see record()."""
+    return GLib.find_program_in_path("espeak-ng") is not None
+
+
+def record(text, lang, path):
+    """Write text spoken by espeak-ng to the WAV file at path. Blocks
+    until done; raises GLib.Error if it fails.
+    """
+    process = Gio.Subprocess.new(["espeak-ng", "-v", _voice(lang), "-w", path, text],
+                                 Gio.SubprocessFlags.NONE)
+    process.wait_check(None)
+
+
+def is_available():
+    return _command("", "en_US") is not None
+
+
+def speak(text, lang):
+    """Read text aloud with the system's speech synthesizer.
+
+    Returns False if no synthesizer is installed.
+    """
+    command = _command(text, lang)
+    if command is None:
+        return False
+    try:
+        Gio.Subprocess.new(command, Gio.SubprocessFlags.NONE)
+    except GLib.Error as e:
+        print(f"could not speak: {e.message}")
+        return False
+    return True
+
+
+def can_speak_words():
+    return is_available() or forvo.is_enabled()
+
+
+def speak_word(word, lang):
+    """Play a native speaker's recording of word if the user set up
+    Forvo, falling back to the speech synthesizer.
+    """
+    if not forvo.is_enabled():
+        return speak(word, lang)
+
+    def on_played(error):
+        if error is not None:
+            if error != "not-found":
+                print(f"could not play the Forvo recording of {word}: {error}")
+            speak(word, lang)
+
+    forvo.play(word, lang, on_played)
+    return True
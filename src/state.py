@@ -0,0 +1,150 @@
+# state.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio, GObject
+from . import backends, dictionaries, languages
+from .core import case_forms, compose, lookup_word, mark_compound, normalize, notation, phonemes
+from .favorites import Favorites
+from .history import History
+from .langpacks import LanguagePacks
+from .online import OnlineResults
+from .userdict import UserDictionary
+
+
+class AppState(GObject.Object):
+    """What the window, its pages and dialogs share.
+
+    The selected language and its dictionary are kept here, so every
+    part of the app reads the same ones instead of loading its own.
+    Listen to "notify::lang" and "notify::dictionary" for changes; the
+    dictionary is None while it loads.
+    """
+    __gtype_name__ = 'Word2ipaAppState'
+    __gsignals__ = {
+        'dictionary-failed': (GObject.SignalFlags.RUN_FIRST, None, (str,)),
+    }
+
+    lang = GObject.Property(type=str, default="en_US")
+    dictionary = GObject.Property(type=object)
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        self.history = History.get_default()
+        self.favorites = Favorites.get_default()
+        self.user_dict = UserDictionary.get_default()
+        self.online_results = OnlineResults.get_default()
+        # made first so its chains are dropped before others react to changes
+        self.backends = backends.Backends.get_default()
+        self._loading = None
+        LanguagePacks.get_default().connect("changed", self._on_packs_changed)
+
+    def select_language(self, code):
+        """Make code the current language and load its dictionary."""
+        if code == self.lang and (self.dictionary is not None or self._loading == code):
+            return
+        self.lang = code
+        self.load_dictionary()
+
+    def load_dictionary(self):
+        self.dictionary = None
+        self._loading = self.lang
+        dictionaries.load_async(self.lang, self._on_dictionary_loaded)
+
+    def _on_dictionary_loaded(self, dictionary, error):
+        if self._loading != self.lang or (dictionary is not None and dictionary.lang != self.lang):
+            # the language was changed again while this one was loading
+            return
+        self._loading = None
+        if error:
+            self.emit('dictionary-failed', error)
+            return
+        self.dictionary = dictionary
+
+    def _on_packs_changed(self, packs):
+        # a downloaded dictionary in use may have been replaced
+        if self.lang not in languages.BUNDLED:
+            self.load_dictionary()
+
+    def lookup(self, word):
+        """Return the IPA for word from the first backend that has it, by
+        default the user's own entries, then the dictionary and the
+        ones found online.
+        """
+        return self.lookup_match(word)[0]
+
+    def lookup_match(self, word, composed=True):
+        """Return (ipa, form found, match) for word like core.lookup_word(),
+        to tell "paris" was found as "Paris". Numbers and abbreviations
+        are spelled out if word isn't found as it is, with the
+        "spelled-out" match, and unless composed is False words like
+        "well-known" are put together from their parts, with the
+        "composed" match and the parts joined by " + " as form.
+        """
+        if self.dictionary is None:
+            return None, None, None
+        sources = [self.backends.chain(self.lang, self.dictionary).for_language(self.lang)]
+        ipa, form, match = lookup_word(word, sources)
+        if ipa is None:
+            ipa, form = normalize.lookup_expanded(word, self.lang, sources)
+            match = "spelled-out" if ipa is not None else None
+        if ipa is None and composed:
+            ipa, parts = compose(word, lambda part: lookup_word(part, sources)[0])
+            if ipa is not None:
+                form, match = " + ".join(parts), "composed"
+        return ipa, form, match
+
+    def is_online_result(self, word):
+        """Whether the IPA lookup() returns for word came from Wiktionary."""
+        if self.dictionary is None:
+            return False
+        chain = self.backends.chain(self.lang, self.dictionary)
+        for form, _match in case_forms(word):
+            transcriptions, backend = chain.transcribe_from(form, self.lang)
+            if transcriptions:
+                return backend.name == backends.ONLINE
+        return False
+
+    def display(self, ipa, lang=None, word=None):
+        """Rewrite ipa in the transcription standard and with the
+        optional sounds handling chosen by the user.
+
+        Morpheme boundaries are left out unless the user asked for them,
+        then they are also worked out for compounds of the current
+        language if word is given.
+        """
+        lang = lang or self.lang
+        # withdrawn symbols of older dictionaries
+        ipa = notation.canonicalize(ipa)
+        if not self.settings.get_boolean('morpheme-boundaries'):
+            ipa = phonemes.strip_boundaries(ipa)
+        elif word is not None and lang == self.lang:
+            # parts have to be words of their own, not put together themselves
+            ipa = mark_compound(word, ipa, lambda part: self.lookup_match(part, composed=False)[0])
+        profile = self.settings.get_value('transcription-profiles').unpack().get(lang, "")
+        ipa = notation.format_optional(ipa, self.settings.get_string('optional-sounds'))
+        ipa = notation.convert(ipa, profile)
+        return notation.normalize(ipa, self.settings.get_string('unicode-normalization'))
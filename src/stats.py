@@ -0,0 +1,134 @@
+# stats.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GObject
+import datetime
+import time
+from . import storage
+from .core import phonemes
+
+STATS_FILE = 'stats.json'
+CONFUSIONS_FILE = 'confusions.json'
+ACTIVITY_FILE = 'activity.json'
+
+
+def new_activity():
+    return {"lookups": {}, "symbols": {}}
+
+
+def apply_lookup(activity, change):
+    """Count a journaled lookup, {"day", "symbols"}, in activity."""
+    lookups = activity["lookups"]
+    lookups[change["day"]] = lookups.get(change["day"], 0) + 1
+    for symbol in change["symbols"]:
+        activity["symbols"].setdefault(symbol, change["day"])
+
+
+class Stats(GObject.Object):
+    """Summaries of finished practice sessions, oldest first."""
+    __gtype_name__ = 'Word2ipaStats'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.sessions = storage.load_json(STATS_FILE, [])
+        # expected sound -> {sound typed instead -> count}
+        self.confusions = storage.load_json(CONFUSIONS_FILE, {})
+        # {"lookups": {date: count}, "symbols": {symbol: date first seen}}
+        self.activity = new_activity()
+        # lookups are frequent, so they are written in batches like history
+        self.writer = storage.WriteBehind(ACTIVITY_FILE, lambda: self.activity, self.reload_activity,
+                                          default=new_activity())
+        with storage.lock(ACTIVITY_FILE):
+            self.reload_activity(storage.load_json(ACTIVITY_FILE, new_activity()), self.writer.replay())
+        self.writer.flush()
+
+    def reload_activity(self, data, changes):
+        """Rebuild the activity from saved data and the journaled lookups,
+        which may include ones made in other instances.
+        """
+        self.activity = data
+        for change in changes:
+            apply_lookup(self.activity, change)
+
+    def record_lookup(self, ipa):
+        change = {"day": datetime.date.today().isoformat(), "symbols": list(dict.fromkeys(phonemes.split(ipa)))}
+        apply_lookup(self.activity, change)
+        self.writer.record(change)
+
+    def flush(self):
+        self.writer.flush()
+
+    def week_summary(self, now=None):
+        """Lookups, practice sessions and new sounds of the last 7 days."""
+        now = now or time.time()
+        since = datetime.date.fromtimestamp(now) - datetime.timedelta(days=7)
+        since_iso = since.isoformat()
+        return {
+            "lookups": sum(count for day, count in self.activity["lookups"].items() if day > since_iso),
+            "sessions": sum(1 for session in self.sessions if session["date"] > now - 7 * 24 * 3600),
+            "symbols": sorted(symbol for symbol, day in self.activity["symbols"].items() if day > since_iso),
+        }
+
+    def add_session(self, summary, confusions=None):
+        # merged with what other instances saved meanwhile
+        self.sessions = storage.update_json(STATS_FILE, [], lambda sessions: sessions + [summary])
+        if confusions:
+            def change(saved):
+                for (expected, given), count in confusions.items():
+                    row = saved.setdefault(expected, {})
+                    row[given] = row.get(given, 0) + count
+                return saved
+            self.confusions = storage.update_json(CONFUSIONS_FILE, {}, change)
+        self.emit('changed')
+
+    def top_confusions(self, count=5):
+        """The most frequent (expected, given, count) mix-ups."""
+        pairs = [(expected, given, n)
+                 for expected, row in self.confusions.items()
+                 for given, n in row.items()]
+        pairs.sort(key=lambda pair: pair[2], reverse=True)
+        return pairs[:count]
+
+    def average_accuracy(self, sessions=None):
+        sessions = self.sessions if sessions is None else sessions
+        total = sum(session["total"] for session in sessions)
+        if not total:
+            return None
+        return sum(session["correct"] for session in sessions) / total
+
+    def trend(self, window=5):
+        """Accuracy of the last window sessions minus the window before them."""
+        if len(self.sessions) < window * 2:
+            return None
+        recent = self.average_accuracy(self.sessions[-window:])
+        previous = self.average_accuracy(self.sessions[-window * 2:-window])
+        if recent is None or previous is None:
+            return None
+        return recent - previous
@@ -0,0 +1,170 @@
+# storage.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GLib
+import contextlib
+import fcntl
+import json
+import os
+
+# files whose lock was left behind by an instance that didn't finish
+# writing them, so the window can warn about it
+stale_locks = []
+
+
+def data_dir():
+    """Return the app's directory inside XDG_DATA_HOME, creating it if needed."""
+    path = os.path.join(GLib.get_user_data_dir(), 'word2ipa')
+    os.makedirs(path, exist_ok=True)
+    return path
+
+
+def data_path(name):
+    return os.path.join(data_dir(), name)
+
+
+def load_json(name, default):
+    """Read a JSON file from the data dir, falling back to default."""
+    try:
+        with open(data_path(name), encoding='utf-8') as f:
+            return json.load(f)
+    except FileNotFoundError:
+        return default
+    except (OSError, ValueError) as e:
+        print(f"could not read {name}: {e}")
+        return default
+
+
+def save_json(name, data):
+    """Write a JSON file to the data dir, replacing it atomically."""
+    path = data_path(name)
+    tmp_path = f'{path}.{os.getpid()}.tmp'
+    with open(tmp_path, 'w', encoding='utf-8') as f:
+        json.dump(data, f, ensure_ascii=False, indent=2)
+    os.replace(tmp_path, path)
+
+
+@contextlib.contextmanager
+def lock(name):
+    """Keep other instances of the app, or the command line, from
+    changing the data file name until the block is done.
+
+    The holder's pid is kept in the lock file while it is held; finding
+    one there means an instance died while writing, which is reported
+    through stale_locks.
+    """
+    with open(data_path(f'{name}.lock'), 'a+', encoding='utf-8') as f:
+        fcntl.flock(f, fcntl.LOCK_EX)
+        try:
+            f.seek(0)
+            if holder := f.read().strip():
+                print(f"{name} was left locked by process {holder}, its last changes may be missing")
+                stale_locks.append(name)
+            f.truncate(0)
+            f.write(str(os.getpid()))
+            f.flush()
+            yield
+        finally:
+            # released cleanly even if the block failed, only a crash leaves the pid
+            f.truncate(0)
+            fcntl.flock(f, fcntl.LOCK_UN)
+
+
+def update_json(name, default, change):
+    """Apply change(data) to the JSON file as it is on disk right now and
+    return the result, so changes made by other instances since it was
+    loaded are kept.
+    """
+    with lock(name):
+        data = change(load_json(name, default))
+        save_json(name, data)
+    return data
+
+
+class WriteBehind:
+    """Saves a JSON file in batches instead of on every change.
+
+    Each change is appended to a journal next to the file right away so
+    it survives a crash, while the file itself is only rewritten once
+    delay seconds have passed or flush() is called. Owners replay the
+    journal on load. default is the data of a file that doesn't exist
+    yet.
+
+    The journal is shared by every instance of the app. On flush the
+    owner's reload(data, changes) is called with the file and all the
+    journaled changes, so it picks up what the others did before
+    snapshot() is written.
+    """
+
+    def __init__(self, name, snapshot, reload, delay=10, default=None):
+        self.name = name
+        self.default = [] if default is None else default
+        self.journal_name = f'{name}.journal'
+        # returns the data to write on flush
+        self.snapshot = snapshot
+        self.reload = reload
+        self.delay = delay
+        self.pending = False
+        self._source = 0
+
+    def replay(self):
+        """Return the changes journaled since the last flush, oldest first."""
+        changes = []
+        try:
+            with open(data_path(self.journal_name), encoding='utf-8') as f:
+                for line in f:
+                    try:
+                        changes.append(json.loads(line))
+                    except ValueError:
+                        # the last line was cut short by a crash
+                        break
+        except FileNotFoundError:
+            pass
+        except OSError as e:
+            print(f"could not read {self.journal_name}: {e}")
+        self.pending = bool(changes)
+        return changes
+
+    def record(self, change):
+        with lock(self.name), open(data_path(self.journal_name), 'a', encoding='utf-8') as f:
+            f.write(json.dumps(change, ensure_ascii=False) + '\n')
+        self.pending = True
+        if not self._source:
+            self._source = GLib.timeout_add_seconds(self.delay, self._on_timeout)
+
+    def _on_timeout(self):
+        self._source = 0
+        self.flush()
+        return GLib.SOURCE_REMOVE
+
+    def flush(self):
+        """Write the file now and start a new journal."""
+        if self._source:
+            GLib.source_remove(self._source)
+            self._source = 0
+        if not self.pending:
+            return
+        with lock(self.name):
+            self.reload(load_json(self.name, self.default), self.replay())
+            save_json(self.name, self.snapshot())
+            try:
+                os.remove(data_path(self.journal_name))
+            except FileNotFoundError:
+                pass
+        self.pending = False
@@ -0,0 +1,132 @@
+# symbol_page.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, GLib, Gtk
+import sqlite3
+import threading
+from . import languages, speech, symbols
+from .core import notation
+from .state import AppState
+
+# how many words the dictionary is searched for beyond the first examples
+MORE_EXAMPLES = 20
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/symbol_page.ui')
+class Word2ipaSymbolPage(Adw.NavigationPage):
+    """Everything about one symbol of the IPA lookup table: how its sound
+    is made, words with it and the symbols whose sounds are closest.
+    """
+    __gtype_name__ = 'Word2ipaSymbolPage'
+    symbol_label = Gtk.Template.Child()
+    sound_label = Gtk.Template.Child()
+    former_label = Gtk.Template.Child()
+    features_box = Gtk.Template.Child()
+    listen_button = Gtk.Template.Child()
+    articulation_group = Gtk.Template.Child()
+    examples_list = Gtk.Template.Child()
+    more_examples_group = Gtk.Template.Child()
+    more_examples_spinner = Gtk.Template.Child()
+    more_examples_list = Gtk.Template.Child()
+    related_group = Gtk.Template.Child()
+    related_list = Gtk.Template.Child()
+
+    def __init__(self, entry, table, examples_lang, examples, on_symbol_chosen, **kwargs):
+        super().__init__(tag=entry["symbol"], title=entry["symbol"], **kwargs)
+        self.entry = entry
+        self.examples_lang = examples_lang
+        self.examples = examples
+        self.on_symbol_chosen = on_symbol_chosen
+        self.state = AppState.get_default()
+
+        self.symbol_label.set_label(entry["symbol"])
+        self.sound_label.set_label(entry["sound"])
+        if former := notation.former_symbols(entry["symbol"]):
+            self.former_label.set_label(_("Formerly written {}").format(", ".join(former)))
+            self.former_label.set_visible(True)
+        chips = symbols.chips(entry)
+        for label, tooltip in chips:
+            # looks like the filter chips of the IPA Lookup page, but can't be clicked
+            self.features_box.append(Gtk.Button(label=label, tooltip_text=tooltip, can_target=False,
+                                                focusable=False, css_classes=["pill", "small"]))
+        self.features_box.set_visible(bool(chips))
+        self.listen_button.set_sensitive(bool(examples) and speech.can_speak_words())
+
+        articulation = symbols.articulation(entry, table)
+        for title, value in articulation:
+            self.articulation_group.add(Adw.ActionRow(title=title, subtitle=value, css_classes=["property"]))
+        self.articulation_group.set_visible(bool(articulation))
+
+        for word, ipa in examples:
+            self.examples_list.append(self.create_example_row(word, ipa, examples_lang))
+
+        related = symbols.related(entry, table)
+        for other in related:
+            row = Adw.ActionRow(title=other["symbol"], subtitle=other["sound"], activatable=True)
+            row.add_suffix(Gtk.Image(icon_name="go-next-symbolic"))
+            row.connect("activated", lambda _r, symbol=other["symbol"]: self.on_symbol_chosen(symbol))
+            self.related_list.append(row)
+        self.related_group.set_visible(bool(related))
+
+        self.find_more_examples()
+
+    def create_example_row(self, word, ipa, lang):
+        row = Adw.ActionRow(title=word, subtitle=ipa, use_markup=False, activatable=True,
+                            action_name="win.look-up", action_target=GLib.Variant("(ss)", (word, lang)))
+        # hearing the sound inside a word, not only on its own
+        play_button = Gtk.Button(icon_name="audio-volume-high-symbolic",
+                                 tooltip_text=_("Listen"),
+                                 valign=Gtk.Align.CENTER,
+                                 sensitive=speech.can_speak_words(),
+                                 css_classes=["flat", "circular"])
+        play_button.connect("clicked", lambda _b: speech.speak_word(word, lang))
+        row.add_suffix(play_button)
+        return row
+
+    def find_more_examples(self):
+        dictionary = self.state.dictionary
+        if dictionary is None:
+            self.more_examples_group.set_visible(False)
+            return
+        self.more_examples_group.set_title(
+            _("More Examples in {}").format(languages.display_name(dictionary.lang)))
+        shown = {word for word, _ipa in self.examples} if self.examples_lang == dictionary.lang else set()
+        symbol = self.entry["symbol"]
+
+        def worker():
+            try:
+                found = dictionary.examples(symbol, count=MORE_EXAMPLES + len(shown))
+            except sqlite3.Error as e:
+                print(f"could not find examples in {dictionary.lang}: {e}")
+                found = []
+            GLib.idle_add(on_found, found)
+
+        def on_found(found):
+            self.more_examples_spinner.set_visible(False)
+            found = [(word, ipa) for word, ipa in found if word not in shown][:MORE_EXAMPLES]
+            for word, ipa in found:
+                self.more_examples_list.append(
+                    self.create_example_row(word, self.state.display(ipa, dictionary.lang, word), dictionary.lang))
+            self.more_examples_group.set_visible(bool(found))
+
+        # the whole dictionary may have to be searched
+        threading.Thread(target=worker, daemon=True).start()
+
+    @Gtk.Template.Callback()
+    def on_listen_clicked(self, button):
+        speech.speak_word(self.examples[0][0], self.examples_lang)
@@ -0,0 +1,117 @@
+# symbol_quiz.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Adw, Gtk
+import random
+from . import speech, symbols
+from .core import phonemes
+from .practice import PracticeSession
+
+ROUND_LENGTH = 10
+CHOICES = 4
+
+
+def make_question(entries, rng=random):
+    """Pick a symbol and CHOICES example words, shuffled, of which only
+    one has the symbol's sound.
+
+    Returns (entry, answer, answer_ipa, choices).
+    """
+    entry = rng.choice([entry for entry in entries if entry.get("examples")])
+    answer, answer_ipa = rng.choice(list(zip(entry["examples"], entry["ipa_examples"])))
+    # words of other symbols that don't happen to have this sound too
+    distractors = sorted({word for other in entries if other is not entry
+                          for word, ipa in zip(other.get("examples", []), other.get("ipa_examples", []))
+                          if word != answer and entry["symbol"] not in phonemes.split(ipa)})
+    choices = rng.sample(distractors, min(CHOICES - 1, len(distractors))) + [answer]
+    rng.shuffle(choices)
+    return entry, answer, answer_ipa, choices
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/symbol_quiz.ui')
+class Word2ipaSymbolQuizDialog(Adw.Dialog):
+    """Shows an IPA symbol and asks which word has its sound."""
+    __gtype_name__ = 'Word2ipaSymbolQuizDialog'
+    status_page = Gtk.Template.Child()
+    choices_box = Gtk.Template.Child()
+    feedback_label = Gtk.Template.Child()
+    score_label = Gtk.Template.Child()
+    listen_button = Gtk.Template.Child()
+    next_button = Gtk.Template.Child()
+
+    def __init__(self, on_finished, **kwargs):
+        super().__init__(**kwargs)
+        self.on_finished = on_finished
+        self.entries = symbols.load_table()
+        # the examples of the lookup table are English words
+        self.session = PracticeSession("symbols", "en_US")
+        self.listen_button.set_sensitive(speech.can_speak_words())
+        self.index = -1
+        self.score = 0
+        self.next_question()
+
+    def next_question(self):
+        self.index += 1
+        if self.index >= ROUND_LENGTH:
+            self.close()
+            return
+
+        self.entry, self.answer, self.answer_ipa, choices = make_question(self.entries)
+        self.answered = False
+        self.status_page.set_title(self.entry["symbol"])
+        self.status_page.set_description(_("Symbol {} of {}: which word has this sound?").format(
+            self.index + 1, ROUND_LENGTH))
+        while child := self.choices_box.get_first_child():
+            self.choices_box.remove(child)
+        for word in choices:
+            button = Gtk.Button(label=word, css_classes=["pill"])
+            button.connect("clicked", self.on_choice_clicked, word)
+            self.choices_box.append(button)
+        self.feedback_label.set_label("")
+        self.listen_button.set_visible(False)
+        self.next_button.set_visible(False)
+
+    def on_choice_clicked(self, button, word):
+        if self.answered:
+            return
+        self.answered = True
+        correct = word == self.answer
+        self.session.record(self.answer, self.answer_ipa, word, correct)
+        if correct:
+            self.score += 1
+            button.add_css_class("success")
+            self.feedback_label.set_label(_("Correct! {} is {}").format(self.answer, self.answer_ipa))
+        else:
+            button.add_css_class("error")
+            self.feedback_label.set_label(_("It was {}, {}").format(self.answer, self.answer_ipa))
+        self.score_label.set_label(_("Score: {} of {}").format(self.score, self.index + 1))
+        self.listen_button.set_visible(True)
+        self.next_button.set_visible(True)
+        self.next_button.grab_focus()
+
+    @Gtk.Template.Callback()
+    def on_listen_clicked(self, button):
+        speech.speak_word(self.answer, "en_US")
+
+    @Gtk.Template.Callback()
+    def on_next_clicked(self, button):
+        self.next_question()
+
+    @Gtk.Template.Callback()
+    def on_closed(self, dialog):
+        self.on_finished(self.session)
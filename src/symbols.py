@@ -0,0 +1,216 @@
+# symbols.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Gio
+import json
+from .consonant_table import MANNERS, PLACES
+from .core import similarity
+
+TABLE_PATH = "/io/github/mohfy/word2ipa/dicts/ipa_lookup_table.json"
+
+
+def load_table():
+    """The entries of the bundled IPA lookup table: a symbol, the name of
+    its sound, its features and example words with their IPA.
+
+    Features the table doesn't give are filled in from the ones the
+    similarity scores use, so the charts, filters and symbol pages all
+    agree with them.
+    """
+    data = Gio.resources_lookup_data(TABLE_PATH, Gio.ResourceLookupFlags.NONE)
+    return [{**similarity.features(entry["symbol"]), **entry}
+            for entry in json.loads(data.get_data().decode("utf-8"))]
+
+
+HEIGHTS = {
+    "close": _("Close"),
+    "near-close": _("Near-close"),
+    "close-mid": _("Close-mid"),
+    "mid": _("Mid"),
+    "open-mid": _("Open-mid"),
+    "near-open": _("Near-open"),
+    "open": _("Open"),
+}
+BACKNESSES = {
+    "front": _("Front"),
+    "central": _("Central"),
+    "back": _("Back"),
+}
+
+# what related() compares entries by
+ARTICULATION_KEYS = ["voiced", "place", "manner", "height", "backness", "rounded", "diphthong"]
+
+# features the IPA Lookup list can be narrowed down by, in the order
+# their filter chips are shown
+FILTERS = [
+    ("vowel", _("Vowel")),
+    ("diphthong", _("Diphthong")),
+    ("consonant", _("Consonant")),
+    ("voiced", _("Voiced")),
+    ("voiceless", _("Voiceless")),
+    ("plosive", _("Plosive")),
+    ("nasal", _("Nasal")),
+    ("fricative", _("Fricative")),
+    ("affricate", _("Affricate")),
+    ("approximant", _("Approximant")),
+    ("front", _("Front")),
+    ("central", _("Central")),
+    ("back", _("Back")),
+    ("rounded", _("Rounded")),
+]
+
+
+# other words for features, as natural_class() understands them
+SYNONYMS = {
+    "stop": "plosive",
+    "flap": "tap",
+    "spread": "unrounded",
+}
+
+
+def features(entry):
+    """The articulatory features of a lookup table entry: FILTERS ids,
+    its place or height and "lateral", "unrounded" or "long".
+    """
+    found = set()
+    if entry.get("diphthong"):
+        found.add("diphthong")
+    if "height" in entry:
+        found.update({"vowel", entry["backness"], entry["height"]})
+        found.add("rounded" if entry.get("rounded") else "unrounded")
+    if "manner" in entry:
+        found.update({"consonant", entry["place"]})
+        found.add("voiced" if entry.get("voiced") else "voiceless")
+        # lateral approximants are approximants and laterals too
+        found.update(entry["manner"].split())
+    if entry.get("long"):
+        found.add("long")
+    return found
+
+
+def natural_class(query):
+    """The features a query like "voiceless fricatives" or "front rounded
+    vowels" names, or None if some of its words aren't features.
+    """
+    names = dict(SYNONYMS)
+    extra = [("lateral", _("Lateral")), ("long", _("Long")), ("unrounded", _("Unrounded"))]
+    for key, label in FILTERS + PLACES + MANNERS + list(HEIGHTS.items()) + list(BACKNESSES.items()) + extra:
+        names[key] = key
+        names[label.casefold()] = key
+
+    found = set()
+    for word in query.casefold().replace(",", " ").split():
+        # "fricatives", "affricates" and "approximants" name the class too
+        for candidate in (word, word.removesuffix("s"), word.removesuffix("es")):
+            if candidate in names:
+                found.add(names[candidate])
+                break
+        else:
+            return None
+    return found or None
+
+
+def articulation(entry, table):
+    """(property, value) pairs describing how the sound of a lookup
+    table entry is made.
+    """
+    if "manner" in entry:
+        return [
+            (_("Voicing"), _("Voiced") if entry.get("voiced") else _("Voiceless")),
+            (_("Place"), dict(PLACES).get(entry["place"], entry["place"])),
+            # affricates aren't a row of the consonant table
+            (_("Manner"), dict(MANNERS + FILTERS).get(entry["manner"], entry["manner"])),
+        ]
+    if "height" in entry:
+        return [
+            (_("Height"), HEIGHTS.get(entry["height"], entry["height"])),
+            (_("Backness"), BACKNESSES.get(entry["backness"], entry["backness"])),
+            (_("Rounding"), _("Rounded") if entry.get("rounded") else _("Unrounded")),
+        ]
+    if entry.get("diphthong"):
+        parts = glide(entry, table)
+        if len(parts) == 2:
+            return [(_("Diphthong"), _("Glides from {} to {}").format(*parts))]
+        return [(_("Diphthong"), _("Two vowels in one syllable"))]
+    return []
+
+
+def chips(entry):
+    """(label, tooltip) of each distinctive feature of a lookup table
+    entry, like ("Voiced", "Voicing"), in the order they are shown.
+    """
+    found = []
+    if "manner" in entry:
+        found += [
+            (_("Voiced") if entry.get("voiced") else _("Voiceless"), _("Voicing")),
+            (dict(PLACES).get(entry["place"], entry["place"]), _("Place")),
+            (dict(MANNERS + FILTERS).get(entry["manner"], entry["manner"]), _("Manner")),
+        ]
+    elif "height" in entry:
+        found += [
+            (HEIGHTS.get(entry["height"], entry["height"]), _("Height")),
+            (BACKNESSES.get(entry["backness"], entry["backness"]), _("Backness")),
+            (_("Rounded") if entry.get("rounded") else _("Unrounded"), _("Rounding")),
+        ]
+    elif entry.get("diphthong"):
+        found.append((_("Diphthong"), _("Two vowels in one syllable")))
+    if entry.get("long"):
+        found.append((_("Long"), _("Length")))
+    return found
+
+
+def describe(entry, table):
+    """One line on a lookup table entry, like "ʃ (sh): Voiceless,
+    Postalveolar, Fricative".
+    """
+    values = [value for _property, value in articulation(entry, table)]
+    name = _("{symbol} ({sound})").format(symbol=entry["symbol"], sound=entry["sound"])
+    return f"{name}: {', '.join(values)}" if values else name
+
+
+def glide(entry, table):
+    """The vowels of the table a diphthong entry starts and ends with,
+    ignoring length marks.
+    """
+    vowels = {other["symbol"].rstrip("ː"): other["symbol"] for other in table if "height" in other}
+    symbol = entry["symbol"]
+    for split in range(1, len(symbol)):
+        start, end = symbol[:split], symbol[split:]
+        if start in vowels and end in vowels:
+            return [vowels[start], vowels[end]]
+    return []
+
+
+def related(entry, table, count=6):
+    """Up to count other entries of table that sound closest to entry:
+    the vowels a diphthong glides between, then the ones made most alike
+    by the similarity scores.
+    """
+    parts = glide(entry, table)
+
+    def shared(other):
+        return sum(1 for key in ARTICULATION_KEYS if key in entry and entry[key] == other.get(key))
+
+    # diphthongs are only described by being one
+    needed = min(2, sum(1 for key in ARTICULATION_KEYS if key in entry))
+    others = [other for other in table if other is not entry
+              and (other["symbol"] in parts or shared(other) >= needed)]
+    others.sort(key=lambda other: (other["symbol"] not in parts,
+                                   similarity.sound_distance(entry["symbol"], other["symbol"])))
+    return others[:count]
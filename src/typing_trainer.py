@@ -0,0 +1,112 @@
+# typing_trainer.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import Adw, GLib, Gtk
+from .core import phonemes, similarity
+from .ipa_keyboard import Word2ipaIpaKeyboard
+from .practice import PracticeSession
+
+
+def closest_variant(ipa, answer):
+    """The variant of a dictionary value that is nearest to the answer."""
+    return min(phonemes.variants(ipa), key=lambda variant: similarity.distance(variant, answer))
+
+
+def highlight(pairs):
+    """Markup of the expected sounds with the ones the user missed underlined."""
+    markup = ""
+    for expected, given in pairs:
+        if expected is None:
+            continue
+        text = GLib.markup_escape_text(expected)
+        if expected != given:
+            text = f'<span underline="error" weight="bold">{text}</span>'
+        markup += text
+    return f"/{markup}/"
+
+
+@Gtk.Template(resource_path='/io/github/mohfy/word2ipa/typing_trainer.ui')
+class Word2ipaTypingTrainerDialog(Adw.Dialog):
+    """Shows a word and asks for its transcription."""
+    __gtype_name__ = 'Word2ipaTypingTrainerDialog'
+    status_page = Gtk.Template.Child()
+    answer_entry = Gtk.Template.Child()
+    feedback_label = Gtk.Template.Child()
+    next_button = Gtk.Template.Child()
+
+    def __init__(self, lang, dictionary, words, on_finished, **kwargs):
+        """dictionary is the loaded one of lang, words the ones of the
+        round, see practice_words().
+        """
+        super().__init__(**kwargs)
+        self.lang = lang
+        self.on_finished = on_finished
+        self.dictionary = dictionary
+        self.words = words
+        self.session = PracticeSession("typing", lang)
+        self.index = -1
+        self.next_word()
+
+    def next_word(self):
+        self.index += 1
+        if self.index >= len(self.words):
+            self.close()
+            return
+
+        self.word = self.words[self.index]
+        self.answered = False
+        self.status_page.set_title(self.word)
+        self.status_page.set_description(_("Word {} of {}").format(self.index + 1, len(self.words)))
+        self.answer_entry.set_text("")
+        self.answer_entry.set_sensitive(True)
+        self.answer_entry.grab_focus()
+        self.feedback_label.set_label("")
+        self.next_button.set_visible(False)
+
+    @Gtk.Template.Callback()
+    def on_answer_applied(self, entry):
+        answer = entry.get_text().strip()
+        if self.answered or not answer:
+            return
+        self.answered = True
+
+        ipa = self.dictionary.lookup(self.word)
+        expected = closest_variant(ipa, answer)
+        pairs = phonemes.align(expected, answer)
+        errors = sum(1 for a, b in pairs if a != b)
+        self.session.record(self.word, expected, answer, errors == 0, answer_is_ipa=True)
+
+        if errors == 0:
+            self.feedback_label.set_markup(f'{_("Correct!")} {GLib.markup_escape_text(expected)}')
+        else:
+            self.feedback_label.set_markup(highlight(pairs))
+            self.feedback_label.set_tooltip_text(
+                ngettext("{} sound wrong", "{} sounds wrong", errors).format(errors))
+        self.answer_entry.set_sensitive(False)
+        self.next_button.set_visible(True)
+        self.next_button.grab_focus()
+
+    @Gtk.Template.Callback()
+    def on_next_clicked(self, button):
+        self.feedback_label.set_tooltip_text(None)
+        self.next_word()
+
+    @Gtk.Template.Callback()
+    def on_closed(self, dialog):
+        self.on_finished(self.session)
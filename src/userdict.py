@@ -0,0 +1,323 @@
+# userdict.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+from gi.repository import GObject
+import datetime
+import json
+from . import storage
+from .core import Dictionary, collation, csv_import, orthography, phonemes
+
+USER_DICT_FILE = 'user_dictionary.json'
+DELETED_FILE = 'deleted_entries.json'
+SPELLING_RULES_FILE = 'spelling_rules.json'
+ENTRY_INFO_FILE = 'entry_info.json'
+# deleted entries can be restored for this long
+KEEP_DELETED_DAYS = 30
+# where an entry came from, besides the name of the file it was imported from
+SOURCE_MANUAL = "manual"
+SOURCE_ONLINE = "online"
+
+
+def now():
+    """The time an entry is changed, as stored in its info."""
+    return datetime.datetime.now().astimezone().isoformat(timespec="seconds")
+
+
+class UserEntry(GObject.Object):
+    """An entry of the user dictionary as a row of the editor's table."""
+    __gtype_name__ = 'Word2ipaUserEntry'
+
+    word = GObject.Property(type=str)
+    # the first variant of the IPA, and the others after it
+    ipa = GObject.Property(type=str)
+    variants = GObject.Property(type=str)
+    # see UserDictionary.info()
+    source = GObject.Property(type=str)
+    modified = GObject.Property(type=str)
+    # joined by ", "
+    tags = GObject.Property(type=str)
+
+    def __init__(self, word, ipa, info=None):
+        main, *others = phonemes.variants(ipa) or [ipa]
+        info = info or {}
+        super().__init__(word=word, ipa=main, variants=", ".join(others), source=info.get("source", ""),
+                         modified=info.get("modified", ""), tags=", ".join(info.get("tags", [])))
+
+    def joined(self, ipa=None, variants=None):
+        """The dictionary value of the entry, with ipa or variants replaced."""
+        ipa = self.ipa if ipa is None else ipa
+        variants = self.variants if variants is None else variants
+        return ", ".join([ipa] + phonemes.variants(variants))
+
+
+class UserDictionary(GObject.Object):
+    """Entries added or corrected by the user.
+
+    They are keyed by language code, consulted before the bundled
+    dictionaries and stored in XDG_DATA_HOME so they survive updates.
+    """
+    __gtype_name__ = 'Word2ipaUserDictionary'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.languages = storage.load_json(USER_DICT_FILE, {})
+        # {lang: {word: {"ipa": ..., "deleted": date}}}
+        self.deleted = storage.load_json(DELETED_FILE, {})
+        # {lang: text of its orthography rules}
+        self.spelling_rules = storage.load_json(SPELLING_RULES_FILE, {})
+        # {lang: {word: info}}, see info()
+        self.entry_info = storage.load_json(ENTRY_INFO_FILE, {})
+        self.purge_deleted()
+
+    def reload(self):
+        """Read the entries again after the file was replaced."""
+        self.languages = storage.load_json(USER_DICT_FILE, {})
+        self.deleted = storage.load_json(DELETED_FILE, {})
+        self.spelling_rules = storage.load_json(SPELLING_RULES_FILE, {})
+        self.entry_info = storage.load_json(ENTRY_INFO_FILE, {})
+        self.emit('changed')
+
+    def count(self, lang):
+        return len(self.languages.get(lang, {}))
+
+    def entries(self, lang):
+        return collation.sort_words(self.languages.get(lang, {}).items(), lang,
+                                    word=lambda entry: entry[0])
+
+    def lookup(self, lang, word):
+        return self.languages.get(lang, {}).get(word)
+
+    def info(self, lang, word):
+        """What is known about an entry: its "source", SOURCE_MANUAL,
+        SOURCE_ONLINE or the name of the file it was imported from, when
+        it was last "modified" in ISO 8601, the "tags" the user gave it
+        and the "unknown" symbols of entries imported with symbols that
+        aren't IPA. Entries added before this was kept have none of them.
+        """
+        return self.entry_info.get(lang, {}).get(word, {})
+
+    def to_review(self, lang):
+        """(word, ipa, unknown symbols) of the entries imported with
+        symbols that aren't IPA, until they are edited or marked reviewed.
+        """
+        entries = self.languages.get(lang, {})
+        return [(word, entries[word], info["unknown"]) for word, info in self.entry_info.get(lang, {}).items()
+                if info.get("unknown") and word in entries]
+
+    def set_tags(self, lang, word, tags):
+        """Replace the tags of an entry with the list tags."""
+        self._update_info(lang, {word: {"tags": list(dict.fromkeys(tags)) or None, "modified": now()}})
+        self.emit('changed')
+
+    def mark_reviewed(self, lang, word):
+        self._update_info(lang, {word: {"unknown": None}})
+        self.emit('changed')
+
+    def as_dictionary(self, lang):
+        return Dictionary(lang, self.languages.get(lang, {}))
+
+    def set(self, lang, word, ipa, old_word=None, source=None):
+        """Add or change an entry, renaming old_word to word. source is
+        where it came from; entries typed in are SOURCE_MANUAL, and edits
+        keep the source an entry had.
+        """
+        def change(languages):
+            entries = languages.setdefault(lang, {})
+            if old_word is not None and old_word != word:
+                entries.pop(old_word, None)
+            entries[word] = ipa
+            return languages
+        previous = self.info(lang, old_word if old_word is not None else word)
+        # an entry edited by hand has been looked at
+        info = {word: {**previous, "unknown": None, "modified": now(),
+                       "source": source or previous.get("source", SOURCE_MANUAL)}}
+        if old_word is not None and old_word != word:
+            info[old_word] = None
+        self._update_info(lang, info)
+        self._save(change)
+
+    def add_many(self, lang, entries, source, info=None):
+        """Add (word, ipa) entries from source, replacing the ones of the
+        same words. info optionally maps words to more of what is kept
+        about them, like the "unknown" symbols of entries to review or the
+        source and time of entries exported before.
+        """
+        def change(languages):
+            languages.setdefault(lang, {}).update(entries)
+            return languages
+        info = info or {}
+        modified = now()
+        self._update_info(lang, {word: {"source": source, "modified": modified, "unknown": None,
+                                        **info.get(word, {})} for word, _ipa in entries})
+        self._save(change)
+
+    def import_dictionary(self, lang, data, name):
+        """Add the entries of a dictionary exported as JSON, keeping the
+        source, time and tags they had. Return how many there were; raises
+        DictionaryError if data isn't such a dictionary.
+        """
+        entries = list(Dictionary.parse(lang, data).items())
+        exported = json.loads(data).get("entry_info", {})
+        if not isinstance(exported, dict):
+            exported = {}
+        entries, unknown, _rejected = csv_import.check_symbols(entries, strict=False)
+        info = {}
+        for word, _ipa in entries:
+            kept = exported.get(word) if isinstance(exported.get(word), dict) else {}
+            info[word] = {key: kept[key] for key in ("source", "modified") if isinstance(kept.get(key), str)}
+            if isinstance(kept.get("tags"), list):
+                info[word]["tags"] = [tag for tag in kept["tags"] if isinstance(tag, str)] or None
+            if word in unknown:
+                info[word]["unknown"] = unknown[word]
+        self.add_many(lang, entries, name, info)
+        return len(entries)
+
+    def set_many(self, lang, transcriptions):
+        """Change the IPA of several entries at once, from a word -> ipa dict."""
+        def change(languages):
+            entries = languages.setdefault(lang, {})
+            for word, ipa in transcriptions.items():
+                if word in entries:
+                    entries[word] = ipa
+            return languages
+        self._update_info(lang, {word: {"modified": now()} for word in transcriptions
+                                 if self.lookup(lang, word) is not None})
+        self._save(change)
+
+    def remove(self, lang, word):
+        """Move an entry to the recently deleted list."""
+        self.remove_many(lang, [word])
+
+    def remove_many(self, lang, words):
+        removed = {word: ipa for word in words if (ipa := self.lookup(lang, word)) is not None}
+        if not removed:
+            return
+
+        def trash(deleted):
+            today = datetime.date.today().isoformat()
+            for word, ipa in removed.items():
+                deleted.setdefault(lang, {})[word] = {"ipa": ipa, "deleted": today}
+            return deleted
+        self.deleted = storage.update_json(DELETED_FILE, {}, trash)
+
+        def change(languages):
+            entries = languages.get(lang, {})
+            for word in removed:
+                entries.pop(word, None)
+            if lang in languages and not entries:
+                del languages[lang]
+            return languages
+        self._save(change)
+
+    def deleted_entries(self, lang):
+        """Return (word, ipa, date deleted) of the recently deleted entries, newest first."""
+        entries = [(word, entry["ipa"], datetime.date.fromisoformat(entry["deleted"]))
+                   for word, entry in self.deleted.get(lang, {}).items()]
+        return sorted(entries, key=lambda entry: entry[2], reverse=True)
+
+    def restore_deleted(self, lang, word):
+        self.restore_many(lang, [word])
+
+    def restore_many(self, lang, words):
+        restored = {word: entry["ipa"] for word in words if (entry := self.deleted.get(lang, {}).get(word))}
+        if not restored:
+            return
+        self._forget_deleted(lambda deleted_lang, deleted_word, _entry:
+                             deleted_lang == lang and deleted_word in restored)
+
+        def change(languages):
+            languages.setdefault(lang, {}).update(restored)
+            return languages
+        self._save(change)
+
+    def purge_deleted(self):
+        """Drop the deleted entries older than KEEP_DELETED_DAYS."""
+        cutoff = (datetime.date.today() - datetime.timedelta(days=KEEP_DELETED_DAYS)).isoformat()
+        if any(entry["deleted"] < cutoff for entries in self.deleted.values() for entry in entries.values()):
+            self._forget_deleted(lambda _lang, _word, entry: entry["deleted"] < cutoff)
+
+    def set_spelling_rules(self, lang, text):
+        def change(rules):
+            if text.strip():
+                rules[lang] = text
+            else:
+                rules.pop(lang, None)
+            return rules
+        self.spelling_rules = storage.update_json(SPELLING_RULES_FILE, {}, change)
+        # words may be spelled out by the rules
+        self.emit('changed')
+
+    def rules(self, lang):
+        """The parsed spelling rules of lang, {} if it has no usable ones."""
+        try:
+            return orthography.parse_rules(self.spelling_rules.get(lang, ""))
+        except orthography.RuleError:
+            return {}
+
+    def spell(self, lang, word):
+        """Return (ipa, letters without a rule) for word by the spelling
+        rules of lang, or None if it has no usable rules.
+        """
+        rules = self.rules(lang)
+        return orthography.transcribe(word, rules) if rules else None
+
+    def _forget_deleted(self, matches):
+        def change(deleted):
+            for lang in list(deleted):
+                deleted[lang] = {word: entry for word, entry in deleted[lang].items()
+                                 if not matches(lang, word, entry)}
+                if not deleted[lang]:
+                    del deleted[lang]
+            return deleted
+        self.deleted = storage.update_json(DELETED_FILE, {}, change)
+
+    def _update_info(self, lang, info):
+        """Merge word -> {key: value} into the entry info of lang; a None
+        value drops the key and a None word info the word.
+        """
+        def change(entry_info):
+            words = entry_info.setdefault(lang, {})
+            for word, keys in info.items():
+                merged = {**words.get(word, {}), **(keys or {})} if keys is not None else {}
+                merged = {key: value for key, value in merged.items() if value is not None}
+                if merged:
+                    words[word] = merged
+                else:
+                    words.pop(word, None)
+            if not words:
+                del entry_info[lang]
+            return entry_info
+        self.entry_info = storage.update_json(ENTRY_INFO_FILE, {}, change)
+
+    def _save(self, change):
+        # applied to the file rather than to self.languages so entries
+        # saved by other instances meanwhile aren't lost
+        self.languages = storage.update_json(USER_DICT_FILE, {}, change)
+        self.emit('changed')
@@ -0,0 +1,97 @@
+# vowel_chart.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import GObject, Gtk
+
+# (symbol, height, backness, rounded); height runs from 0 (close) to 6
+# (open) and backness from 0 (front) to 2 (back), as on the IPA chart
+VOWELS = [
+    ("i", 0, 0, False), ("y", 0, 0, True),
+    ("ɨ", 0, 1, False), ("ʉ", 0, 1, True),
+    ("ɯ", 0, 2, False), ("u", 0, 2, True),
+    ("ɪ", 1, 0.4, False), ("ʏ", 1, 0.4, True), ("ʊ", 1, 1.6, True),
+    ("e", 2, 0, False), ("ø", 2, 0, True),
+    ("ɘ", 2, 1, False), ("ɵ", 2, 1, True),
+    ("ɤ", 2, 2, False), ("o", 2, 2, True),
+    ("ə", 3, 1, None),
+    ("ɛ", 4, 0, False), ("œ", 4, 0, True),
+    ("ɜ", 4, 1, False), ("ɞ", 4, 1, True),
+    ("ʌ", 4, 2, False), ("ɔ", 4, 2, True),
+    ("æ", 5, 0, False), ("ɐ", 5, 1, None),
+    ("a", 6, 0, False), ("ɶ", 6, 0, True),
+    ("ɑ", 6, 2, False), ("ɒ", 6, 2, True),
+]
+
+WIDTH = 420
+HEIGHT = 300
+MARGIN = 24
+# rounded vowels sit right of the point, unrounded ones left of it
+PAIR_OFFSET = 14
+BUTTON_SIZE = 28
+
+
+def position(height, backness):
+    """Where a vowel's point is on the trapezoid, in pixels."""
+    top = WIDTH - 2 * MARGIN
+    # the front edge slants so open vowels start a third of the way in
+    front = height / 6 * top / 3
+    x = MARGIN + front + backness / 2 * (top - front)
+    y = MARGIN + height / 6 * (HEIGHT - 2 * MARGIN)
+    return x, y
+
+
+class Word2ipaVowelChart(Gtk.Overlay):
+    """The IPA vowel trapezoid with a button for every vowel."""
+    __gtype_name__ = 'Word2ipaVowelChart'
+    __gsignals__ = {
+        'vowel-activated': (GObject.SignalFlags.RUN_FIRST, None, (str,)),
+    }
+
+    def __init__(self, **kwargs):
+        super().__init__(halign=Gtk.Align.CENTER, **kwargs)
+        area = Gtk.DrawingArea(content_width=WIDTH, content_height=HEIGHT)
+        area.set_draw_func(self.draw)
+        self.set_child(area)
+
+        fixed = Gtk.Fixed()
+        for symbol, height, backness, rounded in VOWELS:
+            x, y = position(height, backness)
+            if rounded is not None:
+                x += PAIR_OFFSET if rounded else -PAIR_OFFSET
+            button = Gtk.Button(label=symbol, tooltip_text=symbol,
+                                width_request=BUTTON_SIZE, height_request=BUTTON_SIZE,
+                                css_classes=["flat", "circular"])
+            button.connect("clicked", lambda _b, symbol=symbol: self.emit('vowel-activated', symbol))
+            fixed.put(button, x - BUTTON_SIZE / 2, y - BUTTON_SIZE / 2)
+        self.add_overlay(fixed)
+
+    def draw(self, area, cr, width, height):
+        color = area.get_color()
+        cr.set_source_rgba(color.red, color.green, color.blue, 0.3)
+        cr.set_line_width(1.5)
+        # outline, then the close-mid and open-mid lines and the central line
+        corners = [position(0, 0), position(0, 2), position(6, 2), position(6, 0)]
+        cr.move_to(*corners[-1])
+        for corner in corners:
+            cr.line_to(*corner)
+        for row in (2, 4):
+            cr.move_to(*position(row, 0))
+            cr.line_to(*position(row, 2))
+        cr.move_to(*position(0, 1))
+        cr.line_to(*position(6, 1))
+        cr.stroke()
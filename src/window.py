@@ -17,23 +17,105 @@
 #
 # SPDX-License-Identifier: GPL-3.0-or-later
 
-from gi.repository import Adw, Gtk, Gio, GObject
-import json
+from gi.repository import Adw, Gdk, Gtk, Gio, GLib, GObject, Pango
+import datetime
+import sqlite3
+import threading
+from . import (backends, content_filter, dictionaries, export, features, languages, links, online, share, speech, storage,
+               symbols)
+from .accent_comparison import Word2ipaAccentComparison, compared_accents
+from .batch import Word2ipaBatchDialog, words_of_text
+from .chart_render import render_chart
+from .comparison import ComparisonBasket, Word2ipaComparisonDialog
+from .consonant_table import Word2ipaConsonantTable
+from .core import clean_input, collation, lookup_word, normalize, notation, phonemes, respelling, word_of_the_day
+from .favorites_page import Word2ipaFavoritesPage
+from .global_search import Word2ipaGlobalSearchDialog
+from .langpacks import LanguagePacks
+from .pinned_cards import Word2ipaPinnedCards
+from .practice_page import Word2ipaPracticePage
+from .preferences import present_entry_dialog
+from .sound_changes import Word2ipaSoundChangesDialog
+from .stats import Stats
+from .state import AppState
+from .symbol_page import Word2ipaSymbolPage
+from .userdict import SOURCE_ONLINE
+from .vowel_chart import Word2ipaVowelChart
+from .word_result import set_star_state
+
+# shown under the entry instead of looking up unusable input
+INPUT_HINTS = {
+    "empty": _("Type a word to see its pronunciation"),
+    "control": _("The word contains invisible characters, try typing it again"),
+    "no-letters": _("Only words can be transcribed, not emoji or symbols"),
+}
+
+# how long typing has to pause before the result follows it, in ms
+LIVE_LOOKUP_DELAY = 300
+
 
 @Gtk.Template(resource_path='/io/github/mohfy/word2ipa/window.ui')
 class Word2ipaWindow(Adw.ApplicationWindow):
     __gtype_name__ = 'Word2ipaWindow'
     selected_lang = "en_US"
+    stack = Gtk.Template.Child()
     ipa_dict_list = Gtk.Template.Child()
+    symbol_navigation = Gtk.Template.Child()
     word_text = Gtk.Template.Child()
     ipa_text = Gtk.Template.Child()
+    respelling_label = Gtk.Template.Child()
+    online_label = Gtk.Template.Child()
+    case_label = Gtk.Template.Child()
+    level_label = Gtk.Template.Child()
+    listen_button = Gtk.Template.Child()
+    input_hint = Gtk.Template.Child()
+    star_button = Gtk.Template.Child()
     language_changer = Gtk.Template.Child()
+    dictionary_spinner = Gtk.Template.Child()
+    dictionary_source_button = Gtk.Template.Child()
+    dictionary_source_label = Gtk.Template.Child()
+    compare_accents_row = Gtk.Template.Child()
+    accent_comparison = Gtk.Template.Child()
+    rhymes_box = Gtk.Template.Child()
+    rhymes_row = Gtk.Template.Child()
     history = Gtk.Template.Child()
+    history_list = Gtk.Template.Child()
+    history_search_entry = Gtk.Template.Child()
+    pinned_cards = Gtk.Template.Child()
+    consonant_table = Gtk.Template.Child()
+    symbol_filters = Gtk.Template.Child()
+    symbol_search_bar = Gtk.Template.Child()
+    symbol_search_entry = Gtk.Template.Child()
+    toast_overlay = Gtk.Template.Child()
+    result_status = Gtk.Template.Child()
+    status_button = Gtk.Template.Child()
+    comparison_button = Gtk.Template.Child()
+    word_of_the_day_group = Gtk.Template.Child()
+    word_of_the_day_row = Gtk.Template.Child()
+    word_of_the_day_listen_button = Gtk.Template.Child()
+    word_of_the_day_star_button = Gtk.Template.Child()
 
     def __init__(self, **kwargs):
         super().__init__(**kwargs)
         self.init_template()
 
+        self.state = AppState.get_default()
+        self.state.connect("notify::dictionary", self.on_dictionary_changed)
+        self.state.connect("dictionary-failed", self.on_dictionary_failed)
+        self.pending_word = None
+        # the word whose result is shown or was not found
+        self.looked_up_word = None
+        # (language, word) the accent comparison is for
+        self.accent_comparison_key = None
+        # what the button of result_status does, if it has one
+        self.status_callback = None
+        self.rhyme_rows = []
+        # (lang, day) the word of the day was picked for, and the word
+        self.word_of_the_day_for = None
+        self.word_of_the_day = None
+
+        self.language_changer.set_model(Gtk.StringList.new(languages.display_names()))
+        LanguagePacks.get_default().connect("changed", self.on_language_packs_changed)
 
         # for searching in lang selector
         expr = Gtk.ClosureExpression.new(
@@ -44,49 +126,856 @@ class Word2ipaWindow(Adw.ApplicationWindow):
         self.language_changer.set_expression(expr)
 
         # init IPA Dictionary
-        ipa_dict_json = Gio.resources_lookup_data(f"/io/github/mohfy/word2ipa/dicts/ipa_lookup_table.json", Gio.ResourceLookupFlags.NONE).get_data().decode("utf-8")
-        ipa_data = json.loads(ipa_dict_json)
+        ipa_data = symbols.load_table()
+        self.ipa_data = ipa_data
+        self.consonant_table.set_entries(ipa_data)
+        # the sounds of a constructed language, which the table is limited to
+        self.inventory = None
+        # symbol -> row, for filtering the table
+        self.symbol_rows = {}
+        self.symbol_entries = {}
+        # shown when hovering a sound of the result
+        self.symbol_descriptions = {}
+        # symbol -> (lang, [(word, ipa)]) shown on the page of each symbol
+        self.symbol_examples = {}
+        self.bundled_examples = {}
+        self.symbol_features = {}
+        # what the search on the IPA Lookup page matches for each symbol
+        self.symbol_search_text = {}
         for ipa_info in ipa_data:
-            ipa_info_row = Adw.ExpanderRow()
-            ipa_info_row.set_title(ipa_info["symbol"])
-            ipa_info_row.set_subtitle(ipa_info["sound"])
+            ipa_info_row = Adw.ActionRow(title=ipa_info["symbol"], subtitle=ipa_info["sound"], activatable=True)
+            ipa_info_row.add_suffix(Gtk.Image(icon_name="go-next-symbolic"))
+            ipa_info_row.connect("activated", lambda _r, symbol=ipa_info["symbol"]: self.show_symbol(symbol))
+            self.ipa_dict_list.add(ipa_info_row)
+            self.symbol_rows[ipa_info["symbol"]] = ipa_info_row
+            self.symbol_entries[ipa_info["symbol"]] = ipa_info
+            self.symbol_descriptions[ipa_info["symbol"]] = symbols.describe(ipa_info, ipa_data)
+            self.symbol_features[ipa_info["symbol"]] = symbols.features(ipa_info)
+            # old symbols like ʤ find the ones written now
+            self.symbol_search_text[ipa_info["symbol"]] = " ".join(
+                [ipa_info["symbol"], ipa_info["sound"]] + ipa_info["examples"]
+                + notation.former_symbols(ipa_info["symbol"])).casefold()
 
-            for id, example in enumerate(ipa_info["examples"]):
-                example_row = Adw.ActionRow()
-                example_row.set_title(f'{example} => {ipa_info["ipa_examples"][id]}')
-                ipa_info_row.add_row(example_row)
+            examples = list(zip(ipa_info["examples"], ipa_info["ipa_examples"]))
+            self.bundled_examples[ipa_info["symbol"]] = examples
+            self.symbol_examples[ipa_info["symbol"]] = ("en_US", examples)
 
-            self.ipa_dict_list.add(ipa_info_row)
+        # touch screens have no hover, a long press shows the description instead
+        self.symbol_popover = Gtk.Popover(child=Gtk.Label(wrap=True, max_width_chars=30))
+        self.symbol_popover.set_parent(self.ipa_text)
+        long_press = Gtk.GestureLongPress(touch_only=True)
+        long_press.connect("pressed", self.on_result_long_pressed)
+        self.ipa_text.add_controller(long_press)
+
+        self.active_filters = set()
+        for feature, label in symbols.FILTERS:
+            chip = Gtk.ToggleButton(label=label, css_classes=["pill", "small"])
+            chip.connect("toggled", self.on_symbol_filter_toggled, feature)
+            self.symbol_filters.append(chip)
+
+        self.state.user_dict.connect("changed", self.on_user_dict_changed)
+
+        self.history_model = self.state.history
+        self.history_filter = Gtk.CustomFilter.new(self.history_matches)
+        self.history_list.bind_model(Gtk.FilterListModel.new(self.history_model.store, self.history_filter),
+                                     self.create_history_row)
+        self.history_list.set_placeholder(
+            Gtk.Label(label=_("No matching lookups"), margin_top=12,
+                      margin_bottom=12, css_classes=["dim-label"]))
+        self.history_model.store.connect("items-changed", lambda store, *_args: self.update_history_visibility())
+        self.update_history_visibility()
+        self.connect("close-request", self.on_close_request)
+
+        self.favorites = self.state.favorites
+        self.favorites.connect("changed", self.on_favorites_changed)
+
+        self.comparison = ComparisonBasket.get_default()
+        self.comparison.connect("changed", self.on_comparison_changed)
+
+        self.settings = self.state.settings
+        self.set_default_size(self.settings.get_int("window-width"),
+                              self.settings.get_int("window-height"))
+        if self.settings.get_boolean("window-maximized"):
+            self.maximize()
+        self.settings.connect("changed::transcription-profiles", self.on_transcription_settings_changed)
+        self.settings.connect("changed::optional-sounds", self.on_transcription_settings_changed)
+        self.settings.connect("changed::unicode-normalization", self.on_transcription_settings_changed)
+        self.settings.connect("changed::syllable-dots", lambda *_args: self.refresh_result())
+        self.settings.connect("changed::respelling", lambda *_args: self.refresh_result())
+        self.settings.connect("changed::accents", lambda *_args: self.update_accent_comparison())
+        self.settings.connect("changed::morpheme-boundaries", self.on_transcription_settings_changed)
+        self.settings.connect("changed::dictionary-examples", lambda *_args: self.update_symbol_examples())
+        self.settings.connect("changed::forvo-audio", lambda *_args: self.update_listen_button())
+        self.settings.connect("changed::experimental-features", lambda *_args: self.sound_changes_action.set_enabled(
+            features.is_enabled("sound-changes")))
+        self.update_listen_button()
+
+        self.state.select_language(self.lang_code)
+
+        if storage.stale_locks:
+            self.show_toast(_("Word2ipa was closed while saving, recent changes may be missing"))
+
+        # the result follows the entry while typing, Enter adds it to history
+        self.live_lookup_id = 0
+        self.word_text.connect("changed", self.on_word_text_changed)
+
+        # pages and dialogs ask the window to navigate through these
+        look_up = Gio.SimpleAction.new("look-up", GLib.VariantType.new("(ss)"))
+        look_up.connect("activate", self.on_look_up_action)
+        self.add_action(look_up)
+        show_symbol = Gio.SimpleAction.new("show-symbol", GLib.VariantType.new("s"))
+        show_symbol.connect("activate", self.on_show_symbol_action)
+        self.add_action(show_symbol)
+        show_page = Gio.SimpleAction.new("show-page", GLib.VariantType.new("s"))
+        show_page.connect("activate", lambda _action, parameter: self.show_page(parameter.unpack()))
+        self.add_action(show_page)
+        open_link = Gio.SimpleAction.new("open-link", GLib.VariantType.new("s"))
+        open_link.connect("activate", self.on_open_link_action)
+        self.add_action(open_link)
+
+        # keyboard shortcuts, see main.py for their accelerators
+        for name, callback in [
+            ("focus-entry", self.on_focus_entry_action),
+            ("copy-result", self.on_copy_result_action),
+            ("paste-look-up", self.on_paste_look_up_action),
+            ("search-symbols", self.on_search_symbols_action),
+            ("search-all-languages", self.on_search_all_languages_action),
+        ]:
+            action = Gio.SimpleAction.new(name, None)
+            action.connect("activate", callback)
+            self.add_action(action)
+
+        # experimental, hidden from the menu unless turned on in Preferences
+        self.sound_changes_action = Gio.SimpleAction.new("sound-changes", None)
+        self.sound_changes_action.connect("activate", self.on_sound_changes_action)
+        self.sound_changes_action.set_enabled(features.is_enabled("sound-changes"))
+        self.add_action(self.sound_changes_action)
+
+    def on_close_request(self, window):
+        # don't lose the lookups still waiting to be written
+        self.history_model.flush()
+        Stats.get_default().flush()
+        # the default size is the unmaximized one
+        width, height = self.get_default_size()
+        self.settings.set_int("window-width", width)
+        self.settings.set_int("window-height", height)
+        self.settings.set_boolean("window-maximized", self.is_maximized())
+        return False
+
+    def show_page(self, name):
+        self.stack.set_visible_child_name(name)
+        self.present()
+
+    def set_language(self, code):
+        """Switch to the dictionary of code for this session."""
+        codes = [lang_code for lang_code, _name in languages.available()]
+        if code in codes:
+            self.language_changer.set_selected(codes.index(code))
+
+    @property
+    def lang_code(self):
+        return languages.parse_code(self.selected_lang)
+
+    @property
+    def dictionary(self):
+        return self.state.dictionary
+
+    def lookup(self, word):
+        return self.state.lookup(word)
+
+    def display(self, ipa, lang=None, word=None):
+        return self.state.display(ipa, lang, word)
+
+    def show_toast(self, title):
+        self.toast_overlay.add_toast(Adw.Toast(title=title))
+
+    def on_dictionary_failed(self, state, error):
+        self.dictionary_spinner.set_visible(False)
+        self.hide_result()
+        self.show_status("dialog-error-symbolic", _("Could Not Load the Dictionary"),
+                         _("The {} dictionary may be damaged or missing").format(
+                             languages.display_name(state.lang)),
+                         _("_Retry"), self.state.load_dictionary)
+        print(f"could not load {state.lang} dictionary: {error}")
+
+    def show_status(self, icon_name, title, description, button_label=None, callback=None):
+        """Show a state of the lookup page that has no result, with an
+        optional button to get out of it.
+        """
+        self.result_status.set_icon_name(icon_name)
+        self.result_status.set_title(title)
+        self.result_status.set_description(GLib.markup_escape_text(description))
+        self.status_button.set_label(button_label or "")
+        self.status_button.set_visible(callback is not None)
+        self.status_callback = callback
+        self.result_status.set_visible(True)
+
+    @Gtk.Template.Callback()
+    def on_status_button_clicked(self, button):
+        if self.status_callback is not None:
+            self.status_callback()
+
+    def show_not_found(self, word):
+        lang = self.lang_code
+        self.hide_result()
+        self.show_status("edit-find-symbolic", _("No Results"),
+                         _("“{word}” is not in the {language} dictionary").format(
+                             word=word, language=languages.display_name(lang)),
+                         _("_Add to User Dictionary"),
+                         lambda: present_entry_dialog(self, lang, word))
+
+    def on_dictionary_changed(self, state, pspec):
+        loading = self.dictionary is None
+        self.dictionary_spinner.set_visible(loading)
+        if loading:
+            self.dictionary_source_button.set_visible(False)
+            self.word_of_the_day_group.set_visible(False)
+            return
+
+        # replaces an error shown while the dictionary couldn't be loaded
+        if self.looked_up_word is None:
+            self.show_status("input-keyboard-symbolic", _("Enter a Word"),
+                             _("Type a word above to see how it is pronounced"))
+        else:
+            self.refresh_result()
+
+        self.update_dictionary_source()
+        self.update_symbol_examples()
+        self.update_word_of_the_day()
+        self.update_inventory()
+        if self.pending_word is not None:
+            self.word_text.set_text(self.pending_word)
+            self.pending_word = None
+            self.on_entryrow_apply(self.word_text)
+
+    def update_inventory(self):
+        inventory = LanguagePacks.get_default().inventory(self.lang_code)
+        if inventory is None and self.inventory is None:
+            return
+        self.inventory = set(inventory) if inventory is not None else None
+        self.consonant_table.set_entries([entry for entry in self.ipa_data
+                                          if self.inventory is None or entry["symbol"] in self.inventory])
+        self.filter_symbols()
+
+    def update_dictionary_source(self):
+        metadata = self.dictionary.metadata
+        self.dictionary_source_button.set_visible(bool(metadata.get("source")))
+        if not metadata.get("source"):
+            return
+        source = GLib.markup_escape_text(metadata["source"])
+        if url := metadata.get("url"):
+            source = f'<a href="{GLib.markup_escape_text(url)}">{source}</a>'
+        lines = [_("Pronunciations from {source}").format(source=source)]
+        if license := metadata.get("license"):
+            lines.append(_("License: {license}").format(license=GLib.markup_escape_text(license)))
+        self.dictionary_source_label.set_label("\n".join(lines))
+
+    def on_word_text_changed(self, word_text):
+        if self.live_lookup_id:
+            GLib.source_remove(self.live_lookup_id)
+        self.live_lookup_id = GLib.timeout_add(LIVE_LOOKUP_DELAY, self.on_live_lookup_timeout)
+
+    def on_live_lookup_timeout(self):
+        self.live_lookup_id = 0
+        self.look_up_text(commit=False)
+        return GLib.SOURCE_REMOVE
+
+    def cancel_live_lookup(self):
+        if self.live_lookup_id:
+            GLib.source_remove(self.live_lookup_id)
+            self.live_lookup_id = 0
 
     @Gtk.Template.Callback()
     def on_entryrow_apply(self, word_text):
-        lang = self.selected_lang
-        if "(" in lang and ")" in lang:
-            code = lang[lang.find("(")+1 : lang.find(")")]
+        self.cancel_live_lookup()
+        self.look_up_text(commit=True)
+
+    def look_up_text(self, commit):
+        """Look up the text of the entry. Only a committed lookup, from
+        pressing Enter, is tidied up in the entry and kept in history;
+        the others follow the text while it is typed.
+        """
+        current, problem = clean_input(self.word_text.get_text())
+        if problem == "no-letters" and normalize.expand(current, self.lang_code) != current:
+            # numbers are looked up spelled out
+            problem = None
+        if problem == "empty" and not commit:
+            self.input_hint.set_visible(False)
+            self.looked_up_word = None
+            self.hide_result()
+            self.show_status("input-keyboard-symbolic", _("Enter a Word"),
+                             _("Type a word above to see how it is pronounced"))
+            self.update_accent_comparison()
+            self.update_rhymes()
+            self.update_word_of_the_day()
+            return
+        if problem:
+            # nothing that could be in a dictionary, so don't look it up
+            self.input_hint.set_label(INPUT_HINTS[problem])
+            self.input_hint.set_visible(True)
+            return
+        self.input_hint.set_visible(False)
+        if self.dictionary is None:
+            if commit:
+                # looked up as soon as the dictionary is ready
+                self.pending_word = current
+            return
+        if commit and current != self.word_text.get_text():
+            self.word_text.set_text(current)
+            # the tidied up text is looked up right here
+            self.cancel_live_lookup()
+
+        self.looked_up_word = current
+        if ipa := self.lookup(current):
+            ipa = self.display(ipa, word=current)
+            self.show_result(ipa)
+            if commit:
+                self.history_model.add(current, ipa, self.lang_code)
+                Stats.get_default().record_lookup(ipa)
+        elif commit and online.is_enabled():
+            # only on Enter, so typing doesn't send every prefix online
+            self.look_up_online(current)
         else:
-            code = lang.split()[-1]
+            self.show_not_found(current)
+        self.update_star_button()
+        self.update_accent_comparison()
+        self.update_rhymes()
+        self.update_word_of_the_day()
+
+    def look_up_online(self, word):
+        """Try Wiktionary for a word the dictionaries don't have."""
+        lang = self.lang_code
+        self.hide_result()
+        self.show_status("network-transmit-receive-symbolic", _("Searching Online"),
+                         _("“{}” is not in the dictionary, looking it up on Wiktionary").format(word))
+
+        def on_done(ipa, error):
+            if self.looked_up_word != word or self.lang_code != lang:
+                # another word was looked up meanwhile
+                return
+            if error is not None:
+                print(f"could not look up {word} online: {error}")
+            if ipa is None:
+                self.show_not_found(word)
+                return
+            ipa = self.display(ipa, word=word)
+            self.show_result(ipa)
+            self.update_star_button()
+            self.history_model.add(word, ipa, lang)
+            Stats.get_default().record_lookup(ipa)
+
+        self.state.online_results.look_up_async(word, lang, on_done)
+
+    def update_word_of_the_day(self):
+        """Feature a word on the lookup page while nothing is looked up."""
+        show = self.looked_up_word is None and self.dictionary is not None
+        self.word_of_the_day_group.set_visible(show)
+        if not show:
+            return
+        lang = self.lang_code
+        key = (lang, datetime.date.today())
+        if key != self.word_of_the_day_for:
+            self.word_of_the_day_for = key
+            self.word_of_the_day = word_of_the_day(self.dictionary, key[1],
+                                                   exclude=content_filter.blocked_words(lang))
+        if self.word_of_the_day is None:
+            self.word_of_the_day_group.set_visible(False)
+            return
+        word = self.word_of_the_day
+        self.word_of_the_day_row.set_title(word)
+        self.word_of_the_day_row.set_subtitle(self.display(self.lookup(word), word=word))
+        self.word_of_the_day_listen_button.set_sensitive(speech.can_speak_words())
+        set_star_state(self.word_of_the_day_star_button, self.favorites.contains(word, lang))
+
+    @Gtk.Template.Callback()
+    def on_word_of_the_day_activated(self, row):
+        self.word_text.set_text(self.word_of_the_day)
+        self.cancel_live_lookup()
+        self.look_up_text(commit=True)
 
-        resource_data = Gio.resources_lookup_data(f"/io/github/mohfy/word2ipa/dicts/{code}.json", Gio.ResourceLookupFlags.NONE)
-        current = word_text.get_text()
-        json_str = resource_data.get_data().decode("utf-8")
-        data = json.loads(json_str)
-        if ipa := data["entries"][0][current]:
-            self.ipa_text.show()
-            self.ipa_text.set_text(ipa)
+    @Gtk.Template.Callback()
+    def on_word_of_the_day_listen_clicked(self, button):
+        speech.speak_word(self.word_of_the_day, self.lang_code)
+
+    @Gtk.Template.Callback()
+    def on_word_of_the_day_star_clicked(self, button):
+        word = self.word_of_the_day
+        self.favorites.toggle(word, self.display(self.lookup(word), word=word), self.lang_code)
+
+    def create_history_row(self, item):
+        history_row = Adw.ActionRow()
+        history_row.set_title(item.ipa)
+        history_row.set_subtitle(item.word)
+        history_row.add_suffix(Gtk.Label(label=languages.display_name(item.lang)))
+        if item.count > 1:
+            history_row.add_suffix(Gtk.Label(label=f"×{item.count}",
+                                             tooltip_text=ngettext("Looked up once", "Looked up {} times",
+                                                                   item.count).format(item.count),
+                                             css_classes=["dim-label", "numeric"]))
+
+        star_button = Gtk.Button(valign=Gtk.Align.CENTER, css_classes=["flat", "circular"])
+        star_button.connect("clicked", lambda _b: self.favorites.toggle(item.word, item.ipa, item.lang))
+        set_star_state(star_button, self.favorites.contains(item.word, item.lang))
+        # the row outlives a single toggle, so follow later changes too
+        handler = self.favorites.connect("changed", lambda favorites: set_star_state(
+            star_button, favorites.contains(item.word, item.lang)))
+        history_row.connect("destroy", lambda _r: self.favorites.disconnect(handler))
+        history_row.add_suffix(star_button)
+
+        share_button = Gtk.Button(icon_name="send-to-symbolic",
+                                  tooltip_text=_("Share"),
+                                  valign=Gtk.Align.CENTER,
+                                  css_classes=["flat", "circular"])
+        share_button.connect("clicked", lambda _b: self.share_result(item.word, item.ipa))
+        history_row.add_suffix(share_button)
+
+        remove_button = Gtk.Button(icon_name="window-close-symbolic",
+                                   tooltip_text=_("Remove from History"),
+                                   valign=Gtk.Align.CENTER,
+                                   css_classes=["flat", "circular"])
+        remove_button.connect("clicked", lambda _b: self.history_model.remove(item))
+        history_row.add_suffix(remove_button)
+        return history_row
+
+    def update_star_button(self):
+        word = self.word_text.get_text()
+        found = self.dictionary is not None and self.lookup(word) is not None
+        self.star_button.set_sensitive(found)
+        set_star_state(self.star_button, self.favorites.contains(word, self.lang_code))
 
+    def on_favorites_changed(self, favorites):
+        if self.ipa_text.get_visible():
+            self.update_star_button()
+        if self.word_of_the_day_group.get_visible():
+            self.update_word_of_the_day()
 
-            history_row = Adw.ActionRow()
-            lang = Gtk.Label(label=self.selected_lang)
-            history_row.set_title(ipa)
-            history_row.set_subtitle(current)
+    def update_accent_comparison(self):
+        """Compare the looked up word with the other accents, once their
+        dictionaries are loaded on a worker thread.
+        """
+        code = self.lang_code
+        others = compared_accents(self.settings, code)
+        self.compare_accents_row.set_visible(bool(others))
+        # the word of the last lookup, not the text still being typed
+        word = self.looked_up_word
+        ipa = self.lookup(word) if self.dictionary is not None and word else None
+        self.accent_comparison_key = (code, word)
+        if not self.compare_accents_row.get_active() or not others or ipa is None:
+            self.accent_comparison.set_visible(False)
+            return
+        found = {}
 
-            history_row.add_suffix(lang)
-            self.history.add(history_row)
+        def on_loaded(dictionary, error, other):
+            if self.accent_comparison_key != (code, word):
+                # another word or language was looked up meanwhile
+                return
+            if error:
+                print(f"could not load {other} dictionary: {error}")
+                found[other] = None
+            else:
+                sources = [backends.chain(other, dictionary, online=False).for_language(other)]
+                found[other] = lookup_word(word, sources)[0]
+            if len(found) < len(others):
+                return
+            # only the accents that have the word
+            accents = [(code, self.display(ipa))] + [(other, self.display(found[other], other))
+                                                     for other in others if found[other] is not None]
+            if len(accents) > 1:
+                self.accent_comparison.compare(accents)
+            self.accent_comparison.set_visible(len(accents) > 1)
 
+        for other in others:
+            dictionaries.load_async(other, lambda dictionary, error, other=other: on_loaded(dictionary, error, other))
+
+    def update_rhymes(self):
+        for row in self.rhyme_rows:
+            self.rhymes_row.remove(row)
+        self.rhyme_rows = []
+        rhymes = self.dictionary.rhymes(self.word_text.get_text()) if self.dictionary is not None else []
+        rhymes = collation.sort_words(rhymes, self.lang_code, word=lambda rhyme: rhyme[0])
+        for word, ipa in rhymes:
+            row = Adw.ActionRow(title=word, subtitle=self.display(ipa), activatable=True,
+                                action_name="win.look-up",
+                                action_target=GLib.Variant("(ss)", (word, self.lang_code)))
+            self.rhymes_row.add_row(row)
+            self.rhyme_rows.append(row)
+        self.rhymes_row.set_subtitle(
+            ngettext("{} word", "{} words", len(rhymes)).format(len(rhymes)))
+        self.rhymes_box.set_visible(len(rhymes) > 0)
+
+    def look_up_word(self, word):
+        self.word_text.set_text(word)
+        self.on_entryrow_apply(self.word_text)
+
+    @Gtk.Template.Callback()
+    def on_export_chart_clicked(self, button):
+        learning = {expected for expected, _given, _count in Stats.get_default().top_confusions(10)}
+        dialog = Adw.AlertDialog(heading=_("Export Charts"),
+                                 body=_("Choose which symbols to include in the printable charts"))
+        dialog.add_response("cancel", _("_Cancel"))
+        dialog.add_response("learning", _("_Sounds I Confuse"))
+        dialog.add_response("language", languages.display_name(self.lang_code))
+        dialog.add_response("all", _("_All Symbols"))
+        dialog.set_response_enabled("learning", len(learning) > 0)
+        dialog.set_response_enabled("language", self.dictionary is not None)
+        dialog.set_default_response("all")
+        dialog.set_close_response("cancel")
+
+        def on_response(dialog, response):
+            if response == "cancel":
+                return
+            title = {
+                "all": _("IPA Charts"),
+                "language": languages.display_name(self.lang_code),
+                "learning": _("Sounds I Confuse"),
+            }[response]
+            export.choose_save_path(self, "ipa-charts.pdf",
+                                    lambda path: self.export_chart(path, title, response, learning),
+                                    filters=[export.file_filter(_("PDF"), "*.pdf"),
+                                             export.file_filter(_("PNG Image"), "*.png")])
+
+        dialog.connect("response", on_response)
+        dialog.present(self)
+
+    def export_chart(self, path, title, which, learning):
+        if which == "all":
+            render_chart(path, self.ipa_data, title)
+        elif which == "learning":
+            render_chart(path, self.ipa_data, title, learning)
         else:
-            self.ipa_text.set_text("IPA translation will appear here.")
+            dictionary = self.dictionary
+
+            def worker():
+                # every transcription has to be read to know the sounds
+                sounds = phonemes.inventory(ipa for _word, ipa in dictionary.items())
+                GLib.idle_add(render_chart, path, self.ipa_data, title, sounds)
+
+            threading.Thread(target=worker, daemon=True).start()
+
+    def on_look_up_action(self, action, parameter):
+        """win.look-up((word, lang)): show the lookup page with word's result."""
+        word, lang = parameter.unpack()
+        self.set_language(lang)
+        self.stack.set_visible_child_name("Word to IPA")
+        self.look_up_word(word)
+
+    def on_show_symbol_action(self, action, parameter):
+        """win.show-symbol(sound): open the IPA Lookup entry of sound."""
+        if symbol := self.find_symbol(parameter.unpack()):
+            self.show_symbol(symbol)
+
+    def find_symbol(self, sound):
+        """The IPA Lookup entry for sound, if there is one."""
+        # the table lists English vowels with their usual length mark
+        for symbol in (sound, sound + "ː", sound[0]):
+            if symbol in self.symbol_rows:
+                return symbol
+        return None
+
+    def show_symbol(self, symbol):
+        """Open the page of symbol on top of the IPA Lookup table."""
+        self.symbol_navigation.pop_to_tag("symbols")
+        self.push_symbol_page(symbol)
+        self.stack.set_visible_child_name("page2")
+
+    def push_symbol_page(self, symbol):
+        lang, examples = self.symbol_examples[symbol]
+        self.symbol_navigation.push(Word2ipaSymbolPage(self.symbol_entries[symbol], self.ipa_data,
+                                                       lang, examples, self.push_symbol_page))
+
+    def show_result(self, ipa):
+        """Show ipa split into syllables, with the unstressed ones dimmed
+        and every sound linked to its IPA Lookup entry.
+        """
+        self.ipa_text.set_markup(phonemes.format_syllables(
+            ipa, self.settings.get_boolean('syllable-dots'), self.syllable_markup))
+        self.ipa_text.set_visible(True)
+        self.result_status.set_visible(False)
+        # for readers who don't know IPA
+        show_respelling = (self.settings.get_boolean('respelling')
+                           and self.lang_code in respelling.LANGUAGES)
+        self.respelling_label.set_label(respelling.respell(ipa) if show_respelling else "")
+        self.respelling_label.set_visible(show_respelling)
+        _ipa, form, match = self.state.lookup_match(self.looked_up_word)
+        level = self.dictionary.level(form) if self.dictionary is not None and form is not None else None
+        self.level_label.set_label(level or "")
+        self.level_label.set_visible(level is not None)
+        self.online_label.set_visible(self.state.is_online_result(self.looked_up_word))
+        # say when the word wasn't found as typed
+        if match == "lowercase":
+            self.case_label.set_label(_("Found as “{}”").format(form))
+        elif match == "proper-noun":
+            self.case_label.set_label(_("Found as the name “{}”").format(form))
+        elif match == "spelled-out":
+            self.case_label.set_label(_("Read as “{}”").format(form))
+        elif match == "composed":
+            self.case_label.set_label(_("Put together from {}, not in the dictionary as a whole").format(form))
+        self.case_label.set_visible(match in ("lowercase", "proper-noun", "spelled-out", "composed"))
+
+    def hide_result(self):
+        self.ipa_text.set_visible(False)
+        self.respelling_label.set_visible(False)
+        self.level_label.set_visible(False)
+        self.online_label.set_visible(False)
+        self.case_label.set_visible(False)
+
+    def history_matches(self, item):
+        query = self.history_search_entry.get_text().strip().casefold()
+        return not query or query in item.word.casefold() or query in item.ipa.casefold()
+
+    @Gtk.Template.Callback()
+    def on_history_search_changed(self, entry):
+        self.history_filter.changed(Gtk.FilterChange.DIFFERENT)
+
+    def update_history_visibility(self):
+        self.history.set_visible(self.history_model.store.get_n_items() > 0)
+
+    def syllable_markup(self, marks, sounds):
+        markup = []
+        for sound in sounds:
+            escaped = GLib.markup_escape_text(sound)
+            if symbol := self.find_symbol(sound):
+                # the title is the tooltip of the link
+                markup.append(f'<a href="{GLib.markup_escape_text(symbol)}" '
+                              f'title="{GLib.markup_escape_text(self.symbol_descriptions[symbol])}">{escaped}</a>')
+            else:
+                markup.append(escaped)
+        alpha = {"ˈ": "100%", "ˌ": "80%"}.get(marks.lstrip(phonemes.MORPHEME_BOUNDARY), "55%")
+        return f'<span fgalpha="{alpha}">{"".join(markup)}</span>'
+
+    def on_result_long_pressed(self, gesture, x, y):
+        offset_x, offset_y = self.ipa_text.get_layout_offsets()
+        layout = self.ipa_text.get_layout()
+        inside, index, _trailing = layout.xy_to_index(int((x - offset_x) * Pango.SCALE),
+                                                      int((y - offset_y) * Pango.SCALE))
+        if not inside:
+            return
+        text = layout.get_text()
+        position = len(text.encode("utf-8")[:index].decode("utf-8", errors="ignore"))
+        if position >= len(text):
+            return
+        # two-letter symbols like tʃ first, whichever half was pressed
+        candidates = [text[position:position + 2], text[max(position - 1, 0):position + 1]]
+        symbol = next((sound for sound in candidates if sound in self.symbol_descriptions), None)
+        symbol = symbol or self.find_symbol(text[position])
+        if symbol is None:
+            return
+        self.symbol_popover.get_child().set_label(self.symbol_descriptions[symbol])
+        rect = Gdk.Rectangle()
+        rect.x, rect.y, rect.width, rect.height = int(x), int(y), 1, 1
+        self.symbol_popover.set_pointing_to(rect)
+        self.symbol_popover.popup()
+
+    @Gtk.Template.Callback()
+    def on_result_link_activated(self, label, uri):
+        self.show_symbol(uri)
+        return True
+
+    @Gtk.Template.Callback()
+    def on_symbol_activated(self, chart, symbol):
+        symbol = self.find_symbol(symbol)
+        if symbol is None:
+            return
+        self.show_symbol(symbol)
+        lang, examples = self.symbol_examples[symbol]
+        if examples:
+            speech.speak(examples[0][0], lang)
+
+    def on_symbol_filter_toggled(self, chip, feature):
+        if chip.get_active():
+            self.active_filters.add(feature)
+        else:
+            self.active_filters.discard(feature)
+        self.filter_symbols()
+
+    @Gtk.Template.Callback()
+    def on_symbol_search_changed(self, entry):
+        self.filter_symbols()
+
+    @Gtk.Template.Callback()
+    def on_symbol_search_stopped(self, entry):
+        entry.set_text("")
+        self.symbol_search_bar.set_search_mode(False)
+
+    def filter_symbols(self):
+        query = self.symbol_search_entry.get_text().strip().casefold()
+        # "voiceless fricatives" finds the symbols with those features
+        natural_class = symbols.natural_class(query)
+        # a symbol has to have every selected feature
+        for symbol, row in self.symbol_rows.items():
+            if self.inventory is not None and symbol not in self.inventory:
+                matches = False
+            elif natural_class is not None:
+                matches = self.active_filters | natural_class <= self.symbol_features[symbol]
+            else:
+                matches = self.active_filters <= self.symbol_features[symbol] and query in self.symbol_search_text[symbol]
+            row.set_visible(matches)
+
+    def on_focus_entry_action(self, action, parameter):
+        self.stack.set_visible_child_name("Word to IPA")
+        self.word_text.grab_focus()
+
+    def on_copy_result_action(self, action, parameter):
+        if self.ipa_text.get_visible():
+            self.get_clipboard().set(self.ipa_text.get_text())
+            self.show_toast(_("Copied to clipboard"))
+
+    def update_listen_button(self):
+        self.listen_button.set_sensitive(speech.can_speak_words())
+
+    @Gtk.Template.Callback()
+    def on_listen_clicked(self, button):
+        if self.looked_up_word is not None:
+            speech.speak_word(self.looked_up_word, self.lang_code)
+
+    @Gtk.Template.Callback()
+    def on_share_clicked(self, button):
+        if self.looked_up_word is not None and self.ipa_text.get_visible():
+            self.share_result(self.looked_up_word, self.ipa_text.get_text())
+
+    def share_result(self, word, ipa):
+        def on_failed(text):
+            # still easy to paste wherever it was meant to go
+            self.get_clipboard().set(text)
+            self.show_toast(_("Could not share, copied to clipboard instead"))
+
+        share.share(self, word, ipa, on_failed)
+
+    def on_open_link_action(self, action, parameter):
+        if self.looked_up_word is not None:
+            links.open_word(self, parameter.unpack(), self.looked_up_word, self.lang_code)
+
+    def on_paste_look_up_action(self, action, parameter):
+        self.get_clipboard().read_text_async(None, self.on_paste_look_up_read)
+
+    def on_paste_look_up_read(self, clipboard, result):
+        try:
+            text = clipboard.read_text_finish(result)
+        except GLib.Error as e:
+            print(f"could not read the clipboard: {e.message}")
+            return
+        words = words_of_text(text or "")
+        if len(words) > 1:
+            # a whole paragraph goes to the file transcription dialog
+            Word2ipaBatchDialog(words, self.lang_code).present(self)
+        elif words:
+            self.stack.set_visible_child_name("Word to IPA")
+            self.look_up_word(words[0])
+
+    def on_search_symbols_action(self, action, parameter):
+        self.stack.set_visible_child_name("page2")
+        self.symbol_navigation.pop_to_tag("symbols")
+        self.symbol_search_bar.set_search_mode(True)
+        self.symbol_search_entry.grab_focus()
+
+    def on_search_all_languages_action(self, action, parameter):
+        Word2ipaGlobalSearchDialog(self.word_text.get_text().strip()).present(self)
+
+    def on_sound_changes_action(self, action, parameter):
+        Word2ipaSoundChangesDialog().present(self)
+
+    def update_symbol_examples(self):
+        """Take the symbol examples from the selected language if the
+        user asked for it, the bundled English ones otherwise.
+        """
+        if not self.settings.get_boolean('dictionary-examples') or self.dictionary is None:
+            for symbol, examples in self.bundled_examples.items():
+                self.symbol_examples[symbol] = ("en_US", examples)
+            return
+        dictionary, symbols = self.dictionary, list(self.symbol_rows)
+
+        def worker():
+            try:
+                found = {symbol: dictionary.examples(symbol) for symbol in symbols}
+            except sqlite3.Error as e:
+                print(f"could not find examples in {dictionary.lang}: {e}")
+                return
+            GLib.idle_add(on_found, found)
+
+        def on_found(found):
+            if dictionary is not self.dictionary or not self.settings.get_boolean('dictionary-examples'):
+                # changed while searching
+                return
+            for symbol, examples in found.items():
+                self.symbol_examples[symbol] = (dictionary.lang, examples)
+
+        # one query per symbol, too slow for the main loop on big dictionaries
+        threading.Thread(target=worker, daemon=True).start()
+
+    @Gtk.Template.Callback()
+    def on_compare_accents_toggled(self, row, pspec):
+        self.update_accent_comparison()
+
+    @Gtk.Template.Callback()
+    def on_pin_clicked(self, button):
+        word = self.word_text.get_text()
+        if ipa := self.lookup(word):
+            self.pinned_cards.pin(word, self.display(ipa), self.lang_code)
+
+    @Gtk.Template.Callback()
+    def on_compare_clicked(self, button):
+        word = self.word_text.get_text()
+        if ipa := self.lookup(word):
+            self.comparison.add(word, self.display(ipa), self.lang_code)
+
+    @Gtk.Template.Callback()
+    def on_show_comparison_clicked(self, button):
+        Word2ipaComparisonDialog().present(self)
+
+    def on_comparison_changed(self, comparison):
+        count = len(comparison)
+        self.comparison_button.set_visible(count > 0)
+        self.comparison_button.set_tooltip_text(
+            ngettext("Compare {} Word", "Compare {} Words", count).format(count))
+
+    @Gtk.Template.Callback()
+    def on_star_clicked(self, button):
+        word = self.word_text.get_text()
+        if ipa := self.lookup(word):
+            self.favorites.toggle(word, self.display(ipa), self.lang_code)
+
+    @Gtk.Template.Callback()
+    def on_clear_history_clicked(self, button):
+        self.history_model.clear()
+
+    @Gtk.Template.Callback()
+    def on_edit_clicked(self, button):
+        word = self.word_text.get_text()
+        ipa = ""
+        if self.dictionary is not None:
+            ipa = self.lookup(word) or ""
+        # saving a result found online keeps where it came from
+        source = SOURCE_ONLINE if ipa and self.state.is_online_result(word) else None
+        present_entry_dialog(self, self.lang_code, word, ipa, source)
+
+    def on_user_dict_changed(self, user_dict):
+        self.refresh_result()
+
+    def on_transcription_settings_changed(self, settings, key):
+        self.refresh_result()
+        self.update_accent_comparison()
+
+    def refresh_result(self):
+        # a word not found before may have been added to the user dictionary since
+        word = self.looked_up_word
+        if word is not None and self.dictionary is not None:
+            if ipa := self.lookup(word):
+                self.show_result(self.display(ipa, word=word))
+            else:
+                self.show_not_found(word)
+
+    def on_language_packs_changed(self, packs):
+        # keep the current language selected while the list is rebuilt
+        code = self.lang_code
+        codes = [lang_code for lang_code, _name in languages.available()]
+        self.language_changer.set_model(Gtk.StringList.new(languages.display_names()))
+        self.language_changer.set_selected(codes.index(code) if code in codes else 0)
 
     @Gtk.Template.Callback()
     def on_language_change(self, language_changer, pspec):
         self.selected_lang = language_changer.get_selected_item().get_string()
         print(f"lang changed: {self.selected_lang}")
+        self.compare_accents_row.set_visible(bool(compared_accents(self.state.settings, self.lang_code)))
+        self.accent_comparison.set_visible(False)
+        self.rhymes_box.set_visible(False)
+        self.state.select_language(self.lang_code)
@@ -0,0 +1,116 @@
+# word_lists.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gio, GLib, GObject
+import os
+from . import storage
+from .batch import read_word_list
+
+WORD_LISTS_FILE = 'word_lists.json'
+LIST_PATH = "/io/github/mohfy/word2ipa/dicts/word_lists/{}.txt"
+
+# id -> (language, name, resource file name) of the lists shipped with the app
+BUNDLED = {
+    "dolch-pre-primer": ("en", _("Sight Words: Pre-primer"), "en_dolch_pre_primer"),
+    "dolch-primer": ("en", _("Sight Words: Primer"), "en_dolch_primer"),
+}
+
+
+class WordLists(GObject.Object):
+    """Graded word lists practice can be limited to.
+
+    Besides the bundled ones, the user can import plain text lists for
+    any language; those are kept in XDG_DATA_HOME. Which list is used
+    is chosen per language in the "practice-word-lists" setting.
+    """
+    __gtype_name__ = 'Word2ipaWordLists'
+    __gsignals__ = {
+        'changed': (GObject.SignalFlags.RUN_FIRST, None, ()),
+    }
+
+    _default = None
+
+    @classmethod
+    def get_default(cls):
+        if cls._default is None:
+            cls._default = cls()
+        return cls._default
+
+    def __init__(self):
+        super().__init__()
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        # id -> {"lang", "name", "words"}
+        self.imported = storage.load_json(WORD_LISTS_FILE, {})
+
+    def lists_for(self, lang):
+        """Return (id, name) of the lists that can be used for lang."""
+        bundled = [(list_id, name) for list_id, (list_lang, name, _file) in BUNDLED.items()
+                   if list_lang == lang.split("_")[0]]
+        imported = [(list_id, info["name"]) for list_id, info in self.imported.items()
+                    if info["lang"] == lang]
+        return bundled + imported
+
+    def words(self, list_id):
+        """The lowercase words of a list, or None if there is no such list."""
+        if list_id in self.imported:
+            return set(self.imported[list_id]["words"])
+        if list_id not in BUNDLED:
+            return None
+        data = Gio.resources_lookup_data(LIST_PATH.format(BUNDLED[list_id][2]),
+                                         Gio.ResourceLookupFlags.NONE)
+        lines = data.get_data().decode("utf-8").splitlines()
+        return {line.strip().lower() for line in lines if line.strip() and not line.startswith("#")}
+
+    def import_file(self, path, lang):
+        """Add the words of a text file, one per line, as a list for lang.
+
+        Return (id of the list, count of characters that couldn't be read).
+        """
+        name = os.path.splitext(os.path.basename(path))[0]
+        list_id = f"{lang}:{name}"
+        words, unreadable = read_word_list(path)
+        self.imported[list_id] = {
+            "lang": lang,
+            "name": name,
+            "words": sorted({word.lower() for word in words}),
+        }
+        storage.save_json(WORD_LISTS_FILE, self.imported)
+        self.emit('changed')
+        return list_id, unreadable
+
+    def selected(self, lang):
+        """The id of the list practice in lang is limited to, or ""."""
+        return self.settings.get_value('practice-word-lists').unpack().get(lang, "")
+
+    def select(self, lang, list_id):
+        selected = self.settings.get_value('practice-word-lists').unpack()
+        selected[lang] = list_id
+        self.settings.set_value('practice-word-lists', GLib.Variant('a{ss}', selected))
+
+    def selected_words(self, lang):
+        """The words practice in lang may pick, None for the whole dictionary."""
+        return self.words(self.selected(lang)) if self.selected(lang) else None
+
+    def selected_level(self, lang):
+        """The difficulty level practice in lang is limited to, or None."""
+        return self.settings.get_value('practice-levels').unpack().get(lang) or None
+
+    def select_level(self, lang, level):
+        levels = self.settings.get_value('practice-levels').unpack()
+        levels[lang] = level or ""
+        self.settings.set_value('practice-levels', GLib.Variant('a{ss}', levels))
@@ -0,0 +1,82 @@
+# word_result.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+from gi.repository import Gtk
+from . import dictionaries, speech
+from .core import phonemes, respelling
+from .state import AppState
+
+
+def set_star_state(button, starred):
+    if starred:
+        button.set_icon_name("starred-symbolic")
+        button.set_tooltip_text(_("Remove from Favorites"))
+    else:
+        button.set_icon_name("non-starred-symbolic")
+        button.set_tooltip_text(_("Add to Favorites"))
+
+
+class Word2ipaWordResult(Gtk.Box):
+    """Everything known about one looked up word: its variants split
+    into syllables, a respelling and buttons to hear and star it.
+    """
+    __gtype_name__ = 'Word2ipaWordResult'
+
+    def __init__(self, word, ipa, lang, **kwargs):
+        super().__init__(orientation=Gtk.Orientation.VERTICAL, spacing=6,
+                         margin_top=6, margin_bottom=6, margin_start=6, margin_end=6,
+                         **kwargs)
+        state = AppState.get_default()
+        self.word = word
+        self.lang = lang
+        self.ipa = state.display(ipa, lang, word)
+        self.favorites = state.favorites
+
+        header = Gtk.Box(spacing=6)
+        header.append(Gtk.Label(label=word, xalign=0, selectable=True, css_classes=["title-2"]))
+        if level := dictionaries.load(lang).level(word):
+            header.append(Gtk.Label(label=level, valign=Gtk.Align.CENTER,
+                                    tooltip_text=_("Difficulty Level"),
+                                    css_classes=["caption-heading", "accent"]))
+        self.append(header)
+        dots = state.settings.get_boolean('syllable-dots')
+        for variant in phonemes.variants(self.ipa):
+            self.append(Gtk.Label(label=phonemes.format_syllables(variant, dots), xalign=0,
+                                  selectable=True, css_classes=["title-3"]))
+        if state.settings.get_boolean('respelling') and lang in respelling.LANGUAGES:
+            self.append(Gtk.Label(label=respelling.respell(self.ipa), xalign=0,
+                                  selectable=True, css_classes=["dim-label"]))
+
+        buttons = Gtk.Box(spacing=6, halign=Gtk.Align.END)
+        speak_button = Gtk.Button(icon_name="audio-volume-high-symbolic",
+                                  tooltip_text=_("Listen"),
+                                  sensitive=speech.can_speak_words(),
+                                  css_classes=["flat", "circular"])
+        speak_button.connect("clicked", lambda _b: speech.speak_word(word, lang))
+        buttons.append(speak_button)
+        self.star_button = Gtk.Button(css_classes=["flat", "circular"])
+        self.star_button.connect("clicked", lambda _b: self.favorites.toggle(word, self.ipa, lang))
+        buttons.append(self.star_button)
+        self.append(buttons)
+
+        self.update_star_button()
+        changed_id = self.favorites.connect("changed", lambda *_args: self.update_star_button())
+        self.connect("destroy", lambda *_args: self.favorites.disconnect(changed_id))
+
+    def update_star_button(self):
+        set_star_state(self.star_button, self.favorites.contains(self.word, self.lang))
@@ -0,0 +1,712 @@
+# test_core.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+import datetime
+import json
+import os
+import re
+import tempfile
+import unittest
+
+from core import (
+    BackendChain,
+    BackendError,
+    CompiledDictionary,
+    Dictionary,
+    DictionaryBackend,
+    DictionaryError,
+    RulesBackend,
+    TranscriptionBackend,
+    case_forms,
+    clean_input,
+    collation,
+    compile_dictionary,
+    compose,
+    csv_import,
+    encoding,
+    find_duplicates,
+    find_replace,
+    load_dictionary,
+    lookup_word,
+    mark_compound,
+    normalize,
+    notation,
+    orthography,
+    phonemes,
+    respelling,
+    similarity,
+    sort_levels,
+    sound_change,
+    wikitext,
+    word_of_the_day,
+    word_to_ipa,
+)
+
+
+class DictionaryTest(unittest.TestCase):
+
+    def test_parse_merges_entry_maps(self):
+        data = '{"entries": [{"cat": "/kæt/"}, {"dog": "/dɔɡ/"}]}'
+        dictionary = Dictionary.parse("en_US", data)
+        self.assertEqual(len(dictionary), 2)
+        self.assertEqual(dictionary.lookup("dog"), "/dɔɡ/")
+
+    def test_iteration_and_prefix(self):
+        dictionary = Dictionary("en_US", {"cat": "/kæt/", "catalog": "/ˈkætəlɔɡ/", "dog": "/dɔɡ/"})
+        self.assertEqual(sorted(dictionary), ["cat", "catalog", "dog"])
+        self.assertEqual(dictionary.prefix("cat"), [("cat", "/kæt/"), ("catalog", "/ˈkætəlɔɡ/")])
+        self.assertEqual(dictionary.prefix("", limit=1), [("cat", "/kæt/")])
+        self.assertEqual(dictionary.prefix("x"), [])
+
+    def test_load(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "en_US.json")
+            with open(path, "w", encoding="utf-8") as f:
+                f.write('{"entries": [{"cat": "/kæt/"}]}')
+            self.assertEqual(load_dictionary("en_US", path).lookup("cat"), "/kæt/")
+            compile_dictionary(Dictionary.load("en_US", path), os.path.join(tmp, "en_US.db"))
+            self.assertIsInstance(load_dictionary("en_US", os.path.join(tmp, "en_US.db")), CompiledDictionary)
+
+    def test_parse_accepts_bytes(self):
+        dictionary = Dictionary.parse("en_US", '{"entries": [{"ship": "/ʃɪp/"}]}'.encode())
+        self.assertIn("ship", dictionary)
+
+    def test_parse_reads_metadata(self):
+        data = '{"entries": [], "metadata": {"source": "ipa-dict", "license": "MIT"}}'
+        dictionary = Dictionary.parse("en_US", data)
+        self.assertEqual(dictionary.metadata["source"], "ipa-dict")
+        self.assertEqual(dictionary.attribution(), "en_US: ipa-dict (MIT)")
+
+    def test_attribution_needs_a_source(self):
+        self.assertIsNone(Dictionary("en_US", {}).attribution())
+
+    def test_parse_reads_levels(self):
+        dictionary = Dictionary.parse("en_US", '{"entries": [{"cat": "/kæt/", "feline": "/ˈfiːlaɪn/"}], "levels": {"cat": "A1", "feline": "C1"}}')
+        self.assertEqual(dictionary.level("cat"), "A1")
+        self.assertEqual(dictionary.levels(), ["A1", "C1"])
+        self.assertEqual(dictionary.random_words(5, level="C1"), ["feline"])
+
+    def test_levels_sort_cefr_first(self):
+        self.assertEqual(sort_levels(["Grade 2", "B1", "A2", "Grade 1"]),
+                         ["A2", "B1", "Grade 1", "Grade 2"])
+
+    def test_parse_rejects_invalid_data(self):
+        for data in ['not json', '{}', '{"entries": 1}']:
+            with self.assertRaises(DictionaryError):
+                Dictionary.parse("en_US", data)
+
+    def test_lookup_missing_word(self):
+        self.assertIsNone(Dictionary("en_US", {}).lookup("cat"))
+
+    def test_random_words_skips_non_words(self):
+        dictionary = Dictionary("en_US", {"cat": "/kæt/", "'em": "/əm/", "42": "", "dog": "/dɔɡ/"})
+        self.assertEqual(sorted(dictionary.random_words(10)), ["cat", "dog"])
+
+    def test_random_words_containing_whole_sounds(self):
+        dictionary = Dictionary("en_US", {"bit": "/bɪt/", "bite": "/baɪt/", "beat": "/biːt/"})
+        self.assertEqual(dictionary.random_words(10, containing=["ɪ"]), ["bit"])
+
+    def test_random_words_exclude(self):
+        dictionary = Dictionary("en_US", {"cat": "/kæt/", "Dog": "/dɔɡ/"})
+        self.assertEqual(dictionary.random_words(10, exclude={"dog"}), ["cat"])
+
+    def test_random_words_within(self):
+        dictionary = Dictionary("en_US", {"cat": "/kæt/", "dog": "/dɔɡ/", "cow": "/kaʊ/"})
+        self.assertEqual(sorted(dictionary.random_words(10, within={"cat", "cow", "owl"})), ["cat", "cow"])
+
+    def test_word_of_the_day_depends_on_date_only(self):
+        dictionary = Dictionary("en_US", {"cat": "/kæt/", "dog": "/dɔɡ/", "cow": "/kaʊ/", "owl": "/aʊl/"})
+        day = datetime.date(2025, 3, 1)
+        self.assertEqual(word_of_the_day(dictionary, day), word_of_the_day(dictionary, day))
+        days = [day + datetime.timedelta(days=offset) for offset in range(30)]
+        self.assertGreater(len({word_of_the_day(dictionary, other) for other in days}), 1)
+        self.assertIsNone(word_of_the_day(Dictionary("en_US", {"42": ""}), day))
+
+
+class CompiledDictionaryTest(unittest.TestCase):
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.path = os.path.join(self.tmp.name, "en_US.db")
+        compile_dictionary(Dictionary("en_US", {"cat": "/kæt/", "'em": "/əm/"},
+                                      {"source": "ipa-dict"}, {"cat": "A1"}), self.path)
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def test_lookup(self):
+        dictionary = CompiledDictionary("en_US", self.path)
+        self.assertEqual(len(dictionary), 2)
+        self.assertEqual(dictionary.lookup("cat"), "/kæt/")
+        self.assertIn("'em", dictionary)
+        self.assertIsNone(dictionary.lookup("dog"))
+
+    def test_close(self):
+        dictionary = CompiledDictionary("en_US", self.path)
+        dictionary.lookup("cat")
+        dictionary.close()
+        dictionary.close()
+        # opened again when used
+        self.assertEqual(dictionary.lookup("cat"), "/kæt/")
+
+    def test_iteration_and_prefix(self):
+        dictionary = CompiledDictionary("en_US", self.path)
+        self.assertEqual(list(dictionary), ["'em", "cat"])
+        self.assertEqual(dictionary.prefix("ca"), [("cat", "/kæt/")])
+        self.assertEqual(dictionary.prefix("", limit=1), [("'em", "/əm/")])
+        self.assertEqual(dictionary.prefix("cats"), [])
+
+    def test_metadata(self):
+        dictionary = CompiledDictionary("en_US", self.path)
+        self.assertEqual(dictionary.metadata, {"source": "ipa-dict"})
+
+    def test_levels(self):
+        dictionary = CompiledDictionary("en_US", self.path)
+        self.assertEqual(dictionary.level("cat"), "A1")
+        self.assertIsNone(dictionary.level("'em"))
+        self.assertEqual(dictionary.levels(), ["A1"])
+        self.assertEqual(dictionary.random_words(5, level="A1"), ["cat"])
+        self.assertEqual(dictionary.random_words(5, level="B2"), [])
+
+    def test_random_words_skips_non_words(self):
+        self.assertEqual(CompiledDictionary("en_US", self.path).random_words(5), ["cat"])
+
+    def test_random_words_containing(self):
+        dictionary = CompiledDictionary("en_US", self.path)
+        self.assertEqual(dictionary.random_words(5, containing=["æ"]), ["cat"])
+        self.assertEqual(dictionary.random_words(5, containing=["ʃ"]), [])
+        self.assertEqual(dictionary.random_words(5, exclude={"cat"}), [])
+
+
+class WordToIpaTest(unittest.TestCase):
+
+    def test_first_dictionary_wins(self):
+        user = Dictionary("en_US", {"often": "/ˈɒf(ə)n/"})
+        bundled = Dictionary("en_US", {"often": "/ˈɔfən/", "cat": "/kæt/"})
+        self.assertEqual(word_to_ipa("often", [user, bundled]), "/ˈɒf(ə)n/")
+        self.assertEqual(word_to_ipa("cat", [user, bundled]), "/kæt/")
+        self.assertIsNone(word_to_ipa("dog", [user, bundled]))
+
+    def test_case(self):
+        entries = {"cat": "/kæt/", "Paris": "/ˈpæɹɪs/", "IPA": "/ˌaɪpiːˈeɪ/", "iPhone": "/ˈaɪfoʊn/",
+                   "polish": "/ˈpɑlɪʃ/", "Polish": "/ˈpoʊlɪʃ/"}
+        dictionary = Dictionary("en_US", entries)
+        self.assertEqual(lookup_word("IPA", [dictionary]), ("/ˌaɪpiːˈeɪ/", "IPA", "exact"))
+        self.assertEqual(lookup_word("iPhone", [dictionary])[2], "exact")
+        self.assertEqual(lookup_word("Cat", [dictionary]), ("/kæt/", "cat", "lowercase"))
+        self.assertEqual(lookup_word("paris", [dictionary]), ("/ˈpæɹɪs/", "Paris", "proper-noun"))
+        self.assertEqual(lookup_word("PARIS", [dictionary])[1], "Paris")
+        self.assertEqual(lookup_word("Polish", [dictionary])[0], "/ˈpoʊlɪʃ/")
+        self.assertEqual(lookup_word("dog", [dictionary]), (None, None, None))
+        self.assertEqual(case_forms("cat"), [("cat", "exact"), ("Cat", "proper-noun")])
+
+
+class BackendTest(unittest.TestCase):
+
+    def test_priority(self):
+        user = DictionaryBackend(Dictionary("en_US", {"often": "/ˈɒf(ə)n/"}))
+        bundled = DictionaryBackend(Dictionary("en_US", {"often": "/ˈɔfən/, /ˈɔftən/", "cat": "/kæt/"}))
+        chain = BackendChain([user, bundled])
+        self.assertEqual(chain.transcribe("often", "en_US"), ["/ˈɒf(ə)n/"])
+        self.assertEqual(chain.transcribe_from("cat", "en_US"), (["/kæt/"], bundled))
+        self.assertEqual(BackendChain([bundled, user]).transcribe("often", "en_US"), ["/ˈɔfən/", "/ˈɔftən/"])
+        self.assertEqual(chain.transcribe("cat", "de_DE"), [])
+        self.assertEqual(chain.transcribe_from("dog", "en_US"), ([], None))
+
+    def test_failing_backend_is_skipped(self):
+        class Offline(TranscriptionBackend):
+            def transcribe(self, word, lang):
+                raise BackendError("no connection")
+
+        chain = BackendChain([Offline(), RulesBackend("tok", {"t": "t", "o": "o", "k": "k", "i": "i"})])
+        self.assertEqual(chain.transcribe("toki", "tok"), ["/toki/"])
+        # letters without a rule
+        self.assertEqual(chain.transcribe("pona", "tok"), [])
+
+    def test_as_dictionary(self):
+        chain = BackendChain([DictionaryBackend(Dictionary("en_US", {"Paris": "/ˈpæɹɪs/, /pɑˈɹi/"}))])
+        self.assertEqual(lookup_word("paris", [chain.for_language("en_US")]),
+                         ("/ˈpæɹɪs/, /pɑˈɹi/", "Paris", "proper-noun"))
+        self.assertIsNone(chain.for_language("en_US").lookup("dog"))
+
+
+class CsvImportTest(unittest.TestCase):
+
+    def test_guess_columns(self):
+        rows = csv_import.read_rows("/kæt/\tcat\n\n/dɒɡ/\tdog\n", "\t")
+        self.assertEqual(rows, [["/kæt/", "cat"], ["/dɒɡ/", "dog"]])
+        self.assertEqual(csv_import.guess_columns(rows), (1, 0, False))
+        rows = csv_import.read_rows("Pronunciation;Lemma;Notes\n/kæt/;cat;\n", ";")
+        self.assertEqual(csv_import.guess_columns(rows), (1, 0, True))
+
+    def test_map_rows(self):
+        rows = [["Word", "IPA"], ["cat", "/kæt/"], ["dog"], ["", "/x/"]]
+        self.assertEqual(csv_import.map_rows(rows, 0, 1, header=True), ([("cat", "/kæt/")], 2))
+
+    def test_check_symbols(self):
+        entries = [("cat", "/kæt/"), ("jam", "/ʤæm/"), ("dog", "/d0g/")]
+        self.assertEqual(csv_import.check_symbols(entries, strict=True), (entries[:2], {}, 1))
+        self.assertEqual(csv_import.check_symbols(entries, strict=False), (entries, {"dog": "0"}, 0))
+
+    def test_sniff_delimiter(self):
+        self.assertEqual(csv_import.sniff_delimiter("a;b\nc;d\n"), ";")
+        self.assertEqual(csv_import.sniff_delimiter("word"), ",")
+
+
+class EncodingTest(unittest.TestCase):
+
+    def test_detect(self):
+        self.assertEqual(encoding.detect("ˈkʌlər".encode("utf-8")), "utf-8")
+        self.assertEqual(encoding.detect("café".encode("utf-16")), "utf-16")
+        self.assertEqual(encoding.detect("café".encode("utf-16-le")), "utf-16-le")
+        self.assertEqual(encoding.detect("“café”".encode("cp1252")), "cp1252")
+        self.assertEqual(encoding.detect(b"caf\xe9 \x81"), "latin-1")
+
+    def test_decode(self):
+        self.assertEqual(encoding.decode("“café”".encode("cp1252")), ("“café”", 0))
+        self.assertEqual(encoding.decode(b"caf\xe9 \xef\xbf\xbd", "utf-8"), ("caf\ufffd \ufffd", 1))
+
+
+class FindDuplicatesTest(unittest.TestCase):
+
+    def test_kinds(self):
+        entries = [("color", "/ˈkʌlər/"), ("Nice", "/nis/"), ("colour", "/ˈkʌlə/, /ˈkʌlər/"), ("nice", "/naɪs/")]
+        self.assertEqual(find_duplicates("nice", "/nais/", entries),
+                         [("nice", "/naɪs/", "word"), ("Nice", "/nis/", "case")])
+        self.assertEqual(find_duplicates("colr", "ˈkʌlər", entries),
+                         [("color", "/ˈkʌlər/", "ipa"), ("colour", "/ˈkʌlə/, /ˈkʌlər/", "ipa")])
+        self.assertEqual(find_duplicates("color", "/ˈkʌlər/", entries, editing="color"),
+                         [("colour", "/ˈkʌlə/, /ˈkʌlər/", "ipa")])
+        self.assertEqual(find_duplicates("", "/nis/", entries), [])
+
+    def test_merge_variants(self):
+        self.assertEqual(phonemes.merge_variants("/a/, /b/", "/b/", "/c/"), "/a/, /b/, /c/")
+
+
+class FindReplaceTest(unittest.TestCase):
+
+    def test_plain(self):
+        entries = [("red", "/rɛd/"), ("cat", "/kæt/"), ("error", "/ˈɛrər/")]
+        self.assertEqual(find_replace(entries, "r", "ɹ"), [("red", "/rɛd/", "/ɹɛd/"), ("error", "/ˈɛrər/", "/ˈɛɹəɹ/")])
+        self.assertEqual(find_replace(entries, "", "ɹ"), [])
+        self.assertEqual(find_replace([("a", "/a.b/")], ".", "\\"), [("a", "/a.b/", "/a\\b/")])
+
+    def test_regex(self):
+        entries = [("red", "/rɛd/"), ("error", "/ˈɛrər/")]
+        self.assertEqual(find_replace(entries, r"r(?=/)", "ɹ", regex=True), [("error", "/ˈɛrər/", "/ˈɛrəɹ/")])
+        self.assertEqual(find_replace(entries, r"(ɛ)(d)", r"\2\1", regex=True), [("red", "/rɛd/", "/rdɛ/")])
+        with self.assertRaises(re.error):
+            find_replace(entries, "(", "", regex=True)
+
+
+class MarkCompoundTest(unittest.TestCase):
+
+    def setUp(self):
+        self.dictionary = Dictionary("en_GB", {
+            "bookshop": "/ˈbʊkʃɒp/", "book": "/bʊk/", "shop": "/ʃɒp/",
+            "sunshine": "/ˈsʌnˌʃaɪn/", "sun": "/sʌn/", "shine": "/ʃaɪn/",
+            "carpet": "/ˈkɑːpɪt/", "car": "/kɑː/", "pet": "/pɛt/",
+        })
+
+    def mark(self, word):
+        return mark_compound(word, self.dictionary.lookup(word), self.dictionary.lookup)
+
+    def test_compound(self):
+        self.assertEqual(self.mark("bookshop"), "/ˈbʊk+ʃɒp/")
+        self.assertEqual(self.mark("sunshine"), "/ˈsʌn+ˌʃaɪn/")
+
+    def test_parts_that_sound_different(self):
+        self.assertEqual(self.mark("carpet"), "/ˈkɑːpɪt/")
+
+    def test_boundaries_in_data_are_kept(self):
+        self.assertEqual(mark_compound("bookshop", "/ˈbʊ+kʃɒp/", self.dictionary.lookup), "/ˈbʊ+kʃɒp/")
+
+    def test_compose(self):
+        lookup = Dictionary("en_GB", {"well": "/wɛl/", "known": "/nəʊn/, /ˈnəʊən/", "book": "/bʊk/",
+                                      "shop": "/ʃɒp/"}).lookup
+        self.assertEqual(compose("well-known", lookup), ("/wɛl+nəʊn/", ["well", "known"]))
+        self.assertEqual(compose("shopbook", lookup), ("/ʃɒp+bʊk/", ["shop", "book"]))
+        self.assertEqual(compose("well-read", lookup), (None, None))
+        self.assertEqual(compose("books", lookup), (None, None))
+
+
+class PhonemesTest(unittest.TestCase):
+
+    def test_outside_inventory(self):
+        inventory = {"t", "a", "k", "i"}
+        self.assertEqual(phonemes.outside_inventory("/ˈtaːki/", inventory), [])
+        self.assertEqual(phonemes.outside_inventory("/ˈθaθo/", inventory), ["θ", "o"])
+
+    def test_syllables(self):
+        self.assertEqual(phonemes.syllables("/ˈbʌtər/"), [("ˈ", ["b", "ʌ"]), ("", ["t", "ə", "r"])])
+        self.assertEqual(phonemes.syllables("/ˈeɪprəl/"), [("ˈ", ["eɪ"]), ("", ["p", "r", "ə", "l"])])
+
+    def test_format_syllables(self):
+        self.assertEqual(phonemes.format_syllables("/prəˌnʌnsiˈeɪʃən/"), "/prəˌnʌn.siˈeɪ.ʃən/")
+        self.assertEqual(phonemes.format_syllables("/prəˌnʌnsiˈeɪʃən/", dots=False), "/prəˌnʌnsiˈeɪʃən/")
+        self.assertEqual(phonemes.format_syllables("/kæt/"), "/kæt/")
+        self.assertEqual(phonemes.format_syllables("/ðə ˈkætər/"), "/ðə ˈkæ.tər/")
+        self.assertEqual(phonemes.format_syllables("/ˈbʊk+ʃɒp/"), "/ˈbʊk+ʃɒp/")
+
+    def test_morpheme_boundary_is_not_a_sound(self):
+        self.assertEqual(phonemes.split("/ˈbʊk+ʃɒp/"), ["b", "ʊ", "k", "ʃ", "ɒ", "p"])
+        self.assertEqual(phonemes.strip_boundaries("/ˈbʊk+ʃɒp/"), "/ˈbʊkʃɒp/")
+
+    def test_base(self):
+        self.assertEqual(phonemes.base("iː"), "i")
+        self.assertEqual(phonemes.base("t͡ʃ"), "tʃ")
+
+    def test_inventory(self):
+        self.assertEqual(phonemes.inventory(["/kæt/", "/tiː/, /ti/"]), {"k", "æ", "t", "iː", "i"})
+
+    def test_tokens_keep_everything(self):
+        pieces = phonemes.tokens("/ˈtʃiːz/")
+        self.assertEqual("".join(text for text, _is_sound in pieces), "/ˈtʃiːz/")
+        self.assertEqual([text for text, is_sound in pieces if is_sound], ["tʃ", "iː", "z"])
+
+    def test_split_keeps_multi_char_sounds(self):
+        self.assertEqual(phonemes.split("/ˈfɑːðə/"), ["f", "ɑː", "ð", "ə"])
+        self.assertEqual(phonemes.split("/ˈteɪk/"), ["t", "eɪ", "k"])
+
+    def test_split_keeps_diacritics_and_tie_bars(self):
+        self.assertEqual(phonemes.split("/kʰæ̃t/"), ["kʰ", "æ̃", "t"])
+        self.assertEqual(phonemes.split("/d͡ʒɪn/"), ["d͡ʒ", "ɪ", "n"])
+
+    def test_distance_counts_sounds_not_characters(self):
+        self.assertEqual(phonemes.distance("/ˈʃiːp/", "ʃiːp"), 0)
+        self.assertEqual(phonemes.distance("/ʃiːp/", "/ʃɪp/"), 1)
+        self.assertEqual(phonemes.distance("/kæt/", "/kæts/"), 1)
+
+    def test_align_marks_wrong_segments(self):
+        self.assertEqual(phonemes.align("/ʃiːp/", "/sip/"),
+                         [("ʃ", "s"), ("iː", "i"), ("p", "p")])
+        self.assertEqual(phonemes.align("/kæt/", "/kt/"),
+                         [("k", "k"), ("æ", None), ("t", "t")])
+
+    def test_align_cells_joins_extra_sounds(self):
+        self.assertEqual(phonemes.align_cells("/kæt/", "/kæts/"), ["k", "æ", "ts"])
+        self.assertEqual(phonemes.align_cells("/kæt/", "/skæt/"), ["sk", "æ", "t"])
+        self.assertEqual(phonemes.align_cells("/kæt/", "/kt/"), ["k", None, "t"])
+
+    def test_shared_sounds(self):
+        self.assertEqual(phonemes.shared_sounds(["/kæt/", "/bæt/", "/tæp/"]), {"æ", "t"})
+        self.assertEqual(phonemes.shared_sounds([]), set())
+
+    def test_variants(self):
+        self.assertEqual(phonemes.variants("/ˈeɪ/, /ə/"), ["/ˈeɪ/", "/ə/"])
+
+
+class RespellingTest(unittest.TestCase):
+
+    def test_stressed_syllable_in_capitals(self):
+        self.assertEqual(respelling.respell("/prəˌnʌnsiˈeɪʃən/"), "pruh-nun-see-AY-shun")
+
+    def test_r_coloured_vowels(self):
+        self.assertEqual(respelling.respell("/ˈbʌtər/"), "BUH-ter")
+
+    def test_only_first_variant(self):
+        self.assertEqual(respelling.respell("/kæt/, /kɑt/"), "kat")
+
+
+class NormalizeTest(unittest.TestCase):
+
+    def test_numbers(self):
+        self.assertEqual(normalize.expand("42", "en_US"), "forty two")
+        self.assertEqual(normalize.expand("1,005", "en_UK"), "one thousand five")
+        self.assertEqual(normalize.expand("21st", "en_US"), "twenty first")
+        self.assertEqual(normalize.expand("42", "de"), "zwei und vierzig")
+        self.assertEqual(normalize.expand("21000", "es_ES"), "veintiún mil")
+        self.assertEqual(normalize.expand("71", "fr_FR"), "soixante et onze")
+        self.assertEqual(normalize.expand("42", "ja"), "42")
+        self.assertEqual(normalize.expand(str(10 ** 9), "en_US"), str(10 ** 9))
+
+    def test_abbreviations(self):
+        self.assertEqual(normalize.expand("Dr. Smith", "en_US"), "doctor Smith")
+        self.assertEqual(normalize.expand("z.B.", "de"), "zum Beispiel")
+        self.assertEqual(normalize.expand("cat", "en_US"), "cat")
+
+    def test_lookup_expanded(self):
+        dictionary = Dictionary("en_US", {"forty": "/ˈfɔɹti/", "two": "/ˈtu/, /tə/", "doctor": "/ˈdɑktɚ/"})
+        self.assertEqual(normalize.lookup_expanded("42", "en_US", [dictionary]), ("/ˈfɔɹti ˈtu/", "forty two"))
+        self.assertEqual(normalize.lookup_expanded("Dr.", "en_US", [dictionary]), ("/ˈdɑktɚ/", "doctor"))
+        self.assertEqual(normalize.lookup_expanded("43", "en_US", [dictionary]), (None, None))
+        self.assertEqual(normalize.lookup_expanded("two", "en_US", [dictionary]), (None, None))
+
+
+class OrthographyTest(unittest.TestCase):
+
+    def test_longest_letters_win(self):
+        rules = orthography.parse_rules("# consonants\ns = s\nsh → ʃ\nh = ∅\n\na = a\ni = i")
+        self.assertEqual(orthography.transcribe("Shahi", rules), ("/ʃai/", []))
+        self.assertEqual(orthography.transcribe("sasha-ix", rules), ("/saʃa i/", ["x"]))
+
+    def test_errors(self):
+        with self.assertRaises(orthography.RuleError) as raised:
+            orthography.parse_rules("a = a\nb")
+        self.assertEqual(raised.exception.line, 2)
+
+
+class CleanInputTest(unittest.TestCase):
+
+    def test_trims_and_collapses_whitespace(self):
+        self.assertEqual(clean_input("  ice \t cream \n"), ("ice cream", None))
+
+    def test_rejects_empty_input(self):
+        self.assertEqual(clean_input(" \t "), ("", "empty"))
+
+    def test_rejects_control_characters(self):
+        self.assertEqual(clean_input("ca\x00t")[1], "control")
+
+    def test_rejects_emoji_only(self):
+        self.assertEqual(clean_input("🐈 🎉")[1], "no-letters")
+        self.assertIsNone(clean_input("café")[1])
+
+
+class RhymeTest(unittest.TestCase):
+
+    def test_rhyme_key_starts_at_stressed_vowel(self):
+        self.assertEqual(phonemes.rhyme_key("/ɪnˈsaɪt/"), "aɪt")
+        self.assertEqual(phonemes.rhyme_key("/ˈneɪʃən/"), "eɪʃən")
+
+    def test_rhyme_key_without_stress_uses_last_vowel(self):
+        self.assertEqual(phonemes.rhyme_key("/kæt/"), "æt")
+        self.assertIsNone(phonemes.rhyme_key("/ʃ/"))
+
+    def test_rhymes(self):
+        entries = {"cat": "/kæt/", "hat": "/hæt/", "hot": "/hɑt/", "at's": "/æts/"}
+        self.assertEqual(Dictionary("en_US", entries).rhymes("cat"), [("hat", "/hæt/")])
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "en_US.db")
+            compile_dictionary(Dictionary("en_US", entries), path)
+            self.assertEqual(CompiledDictionary("en_US", path).rhymes("cat"), [("hat", "/hæt/")])
+
+
+class MinimalPairTest(unittest.TestCase):
+
+    ENTRIES = {"ship": "/ʃɪp/", "sheep": "/ʃiːp/", "shop": "/ʃɑp/",
+               "bit": "/bɪt/", "beat": "/biːt/", "bits": "/bɪts/"}
+
+    def test_minimal_pair(self):
+        self.assertEqual(phonemes.minimal_pair("/ʃɪp/", "/ʃiːp/"), ("ɪ", "iː"))
+        self.assertIsNone(phonemes.minimal_pair("/bɪt/", "/bɪts/"))
+        self.assertIsNone(phonemes.minimal_pair("/bɪt/", "/biːd/"))
+
+    def test_minimal_pairs_of_word(self):
+        pairs = Dictionary("en_US", self.ENTRIES).minimal_pairs("ship")
+        self.assertEqual(pairs, [("sheep", "/ʃiːp/", ("ɪ", "iː")), ("shop", "/ʃɑp/", ("ɪ", "ɑ"))])
+
+    def test_closest_pairs_within_limit(self):
+        # "shop" comes first in the dictionary but "sheep" is closer
+        entries = {"shop": "/ʃɑp/", "ship": "/ʃɪp/", "sheep": "/ʃiːp/"}
+        self.assertEqual(Dictionary("en_US", entries).minimal_pairs("ship", limit=1),
+                         [("sheep", "/ʃiːp/", ("ɪ", "iː"))])
+
+    def test_sound_pairs(self):
+        expected = [(("ship", "/ʃɪp/"), ("sheep", "/ʃiːp/")), (("bit", "/bɪt/"), ("beat", "/biːt/"))]
+        self.assertEqual(Dictionary("en_US", self.ENTRIES).sound_pairs("ɪ", "iː"), expected)
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "en_US.db")
+            compile_dictionary(Dictionary("en_US", self.ENTRIES), path)
+            self.assertEqual(sorted(CompiledDictionary("en_US", path).sound_pairs("ɪ", "iː")),
+                             sorted(expected))
+
+
+class SimilarityTest(unittest.TestCase):
+
+    def test_sound_distance_follows_features(self):
+        self.assertEqual(similarity.sound_distance("p", "p"), 0)
+        self.assertLess(similarity.sound_distance("p", "b"), similarity.sound_distance("p", "s"))
+        self.assertLess(similarity.sound_distance("ɪ", "iː"), similarity.sound_distance("ɪ", "ɑ"))
+        self.assertEqual(similarity.sound_distance("a", "p"), 1)
+        self.assertEqual(similarity.sound_distance("☃", "☃"), 0)
+
+    def test_features(self):
+        self.assertEqual(similarity.features("b"), {"voiced": True, "place": "bilabial", "manner": "plosive"})
+        self.assertEqual(similarity.features("uː"),
+                         {"height": "close", "backness": "back", "rounded": True, "long": True})
+        self.assertEqual(similarity.features("aɪ"), {"diphthong": True})
+        self.assertEqual(similarity.features("☃"), {})
+
+    def test_features_match_lookup_table(self):
+        path = os.path.join(os.path.dirname(__file__), os.pardir, "src", "dicts", "ipa_lookup_table.json")
+        with open(path, encoding="utf-8") as f:
+            entries = [entry for entry in json.load(f) if "place" in entry]
+        for entry in entries:
+            with self.subTest(entry["symbol"]):
+                expected = {key: entry[key] for key in ("voiced", "place", "manner")}
+                if expected["place"] == "labial-velar":
+                    expected["place"] = "bilabial"
+                self.assertEqual(similarity.features(entry["symbol"]), expected)
+
+    def test_weights(self):
+        self.assertEqual(similarity.sound_distance("i", "iː", weights={"length": 0}), 0)
+        self.assertGreater(similarity.sound_distance("p", "b", weights={"voicing": 5}),
+                           similarity.sound_distance("p", "b"))
+
+    def test_similarity(self):
+        self.assertEqual(similarity.similarity("/kæt/", "/ˈkæt/"), 1)
+        self.assertEqual(similarity.similarity("", ""), 1)
+        self.assertGreater(similarity.similarity("/ʃɪp/", "/ʃiːp/"), similarity.similarity("/ʃɪp/", "/kæt/"))
+        self.assertEqual(similarity.distance("/kæt/", "/kæts/"), 1)
+
+    def test_sound_alikes(self):
+        entries = {"ship": "/ʃɪp/", "sheep": "/ʃiːp/", "shop": "/ʃɑp/", "cat": "/kæt/"}
+        alikes = Dictionary("en_US", entries).sound_alikes("ship")
+        self.assertEqual(alikes[0][0], "sheep")
+        self.assertEqual([word for word, _ipa, _score in Dictionary("en_US", entries).sound_alikes(
+            "ship", threshold=0.9)], ["sheep"])
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "en_US.db")
+            compile_dictionary(Dictionary("en_US", entries), path)
+            self.assertEqual(CompiledDictionary("en_US", path).sound_alikes("ship"), alikes)
+
+
+class WikitextTest(unittest.TestCase):
+
+    PAGE = """==English==
+===Pronunciation===
+* {{IPA|en|/ˈtɒm.ə.təʊ/|a=RP}}
+* {{IPA|en|/təˈmeɪ.toʊ/|[təˈmeɪ.ɾoʊ]|a=GA}}
+
+==Spanish==
+===Pronunciation===
+* {{IPA|es|/toˈmate/}}
+"""
+
+    def test_find_ipa_of_language(self):
+        self.assertEqual(wikitext.find_ipa(self.PAGE, "es_ES"), "/toˈmate/")
+        self.assertIsNone(wikitext.find_ipa(self.PAGE, "de"))
+        self.assertIsNone(wikitext.find_ipa("==English==\nno pronunciation", "en_US"))
+
+    def test_find_ipa_prefers_accent(self):
+        self.assertEqual(wikitext.find_ipa(self.PAGE, "en_US"), "/təˈmeɪ.toʊ/")
+        self.assertEqual(wikitext.find_ipa(self.PAGE, "en_UK"), "/ˈtɒm.ə.təʊ/")
+
+    def test_find_ipa_skips_empty_arguments(self):
+        self.assertEqual(wikitext.find_ipa("==English==\n* {{IPA|en||/ə/}}", "en_US"), "/ə/")
+        self.assertIsNone(wikitext.find_ipa("==English==\n* {{IPA|en||}}", "en_US"))
+
+
+class ExamplesTest(unittest.TestCase):
+
+    ENTRIES = {"ship": "/ʃɪp/", "shipping": "/ˈʃɪpɪŋ/", "is": "/ɪz/",
+               "sheep": "/ʃiːp/", "fish": "/fɪʃ/"}
+
+    def test_examples_prefers_short_words(self):
+        expected = [("fish", "/fɪʃ/"), ("ship", "/ʃɪp/")]
+        self.assertEqual(Dictionary("en_US", self.ENTRIES).examples("ɪ", 2), expected)
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, "en_US.db")
+            compile_dictionary(Dictionary("en_US", self.ENTRIES), path)
+            self.assertEqual(CompiledDictionary("en_US", path).examples("ɪ", 2), expected)
+
+    def test_examples_match_whole_sounds(self):
+        self.assertEqual(Dictionary("en_US", self.ENTRIES).examples("i"), [])
+
+
+class NotationTest(unittest.TestCase):
+
+    def test_normalize(self):
+        decomposed = "/ka\u0303/"
+        self.assertEqual(notation.normalize(decomposed, "NFC"), "/k\u00e3/")
+        self.assertEqual(notation.normalize("/k\u00e3/", "NFD"), decomposed)
+        self.assertEqual(notation.normalize(decomposed, "keep"), decomposed)
+
+    def test_unknown_symbols(self):
+        self.assertEqual(phonemes.unknown_symbols("/ˈkʰæ̃t/, /kæt˥/"), [])
+        self.assertEqual(phonemes.unknown_symbols("/k@t3@/"), ["@", "3"])
+
+    def test_aliases(self):
+        self.assertEqual(notation.canonicalize("/ʤæm/, /ɷd/"), "/d͡ʒæm/, /ʊd/")
+        self.assertEqual(notation.former_symbols("dʒ"), ["ʤ"])
+        self.assertEqual(notation.former_symbols("d͡ʒ"), ["ʤ"])
+        self.assertEqual(notation.former_symbols("p"), [])
+
+    def test_convert_rewrites_sounds(self):
+        self.assertEqual(notation.convert("/ˈɹɛd/", "wells"), "/ˈred/")
+        self.assertEqual(notation.convert("/bed/", "merriam-webster"), "/bɛd/")
+
+    def test_convert_keeps_multi_char_sounds(self):
+        self.assertEqual(notation.convert("/feɪs/", "merriam-webster"), "/feɪs/")
+
+    def test_unknown_profile_keeps_transcription(self):
+        self.assertEqual(notation.convert("/ɹɛd/", ""), "/ɹɛd/")
+
+    def test_profiles_for(self):
+        self.assertIn("wells", notation.profiles_for("en_US"))
+        self.assertEqual(notation.profiles_for("de"), [])
+
+    def test_optional_segments(self):
+        self.assertEqual(notation.optional_segments("/ˈɒf(ə)n/"),
+                         [("/ˈɒf", False), ("ə", True), ("n/", False)])
+
+    def test_format_optional(self):
+        self.assertEqual(notation.format_optional("/ˈɒf(ə)n/", "keep"), "/ˈɒf(ə)n/")
+        self.assertEqual(notation.format_optional("/ˈɒf(ə)n/", "with"), "/ˈɒfən/")
+        self.assertEqual(notation.format_optional("/ˈɒf(ə)n/", "without"), "/ˈɒfn/")
+        self.assertEqual(notation.format_optional("/ˈɒf(ə)n/, /ˈɔf(ə)n/", "both"),
+                         "/ˈɒfən/, /ˈɒfn/, /ˈɔfən/, /ˈɔfn/")
+
+
+class SoundChangeTest(unittest.TestCase):
+
+    def test_environment(self):
+        flapping = sound_change.parse_rule("t → ɾ / V_V")
+        self.assertEqual(sound_change.apply_rule(flapping, "/ˈwɔtəɹ/"), "/ˈwɔɾəɹ/")
+        self.assertEqual(sound_change.apply_rule(flapping, "/ˈtɔp/"), "/ˈtɔp/")
+        final = sound_change.parse_rule("ə > 0 / _#")
+        self.assertEqual(sound_change.apply_rule(final, "/ˈbɛtə/, /ˈəbɛtə/"), "/ˈbɛt/, /ˈəbɛt/")
+
+    def test_lists_and_order(self):
+        rules = sound_change.parse_rules("p t k -> b d ɡ / V_V\n\n# then\nd → ð")
+        self.assertEqual(sound_change.apply_rules(rules, "/ˈæpətaɪt/"), "/ˈæbəðaɪt/")
+
+    def test_errors(self):
+        for rule, reason in [("t ɾ", "no-arrow"), ("p t → b d ɡ", "count-mismatch"),
+                             ("t → ɾ / VV", "environment"), ("t → ɾ / V#_", "boundary-position")]:
+            with self.assertRaises(sound_change.RuleError) as raised:
+                sound_change.parse_rule(rule)
+            self.assertEqual(raised.exception.reason, reason)
+        with self.assertRaises(sound_change.RuleError) as raised:
+            sound_change.parse_rules("t → d\nt")
+        self.assertEqual(raised.exception.line, 2)
+
+
+class CollationTest(unittest.TestCase):
+
+    def test_accents_sort_with_their_letter(self):
+        self.assertEqual(collation.sort_words(["zèbre", "été", "eau", "fée"], "fr_FR"),
+                         ["eau", "été", "fée", "zèbre"])
+
+    def test_case_is_ignored(self):
+        self.assertEqual(collation.sort_words(["banana", "Apple", "cherry"], "en_US"),
+                         ["Apple", "banana", "cherry"])
+
+    def test_cyrillic(self):
+        self.assertEqual(collation.sort_words(["яблоко", "арбуз", "дом"], "ru"),
+                         ["арбуз", "дом", "яблоко"])
+
+    def test_items_sorted_by_word(self):
+        self.assertEqual(collation.sort_words([("ñu", "/ˈɲu/"), ("nube", "/ˈnuβe/"), ("oso", "/ˈoso/")],
+                                              "es_ES", word=lambda item: item[0]),
+                         [("nube", "/ˈnuβe/"), ("ñu", "/ˈɲu/"), ("oso", "/ˈoso/")])
+
+
+if __name__ == '__main__':
+    unittest.main()
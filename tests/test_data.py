@@ -0,0 +1,212 @@
+# test_data.py
+#
+# Copyright 2025 mohfy
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+#
+# SPDX-License-Identifier: GPL-3.0-or-later
+
+"""Tests of how the app keeps the user's data, against a temporary
+XDG_DATA_HOME and an in-memory settings backend. They need PyGObject
+with GLib and Gio and are skipped without it.
+"""
+
+import datetime
+import importlib.util
+import json
+import os
+import shutil
+import subprocess
+import sys
+import tempfile
+import unittest
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+TMP = tempfile.mkdtemp()
+# read by GLib the first time it is asked, so set before anything imports it
+os.environ["XDG_DATA_HOME"] = os.path.join(TMP, "data")
+os.environ["GSETTINGS_SCHEMA_DIR"] = os.path.join(TMP, "schemas")
+os.environ["GSETTINGS_BACKEND"] = "memory"
+
+try:
+    import gi
+    gi.require_version('Gio', '2.0')
+    from gi.repository import Gio
+    os.makedirs(os.environ["GSETTINGS_SCHEMA_DIR"])
+    subprocess.run(["glib-compile-schemas", "--targetdir", os.environ["GSETTINGS_SCHEMA_DIR"],
+                    os.path.join(ROOT, "data")], check=True, capture_output=True)
+except (ImportError, ValueError, OSError, subprocess.CalledProcessError):
+    Gio = None
+else:
+    # the app's modules use relative imports, so load src as its package
+    spec = importlib.util.spec_from_file_location("word2ipa", os.path.join(ROOT, "src", "__init__.py"),
+                                                  submodule_search_locations=[os.path.join(ROOT, "src")])
+    sys.modules["word2ipa"] = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(sys.modules["word2ipa"])
+    from word2ipa import backups, favorites, history, pipe, storage, userdict
+
+
+def tearDownModule():
+    shutil.rmtree(TMP, ignore_errors=True)
+
+
+@unittest.skipIf(Gio is None, "needs PyGObject with GLib and Gio")
+class DataTestCase(unittest.TestCase):
+    """Starts every test with an empty data dir and default settings."""
+
+    def setUp(self):
+        shutil.rmtree(storage.data_dir(), ignore_errors=True)
+        self.settings = Gio.Settings.new('io.github.mohfy.word2ipa')
+        # the singletons would keep what the previous test loaded
+        userdict.UserDictionary._default = None
+        favorites.Favorites._default = None
+        history.History._default = None
+
+    def tearDown(self):
+        for key in self.settings.props.settings_schema.list_keys():
+            self.settings.reset(key)
+
+
+class HistoryTest(DataTestCase):
+
+    def test_insert_item(self):
+        items = []
+        for word in ["cat", "dog", "cat"]:
+            history.insert_item(items, history.HistoryItem(word=word, ipa="", lang="en_US"))
+        self.assertEqual([(item.word, item.count) for item in items], [("cat", 2), ("dog", 1)])
+        history.insert_item(items, history.HistoryItem(word="cat", ipa="", lang="de_DE"))
+        self.assertEqual(len(items), 3)
+
+    def test_add_and_prune(self):
+        self.settings.set_int('history-limit', 2)
+        lookups = history.History.get_default()
+        for word in ["cat", "dog", "cat", "cow"]:
+            lookups.add(word, f"/{word}/", "en_US")
+        self.assertEqual([(item.word, item.count) for item in lookups], [("cow", 1), ("cat", 2)])
+        self.settings.set_int('history-limit', 1)
+        self.assertEqual([item.word for item in lookups], ["cow"])
+
+    def test_journal_replay_after_crash(self):
+        lookups = history.History.get_default()
+        lookups.add("cat", "/kæt/", "en_US")
+        lookups.add("dog", "/dɔɡ/", "en_US")
+        lookups.remove(lookups.store.get_item(0))
+        # nothing flushed yet, the journal is all there is
+        self.assertEqual(storage.load_json(history.HISTORY_FILE, []), [])
+        with open(storage.data_path(f'{history.HISTORY_FILE}.journal'), 'a', encoding='utf-8') as f:
+            f.write('{"op": "add", "item": {"word": "cow"')
+        # a new instance after a crash
+        history.History._default = None
+        lookups = history.History.get_default()
+        self.assertEqual([item.word for item in lookups], ["cat"])
+        self.assertEqual([entry["word"] for entry in storage.load_json(history.HISTORY_FILE, [])], ["cat"])
+        self.assertFalse(os.path.exists(storage.data_path(f'{history.HISTORY_FILE}.journal')))
+
+
+class EntryInfoTest(DataTestCase):
+
+    def test_tags(self):
+        user_dict = userdict.UserDictionary.get_default()
+        user_dict.set("en_US", "cat", "/kæt/")
+        user_dict.set_tags("en_US", "cat", ["animal", "A1", "animal"])
+        self.assertEqual(userdict.UserEntry("cat", "/kæt/", user_dict.info("en_US", "cat")).tags, "animal, A1")
+        # kept when the entry is renamed
+        user_dict.set("en_US", "cats", "/kæts/", old_word="cat")
+        self.assertEqual(user_dict.info("en_US", "cats")["tags"], ["animal", "A1"])
+        user_dict.set_tags("en_US", "cats", [])
+        self.assertNotIn("tags", user_dict.info("en_US", "cats"))
+
+
+class TrashTest(DataTestCase):
+
+    def test_expiry(self):
+        today = datetime.date.today()
+        old = today - datetime.timedelta(days=userdict.KEEP_DELETED_DAYS + 1)
+        recent = today - datetime.timedelta(days=userdict.KEEP_DELETED_DAYS - 1)
+        storage.save_json(userdict.DELETED_FILE, {
+            "en_US": {"cat": {"ipa": "/kæt/", "deleted": old.isoformat()},
+                      "dog": {"ipa": "/dɔɡ/", "deleted": recent.isoformat()}},
+            "de_DE": {"Hund": {"ipa": "/hʊnt/", "deleted": old.isoformat()}},
+        })
+        user_dict = userdict.UserDictionary.get_default()
+        self.assertEqual(user_dict.deleted_entries("en_US"), [("dog", "/dɔɡ/", recent)])
+        self.assertEqual(storage.load_json(userdict.DELETED_FILE, {}),
+                         {"en_US": {"dog": {"ipa": "/dɔɡ/", "deleted": recent.isoformat()}}})
+
+    def test_remove_and_restore(self):
+        user_dict = userdict.UserDictionary.get_default()
+        user_dict.set("en_US", "cat", "/kæt/")
+        user_dict.remove("en_US", "cat")
+        self.assertIsNone(user_dict.lookup("en_US", "cat"))
+        self.assertEqual([entry[0] for entry in user_dict.deleted_entries("en_US")], ["cat"])
+        user_dict.restore_deleted("en_US", "cat")
+        self.assertEqual(user_dict.lookup("en_US", "cat"), "/kæt/")
+        self.assertEqual(user_dict.deleted_entries("en_US"), [])
+
+
+class PipeTest(DataTestCase):
+
+    def test_every_word_has_a_line(self):
+        self.assertEqual(list(pipe.words(["hello world\n", "42 …\n"])),
+                         [("hello", True), ("world", True), ("42", False), ("…", False)])
+
+
+class BackupTest(DataTestCase):
+
+    def make_old_backup(self, name, entries):
+        path = os.path.join(backups.backups_dir(), name)
+        os.makedirs(path)
+        with open(os.path.join(path, userdict.USER_DICT_FILE), 'w', encoding='utf-8') as f:
+            json.dump(entries, f)
+
+    def test_restore(self):
+        self.make_old_backup("20250101-000000", {"en_US": {"cat": "/kæt/"}})
+        storage.save_json(userdict.USER_DICT_FILE, {"en_US": {"dog": "/dɔɡ/"}})
+        storage.save_json(favorites.FAVORITES_FILE, [])
+        self.assertEqual(backups.differences("20250101-000000")["added"], [("entry", "en_US", "cat", "/kæt/")])
+        backups.restore("20250101-000000")
+        self.assertEqual(storage.load_json(userdict.USER_DICT_FILE, {}), {"en_US": {"cat": "/kæt/"}})
+        # not in the backup
+        self.assertFalse(os.path.exists(storage.data_path(favorites.FAVORITES_FILE)))
+        self.assertEqual(userdict.UserDictionary.get_default().lookup("en_US", "cat"), "/kæt/")
+        # the data before the restore was backed up
+        self.assertEqual(len(backups.list_backups()), 2)
+
+    def test_spelling_rules(self):
+        self.make_old_backup("20250101-000000", {})
+        with open(os.path.join(backups.backups_dir(), "20250101-000000", userdict.SPELLING_RULES_FILE), 'w',
+                  encoding='utf-8') as f:
+            json.dump({"x_tok": "t = t\no = o"}, f)
+        userdict.UserDictionary.get_default().set_spelling_rules("x_tok", "t = t\no = ɔ")
+        self.assertEqual(backups.differences("20250101-000000")["changed"], [("rule", "x_tok", "o", "ɔ", "o")])
+        backups.restore("20250101-000000")
+        self.assertEqual(userdict.UserDictionary.get_default().spell("x_tok", "toto"), ("/toto/", []))
+
+    def test_restore_oldest_backup_at_the_limit(self):
+        self.settings.set_int('backup-count', 2)
+        self.make_old_backup("20250101-000000", {"en_US": {"cat": "/kæt/"}})
+        self.make_old_backup("20250102-000000", {"en_US": {"cow": "/kaʊ/"}})
+        storage.save_json(userdict.USER_DICT_FILE, {"en_US": {"dog": "/dɔɡ/"}})
+        backups.restore("20250101-000000")
+        self.assertEqual(storage.load_json(userdict.USER_DICT_FILE, {}), {"en_US": {"cat": "/kæt/"}})
+
+    def test_restore_missing_backup(self):
+        storage.save_json(userdict.USER_DICT_FILE, {"en_US": {"dog": "/dɔɡ/"}})
+        with self.assertRaises(FileNotFoundError):
+            backups.restore("20250101-000000")
+        self.assertEqual(storage.load_json(userdict.USER_DICT_FILE, {}), {"en_US": {"dog": "/dɔɡ/"}})
+
+
+if __name__ == '__main__':
+    unittest.main()